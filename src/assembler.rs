@@ -0,0 +1,883 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    cpu::{Byte, CpuVariant, Word},
+    instructions::{defined_opcodes, AddressingMode},
+    loader::{Image, Segment},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssemblyErrorKind {
+    UnexpectedCharacter(char),
+    InvalidNumber,
+    ExpectedExpression,
+    ExpectedEndOfLine,
+    ExpectedClosingParenthesis,
+    ExpectedIndexRegister,
+    UnknownMnemonic(String),
+    UnknownDirective(String),
+    UnsupportedAddressingMode(String),
+    UndefinedLabel(String),
+    DuplicateLabel(String),
+    ValueOutOfRange(i32),
+    BranchOutOfRange(i32),
+    MissingOrigin,
+    PastEndOfMemory,
+}
+
+/// An error at a 1-based line and column of the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssemblyError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: AssemblyErrorKind,
+}
+
+impl Display for AssemblyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            AssemblyErrorKind::UnexpectedCharacter(character) => {
+                write!(f, "unexpected character '{}'", character)
+            }
+            AssemblyErrorKind::InvalidNumber => write!(f, "invalid number"),
+            AssemblyErrorKind::ExpectedExpression => write!(f, "expected an expression"),
+            AssemblyErrorKind::ExpectedEndOfLine => write!(f, "expected end of line"),
+            AssemblyErrorKind::ExpectedClosingParenthesis => write!(f, "expected ')'"),
+            AssemblyErrorKind::ExpectedIndexRegister => write!(f, "expected X or Y"),
+            AssemblyErrorKind::UnknownMnemonic(name) => write!(f, "unknown mnemonic '{}'", name),
+            AssemblyErrorKind::UnknownDirective(name) => write!(f, "unknown directive '{}'", name),
+            AssemblyErrorKind::UnsupportedAddressingMode(name) => {
+                write!(f, "addressing mode not supported by {}", name)
+            }
+            AssemblyErrorKind::UndefinedLabel(name) => write!(f, "undefined label '{}'", name),
+            AssemblyErrorKind::DuplicateLabel(name) => {
+                write!(f, "label '{}' already defined", name)
+            }
+            AssemblyErrorKind::ValueOutOfRange(value) => write!(f, "value {} out of range", value),
+            AssemblyErrorKind::BranchOutOfRange(offset) => {
+                write!(f, "branch target {} bytes away", offset)
+            }
+            AssemblyErrorKind::MissingOrigin => write!(f, "code before the first '*=' origin"),
+            AssemblyErrorKind::PastEndOfMemory => write!(f, "code runs past $FFFF"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Identifier(String),
+    Directive(String),
+    Number(i32),
+    Hash,
+    LeftParenthesis,
+    RightParenthesis,
+    Comma,
+    Colon,
+    Less,
+    Greater,
+    Plus,
+    Minus,
+    Star,
+    Equals,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    column: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Expression {
+    Number(i32),
+    Label { name: String, column: usize },
+    CurrentAddress,
+    LowByte(Box<Expression>),
+    HighByte(Box<Expression>),
+    Negate(Box<Expression>),
+    Add(Box<Expression>, Box<Expression>),
+    Subtract(Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    None,
+    Accumulator,
+    Immediate(Expression),
+    Direct(Expression),
+    IndexedX(Expression),
+    IndexedY(Expression),
+    Indirect(Expression),
+    IndexedIndirect(Expression),
+    IndirectIndexed(Expression),
+    ZeroPageRelative(Expression, Expression),
+}
+
+#[derive(Debug, Clone)]
+enum StatementKind {
+    Origin(Expression),
+    Instruction { mnemonic: String, operand: Operand },
+    Bytes(Vec<Expression>),
+    Words(Vec<Expression>),
+}
+
+#[derive(Debug, Clone)]
+struct Statement {
+    line: usize,
+    column: usize,
+    label: Option<(String, usize)>,
+    kind: Option<StatementKind>,
+}
+
+/// One source line with the address it was assembled at and the bytes it produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListingLine {
+    pub line: usize,
+    pub address: Option<Word>,
+    pub bytes: Vec<Byte>,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assembly {
+    pub image: Image,
+    pub labels: HashMap<String, Word>,
+    pub listing: Vec<ListingLine>,
+}
+
+impl Assembly {
+    /// The program as a PRG file, a load address followed by every byte from the lowest
+    /// to the highest address assembled, with gaps between origins zero filled.
+    pub fn to_prg(&self) -> Vec<Byte> {
+        let start = self
+            .image
+            .segments
+            .iter()
+            .map(|segment| segment.address)
+            .min();
+        let Some(start) = start else {
+            return Vec::new();
+        };
+        let end = self
+            .image
+            .segments
+            .iter()
+            .map(|segment| segment.address as usize + segment.data.len())
+            .max()
+            .unwrap();
+        let mut bytes = start.to_le_bytes().to_vec();
+        bytes.resize(2 + end - start as usize, 0);
+        for segment in &self.image.segments {
+            let offset = 2 + (segment.address - start) as usize;
+            bytes[offset..offset + segment.data.len()].copy_from_slice(&segment.data);
+        }
+        bytes
+    }
+
+    pub fn listing_text(&self) -> String {
+        let mut text = String::new();
+        for line in &self.listing {
+            let address = match line.address {
+                Some(address) => format!("{:04X}", address),
+                None => String::new(),
+            };
+            let bytes: Vec<String> = line
+                .bytes
+                .iter()
+                .map(|byte| format!("{:02X}", byte))
+                .collect();
+            let row = format!(
+                "{:5} {:4}  {:<8}  {}",
+                line.line,
+                address,
+                bytes.join(" "),
+                line.source
+            );
+            text += row.trim_end();
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// A two pass assembler for the `.ms` dialect:
+///
+/// ```text
+/// *=$1000             ; origin
+/// start               ; bare label, `start:` works too
+///     lda #<table     ; `<` and `>` take the low and high byte
+///     sta $10
+///     dbg #$8883      ; the custom graphics instruction
+///     jmp start
+/// table .byte 1, 2, $FF
+///     .word start, table+2
+/// ```
+///
+/// Numbers are decimal, `$hex` or `%binary`, `*` is the address of the current line.
+/// Zero page addressing is picked when the operand is known to fit on the first pass.
+pub struct Assembler {
+    opcodes: HashMap<&'static str, Vec<(AddressingMode, Byte)>>,
+}
+
+impl Assembler {
+    pub fn new(variant: CpuVariant) -> Assembler {
+        let mut opcodes: HashMap<&'static str, Vec<(AddressingMode, Byte)>> = HashMap::new();
+        for entry in defined_opcodes(variant, false) {
+            opcodes
+                .entry(entry.mnemonic)
+                .or_default()
+                .push((entry.mode, entry.opcode));
+        }
+        Assembler { opcodes }
+    }
+
+    pub fn assemble(&self, source: &str) -> Result<Assembly, AssemblyError> {
+        let statements = source
+            .lines()
+            .enumerate()
+            .map(|(index, text)| self.parse_line(index + 1, text))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // First pass, place labels and decide on every instruction's addressing mode
+        let mut labels = HashMap::new();
+        let mut encodings = Vec::with_capacity(statements.len());
+        // Kept wider than a Word so code ending at $FFFF doesn't wrap around to $0000
+        let mut address: Option<usize> = None;
+        for statement in &statements {
+            if let Some((name, column)) = &statement.label {
+                let error = |kind| AssemblyError {
+                    line: statement.line,
+                    column: *column,
+                    kind,
+                };
+                let label_address = match address {
+                    Some(address) => Word::try_from(address)
+                        .map_err(|_| error(AssemblyErrorKind::PastEndOfMemory))?,
+                    None => return Err(error(AssemblyErrorKind::MissingOrigin)),
+                };
+                if labels.insert(name.clone(), label_address).is_some() {
+                    return Err(AssemblyError {
+                        line: statement.line,
+                        column: *column,
+                        kind: AssemblyErrorKind::DuplicateLabel(name.clone()),
+                    });
+                }
+            }
+            let encoding = match &statement.kind {
+                Some(StatementKind::Instruction { mnemonic, operand }) => {
+                    let lookup = |expression: &Expression| {
+                        evaluate(expression, &labels, address.unwrap_or(0) as Word, statement).ok()
+                    };
+                    Some(self.encoding(statement, mnemonic, operand, lookup)?)
+                }
+                _ => None,
+            };
+            encodings.push(encoding);
+            address = advance(statement, encoding, address, &labels)?;
+        }
+
+        // Second pass, emit the bytes
+        let mut assembly = Assembly {
+            image: Image::default(),
+            labels: HashMap::new(),
+            listing: Vec::new(),
+        };
+        let mut address: Option<Word> = None;
+        for ((statement, encoding), source) in statements.iter().zip(&encodings).zip(source.lines())
+        {
+            let bytes = match &statement.kind {
+                Some(StatementKind::Origin(expression)) => {
+                    let origin =
+                        evaluate_word(expression, &labels, address.unwrap_or(0), statement)?;
+                    assembly.image.entry.get_or_insert(origin);
+                    assembly.image.segments.push(Segment {
+                        address: origin,
+                        data: Vec::new(),
+                    });
+                    address = Some(origin);
+                    Vec::new()
+                }
+                Some(kind) => {
+                    let current = address.ok_or(AssemblyError {
+                        line: statement.line,
+                        column: statement.column,
+                        kind: AssemblyErrorKind::MissingOrigin,
+                    })?;
+                    emit(statement, kind, *encoding, current, &labels)?
+                }
+                None => Vec::new(),
+            };
+            assembly.listing.push(ListingLine {
+                line: statement.line,
+                address: address.filter(|_| statement.label.is_some() || !bytes.is_empty()),
+                bytes: bytes.clone(),
+                source: source.to_string(),
+            });
+            if let (Some(segment), Some(current)) = (assembly.image.segments.last_mut(), address) {
+                segment.data.extend(&bytes);
+                address = Some(current.wrapping_add(bytes.len() as Word));
+            }
+        }
+        assembly
+            .image
+            .segments
+            .retain(|segment| !segment.data.is_empty());
+        assembly.labels = labels;
+        Ok(assembly)
+    }
+
+    fn is_mnemonic(&self, name: &str) -> bool {
+        self.opcodes.contains_key(name.to_uppercase().as_str())
+    }
+
+    fn parse_line(&self, line: usize, text: &str) -> Result<Statement, AssemblyError> {
+        let tokens = tokenize(line, text)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            line,
+            end_column: text.len() + 1,
+        };
+        let mut statement = Statement {
+            line,
+            column: tokens.first().map_or(1, |token| token.column),
+            label: None,
+            kind: None,
+        };
+
+        if let [Token {
+            kind: TokenKind::Identifier(name),
+            column,
+        }, rest @ ..] = &tokens[..]
+        {
+            let is_label = match rest.first().map(|token| &token.kind) {
+                Some(TokenKind::Colon) => {
+                    parser.position += 1;
+                    true
+                }
+                None => !self.is_mnemonic(name),
+                Some(TokenKind::Directive(_)) => true,
+                Some(TokenKind::Identifier(next)) => {
+                    !self.is_mnemonic(name) && self.is_mnemonic(next)
+                }
+                _ => false,
+            };
+            if is_label {
+                statement.label = Some((name.clone(), *column));
+                parser.position += 1;
+            }
+        }
+
+        let Some(token) = parser.peek().cloned() else {
+            return Ok(statement);
+        };
+        statement.column = token.column;
+        statement.kind = Some(match token.kind {
+            TokenKind::Star => {
+                parser.position += 1;
+                parser.expect(TokenKind::Equals)?;
+                StatementKind::Origin(parser.expression()?)
+            }
+            TokenKind::Directive(name) => {
+                parser.position += 1;
+                let expressions = parser.expression_list()?;
+                match name.to_lowercase().as_str() {
+                    ".byte" => StatementKind::Bytes(expressions),
+                    ".word" => StatementKind::Words(expressions),
+                    _ => {
+                        return Err(parser
+                            .error_at(token.column, AssemblyErrorKind::UnknownDirective(name)))
+                    }
+                }
+            }
+            TokenKind::Identifier(name) if self.is_mnemonic(&name) => {
+                parser.position += 1;
+                StatementKind::Instruction {
+                    mnemonic: name.to_uppercase(),
+                    operand: parser.operand()?,
+                }
+            }
+            TokenKind::Identifier(name) => {
+                return Err(parser.error_at(token.column, AssemblyErrorKind::UnknownMnemonic(name)))
+            }
+            _ => return Err(parser.error_at(token.column, AssemblyErrorKind::ExpectedExpression)),
+        });
+        parser.expect_end()?;
+        Ok(statement)
+    }
+
+    /// Picks the addressing mode, `value` gives operands already known on this pass.
+    fn encoding(
+        &self,
+        statement: &Statement,
+        mnemonic: &str,
+        operand: &Operand,
+        value: impl Fn(&Expression) -> Option<i32>,
+    ) -> Result<(Byte, AddressingMode), AssemblyError> {
+        use AddressingMode::*;
+        let modes = &self.opcodes[mnemonic];
+        let fits_zero_page = |expression: &Expression| {
+            value(expression).is_some_and(|value| (0..=0xFF).contains(&value))
+        };
+        let candidates: &[AddressingMode] = match operand {
+            Operand::None => &[Implied, Accumulator],
+            Operand::Accumulator => &[Accumulator],
+            Operand::Immediate(_) => &[Immediate, ImmediateWord],
+            Operand::Direct(_) if modes.iter().any(|(mode, _)| *mode == Relative) => &[Relative],
+            Operand::Direct(expression) if fits_zero_page(expression) => &[ZeroPage, Absolute],
+            Operand::Direct(_) => &[Absolute],
+            Operand::IndexedX(expression) if fits_zero_page(expression) => &[ZeroPageX, AbsoluteX],
+            Operand::IndexedX(_) => &[AbsoluteX],
+            Operand::IndexedY(expression) if fits_zero_page(expression) => &[ZeroPageY, AbsoluteY],
+            Operand::IndexedY(_) => &[AbsoluteY],
+            Operand::Indirect(_) => &[Indirect, ZeroPageIndirect],
+            Operand::IndexedIndirect(_) => &[IndexedIndirect, AbsoluteIndexedIndirect],
+            Operand::IndirectIndexed(_) => &[IndirectIndexed],
+            Operand::ZeroPageRelative(_, _) => &[ZeroPageRelative],
+        };
+        candidates
+            .iter()
+            .find_map(|candidate| modes.iter().find(|(mode, _)| mode == candidate))
+            .map(|(mode, opcode)| (*opcode, *mode))
+            .ok_or(AssemblyError {
+                line: statement.line,
+                column: statement.column,
+                kind: AssemblyErrorKind::UnsupportedAddressingMode(mnemonic.to_string()),
+            })
+    }
+}
+
+/// The address after `statement` on the first pass, $10000 when it ends at $FFFF.
+fn advance(
+    statement: &Statement,
+    encoding: Option<(Byte, AddressingMode)>,
+    address: Option<usize>,
+    labels: &HashMap<String, Word>,
+) -> Result<Option<usize>, AssemblyError> {
+    let length = match (&statement.kind, encoding) {
+        (Some(StatementKind::Origin(expression)), _) => {
+            let current = address.unwrap_or(0) as Word;
+            return evaluate_word(expression, labels, current, statement)
+                .map(|origin| Some(origin as usize));
+        }
+        (Some(StatementKind::Bytes(expressions)), _) => expressions.len(),
+        (Some(StatementKind::Words(expressions)), _) => expressions.len() * 2,
+        (_, Some((_, mode))) => mode.length() as usize,
+        _ => return Ok(address),
+    };
+    let error = |kind| AssemblyError {
+        line: statement.line,
+        column: statement.column,
+        kind,
+    };
+    let address = address.ok_or(error(AssemblyErrorKind::MissingOrigin))?;
+    let next = address + length;
+    if next > 0x10000 {
+        return Err(error(AssemblyErrorKind::PastEndOfMemory));
+    }
+    Ok(Some(next))
+}
+
+fn emit(
+    statement: &Statement,
+    kind: &StatementKind,
+    encoding: Option<(Byte, AddressingMode)>,
+    address: Word,
+    labels: &HashMap<String, Word>,
+) -> Result<Vec<Byte>, AssemblyError> {
+    let byte = |expression: &Expression| -> Result<Byte, AssemblyError> {
+        let value = evaluate(expression, labels, address, statement)?;
+        if !(-128..=0xFF).contains(&value) {
+            return Err(out_of_range(statement, expression, value));
+        }
+        Ok(value as Byte)
+    };
+    let word = |expression: &Expression| evaluate_word(expression, labels, address, statement);
+    let branch = |expression: &Expression, length: Word| -> Result<Byte, AssemblyError> {
+        let target = word(expression)?;
+        let offset = target as i32 - (address as i32 + length as i32);
+        if !(-128..=127).contains(&offset) {
+            return Err(AssemblyError {
+                line: statement.line,
+                column: expression_column(expression).unwrap_or(statement.column),
+                kind: AssemblyErrorKind::BranchOutOfRange(offset),
+            });
+        }
+        Ok(offset as Byte)
+    };
+
+    let mut bytes = Vec::new();
+    match kind {
+        StatementKind::Origin(_) => {}
+        StatementKind::Bytes(expressions) => {
+            for expression in expressions {
+                bytes.push(byte(expression)?);
+            }
+        }
+        StatementKind::Words(expressions) => {
+            for expression in expressions {
+                bytes.extend(word(expression)?.to_le_bytes());
+            }
+        }
+        StatementKind::Instruction { operand, .. } => {
+            let (opcode, mode) = encoding.unwrap();
+            bytes.push(opcode);
+            match (operand, mode) {
+                (Operand::None | Operand::Accumulator, _) => {}
+                (Operand::ZeroPageRelative(zero_page, target), _) => {
+                    bytes.push(byte(zero_page)?);
+                    bytes.push(branch(target, 3)?);
+                }
+                (Operand::Direct(target), AddressingMode::Relative) => {
+                    bytes.push(branch(target, 2)?)
+                }
+                (
+                    Operand::Immediate(expression)
+                    | Operand::Direct(expression)
+                    | Operand::IndexedX(expression)
+                    | Operand::IndexedY(expression)
+                    | Operand::Indirect(expression)
+                    | Operand::IndexedIndirect(expression)
+                    | Operand::IndirectIndexed(expression),
+                    mode,
+                ) => match mode.length() {
+                    2 => bytes.push(byte(expression)?),
+                    _ => bytes.extend(word(expression)?.to_le_bytes()),
+                },
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+fn evaluate(
+    expression: &Expression,
+    labels: &HashMap<String, Word>,
+    address: Word,
+    statement: &Statement,
+) -> Result<i32, AssemblyError> {
+    let evaluate = |expression| evaluate(expression, labels, address, statement);
+    // Overflow is reported with the value clamped to what an i32 can hold
+    let checked = |value: Option<i32>, saturated: i32| {
+        value.ok_or_else(|| out_of_range(statement, expression, saturated))
+    };
+    Ok(match expression {
+        Expression::Number(value) => *value,
+        Expression::CurrentAddress => address as i32,
+        Expression::Label { name, column } => match labels.get(name) {
+            Some(address) => *address as i32,
+            None => {
+                return Err(AssemblyError {
+                    line: statement.line,
+                    column: *column,
+                    kind: AssemblyErrorKind::UndefinedLabel(name.clone()),
+                })
+            }
+        },
+        Expression::LowByte(inner) => evaluate(inner)? & 0xFF,
+        Expression::HighByte(inner) => (evaluate(inner)? >> 8) & 0xFF,
+        Expression::Negate(inner) => {
+            let value = evaluate(inner)?;
+            checked(value.checked_neg(), value.saturating_neg())?
+        }
+        Expression::Add(left, right) => {
+            let (left, right) = (evaluate(left)?, evaluate(right)?);
+            checked(left.checked_add(right), left.saturating_add(right))?
+        }
+        Expression::Subtract(left, right) => {
+            let (left, right) = (evaluate(left)?, evaluate(right)?);
+            checked(left.checked_sub(right), left.saturating_sub(right))?
+        }
+    })
+}
+
+fn evaluate_word(
+    expression: &Expression,
+    labels: &HashMap<String, Word>,
+    address: Word,
+    statement: &Statement,
+) -> Result<Word, AssemblyError> {
+    let value = evaluate(expression, labels, address, statement)?;
+    Word::try_from(value).map_err(|_| out_of_range(statement, expression, value))
+}
+
+fn out_of_range(statement: &Statement, expression: &Expression, value: i32) -> AssemblyError {
+    AssemblyError {
+        line: statement.line,
+        column: expression_column(expression).unwrap_or(statement.column),
+        kind: AssemblyErrorKind::ValueOutOfRange(value),
+    }
+}
+
+/// The column of the first label in `expression`, the best place to point at.
+fn expression_column(expression: &Expression) -> Option<usize> {
+    match expression {
+        Expression::Label { column, .. } => Some(*column),
+        Expression::LowByte(inner) | Expression::HighByte(inner) | Expression::Negate(inner) => {
+            expression_column(inner)
+        }
+        Expression::Add(left, right) | Expression::Subtract(left, right) => {
+            expression_column(left).or_else(|| expression_column(right))
+        }
+        _ => None,
+    }
+}
+
+fn tokenize(line: usize, text: &str) -> Result<Vec<Token>, AssemblyError> {
+    let mut tokens = Vec::new();
+    let characters: Vec<char> = text.chars().collect();
+    let mut index = 0;
+    while index < characters.len() {
+        let character = characters[index];
+        let column = index + 1;
+        let error = |kind| AssemblyError { line, column, kind };
+        let single = match character {
+            ';' => break,
+            _ if character.is_whitespace() => {
+                index += 1;
+                continue;
+            }
+            '#' => Some(TokenKind::Hash),
+            '(' => Some(TokenKind::LeftParenthesis),
+            ')' => Some(TokenKind::RightParenthesis),
+            ',' => Some(TokenKind::Comma),
+            ':' => Some(TokenKind::Colon),
+            '<' => Some(TokenKind::Less),
+            '>' => Some(TokenKind::Greater),
+            '+' => Some(TokenKind::Plus),
+            '-' => Some(TokenKind::Minus),
+            '*' => Some(TokenKind::Star),
+            '=' => Some(TokenKind::Equals),
+            _ => None,
+        };
+        if let Some(kind) = single {
+            tokens.push(Token { kind, column });
+            index += 1;
+            continue;
+        }
+
+        let word_end = |start: usize| {
+            (start..characters.len())
+                .find(|index| !(characters[*index].is_alphanumeric() || characters[*index] == '_'))
+                .unwrap_or(characters.len())
+        };
+        let (kind, end) = match character {
+            '$' | '%' => {
+                let end = word_end(index + 1);
+                let digits: String = characters[index + 1..end].iter().collect();
+                let radix = if character == '$' { 16 } else { 2 };
+                let value = i32::from_str_radix(&digits, radix)
+                    .map_err(|_| error(AssemblyErrorKind::InvalidNumber))?;
+                (TokenKind::Number(value), end)
+            }
+            _ if character.is_ascii_digit() => {
+                let end = word_end(index);
+                let digits: String = characters[index..end].iter().collect();
+                let value = digits
+                    .parse()
+                    .map_err(|_| error(AssemblyErrorKind::InvalidNumber))?;
+                (TokenKind::Number(value), end)
+            }
+            '.' => {
+                let end = word_end(index + 1);
+                (
+                    TokenKind::Directive(characters[index..end].iter().collect()),
+                    end,
+                )
+            }
+            _ if character.is_alphabetic() || character == '_' => {
+                let end = word_end(index);
+                (
+                    TokenKind::Identifier(characters[index..end].iter().collect()),
+                    end,
+                )
+            }
+            _ => return Err(error(AssemblyErrorKind::UnexpectedCharacter(character))),
+        };
+        tokens.push(Token { kind, column });
+        index = end;
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    line: usize,
+    end_column: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn column(&self) -> usize {
+        self.peek().map_or(self.end_column, |token| token.column)
+    }
+
+    fn error_at(&self, column: usize, kind: AssemblyErrorKind) -> AssemblyError {
+        AssemblyError {
+            line: self.line,
+            column,
+            kind,
+        }
+    }
+
+    fn error(&self, kind: AssemblyErrorKind) -> AssemblyError {
+        self.error_at(self.column(), kind)
+    }
+
+    fn accept(&mut self, kind: TokenKind) -> bool {
+        if self.peek().map(|token| &token.kind) == Some(&kind) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<(), AssemblyError> {
+        if self.accept(kind) {
+            Ok(())
+        } else {
+            Err(self.error(AssemblyErrorKind::ExpectedExpression))
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), AssemblyError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error(AssemblyErrorKind::ExpectedEndOfLine)),
+        }
+    }
+
+    fn index_register(&mut self) -> Result<char, AssemblyError> {
+        match self.peek().map(|token| &token.kind) {
+            Some(TokenKind::Identifier(name)) if name.eq_ignore_ascii_case("x") => {
+                self.position += 1;
+                Ok('X')
+            }
+            Some(TokenKind::Identifier(name)) if name.eq_ignore_ascii_case("y") => {
+                self.position += 1;
+                Ok('Y')
+            }
+            _ => Err(self.error(AssemblyErrorKind::ExpectedIndexRegister)),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, AssemblyError> {
+        match self.peek().map(|token| &token.kind) {
+            None => return Ok(Operand::None),
+            Some(TokenKind::Identifier(name))
+                if name.eq_ignore_ascii_case("a") && self.tokens.len() == self.position + 1 =>
+            {
+                self.position += 1;
+                return Ok(Operand::Accumulator);
+            }
+            _ => {}
+        }
+        if self.accept(TokenKind::Hash) {
+            return Ok(Operand::Immediate(self.expression()?));
+        }
+        if self.accept(TokenKind::LeftParenthesis) {
+            let expression = self.expression()?;
+            if self.accept(TokenKind::Comma) {
+                if self.index_register()? != 'X' {
+                    return Err(self.error_at(
+                        self.tokens[self.position - 1].column,
+                        AssemblyErrorKind::ExpectedIndexRegister,
+                    ));
+                }
+                self.expect_closing_parenthesis()?;
+                return Ok(Operand::IndexedIndirect(expression));
+            }
+            self.expect_closing_parenthesis()?;
+            if self.accept(TokenKind::Comma) {
+                if self.index_register()? != 'Y' {
+                    return Err(self.error_at(
+                        self.tokens[self.position - 1].column,
+                        AssemblyErrorKind::ExpectedIndexRegister,
+                    ));
+                }
+                return Ok(Operand::IndirectIndexed(expression));
+            }
+            return Ok(Operand::Indirect(expression));
+        }
+        let expression = self.expression()?;
+        if !self.accept(TokenKind::Comma) {
+            return Ok(Operand::Direct(expression));
+        }
+        match self.peek().map(|token| &token.kind) {
+            Some(TokenKind::Identifier(name))
+                if (name.eq_ignore_ascii_case("x") || name.eq_ignore_ascii_case("y"))
+                    && self.tokens.len() == self.position + 1 =>
+            {
+                match self.index_register()? {
+                    'X' => Ok(Operand::IndexedX(expression)),
+                    _ => Ok(Operand::IndexedY(expression)),
+                }
+            }
+            _ => Ok(Operand::ZeroPageRelative(expression, self.expression()?)),
+        }
+    }
+
+    fn expect_closing_parenthesis(&mut self) -> Result<(), AssemblyError> {
+        if self.accept(TokenKind::RightParenthesis) {
+            Ok(())
+        } else {
+            Err(self.error(AssemblyErrorKind::ExpectedClosingParenthesis))
+        }
+    }
+
+    fn expression_list(&mut self) -> Result<Vec<Expression>, AssemblyError> {
+        let mut expressions = vec![self.expression()?];
+        while self.accept(TokenKind::Comma) {
+            expressions.push(self.expression()?);
+        }
+        Ok(expressions)
+    }
+
+    fn expression(&mut self) -> Result<Expression, AssemblyError> {
+        if self.accept(TokenKind::Less) {
+            return Ok(Expression::LowByte(Box::new(self.sum()?)));
+        }
+        if self.accept(TokenKind::Greater) {
+            return Ok(Expression::HighByte(Box::new(self.sum()?)));
+        }
+        self.sum()
+    }
+
+    fn sum(&mut self) -> Result<Expression, AssemblyError> {
+        let mut expression = self.term()?;
+        loop {
+            if self.accept(TokenKind::Plus) {
+                expression = Expression::Add(Box::new(expression), Box::new(self.term()?));
+            } else if self.accept(TokenKind::Minus) {
+                expression = Expression::Subtract(Box::new(expression), Box::new(self.term()?));
+            } else {
+                return Ok(expression);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Expression, AssemblyError> {
+        let Some(token) = self.peek().cloned() else {
+            return Err(self.error(AssemblyErrorKind::ExpectedExpression));
+        };
+        self.position += 1;
+        match token.kind {
+            TokenKind::Number(value) => Ok(Expression::Number(value)),
+            TokenKind::Identifier(name) => Ok(Expression::Label {
+                name,
+                column: token.column,
+            }),
+            TokenKind::Star => Ok(Expression::CurrentAddress),
+            TokenKind::Minus => Ok(Expression::Negate(Box::new(self.term()?))),
+            _ => {
+                self.position -= 1;
+                Err(self.error(AssemblyErrorKind::ExpectedExpression))
+            }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{
+    bus::{Bus, BusFault},
+    cpu::{Byte, Word},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BankError {
+    NoBanks,
+    BankSizeTooLarge(usize),
+    WindowPastEndOfMemory { start: Word },
+    WindowOverlaps { start: Word },
+    RegisterInUse(Word),
+    BankOutOfRange { bank: usize, bank_count: usize },
+    WindowOutOfRange { window: usize, window_count: usize },
+    OffsetOutOfRange { offset: usize, bank_size: usize },
+}
+
+/// A location in the backing store, independent of what is currently switched in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhysicalAddress {
+    pub bank: usize,
+    pub offset: usize,
+}
+
+/// A part of the CPU address space that shows one bank at a time. Writing a bank number
+/// to `register` switches the bank, reading it returns the selected bank. Bank numbers
+/// past the last bank wrap around, like a mapper ignoring the high bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BankWindow {
+    pub start: Word,
+    pub register: Word,
+    pub bank: usize,
+}
+
+/// Wraps a bus with windows onto a backing store larger than 64 KiB. Accesses that hit
+/// a window or a bank register are handled here, everything else goes to `inner`.
+pub struct BankedMemory<B: Bus> {
+    pub inner: B,
+    store: Vec<Byte>,
+    bank_size: usize,
+    windows: Vec<BankWindow>,
+}
+
+impl<B: Bus> BankedMemory<B> {
+    /// Creates `bank_count` zeroed banks of `bank_size` bytes and no windows.
+    pub fn new(inner: B, bank_count: usize, bank_size: usize) -> Result<Self, BankError> {
+        if bank_count == 0 || bank_size == 0 {
+            return Err(BankError::NoBanks);
+        }
+        if bank_size > 0x10000 {
+            return Err(BankError::BankSizeTooLarge(bank_size));
+        }
+        Ok(BankedMemory {
+            inner,
+            store: vec![0; bank_count * bank_size],
+            bank_size,
+            windows: Vec::new(),
+        })
+    }
+
+    /// The simple mapper, one window at `start` switched by writing to `register`.
+    pub fn single_window(
+        inner: B,
+        bank_count: usize,
+        bank_size: usize,
+        start: Word,
+        register: Word,
+    ) -> Result<Self, BankError> {
+        let mut memory = BankedMemory::new(inner, bank_count, bank_size)?;
+        memory.add_window(start, register)?;
+        Ok(memory)
+    }
+
+    /// Two independently switched windows, given as `(start, register)` pairs.
+    pub fn two_windows(
+        inner: B,
+        bank_count: usize,
+        bank_size: usize,
+        windows: [(Word, Word); 2],
+    ) -> Result<Self, BankError> {
+        let mut memory = BankedMemory::new(inner, bank_count, bank_size)?;
+        for (start, register) in windows {
+            memory.add_window(start, register)?;
+        }
+        Ok(memory)
+    }
+
+    /// Adds a window showing bank 0 and returns its index.
+    pub fn add_window(&mut self, start: Word, register: Word) -> Result<usize, BankError> {
+        if start as usize + self.bank_size > 0x10000 {
+            return Err(BankError::WindowPastEndOfMemory { start });
+        }
+        let end = start as usize + self.bank_size - 1;
+        if let Some(window) = self.windows.iter().find(|window| {
+            start as usize <= self.window_end(window) && window.start as usize <= end
+        }) {
+            return Err(BankError::WindowOverlaps {
+                start: window.start,
+            });
+        }
+        let in_new_window = |address: Word| (start as usize..=end).contains(&(address as usize));
+        if in_new_window(register)
+            || self.window_index(register).is_some()
+            || self.register_index(register).is_some()
+        {
+            return Err(BankError::RegisterInUse(register));
+        }
+        if let Some(window) = self
+            .windows
+            .iter()
+            .find(|window| in_new_window(window.register))
+        {
+            return Err(BankError::RegisterInUse(window.register));
+        }
+        self.windows.push(BankWindow {
+            start,
+            register,
+            bank: 0,
+        });
+        Ok(self.windows.len() - 1)
+    }
+
+    pub fn windows(&self) -> &[BankWindow] {
+        &self.windows
+    }
+
+    pub fn bank_count(&self) -> usize {
+        self.store.len() / self.bank_size
+    }
+
+    pub fn bank_size(&self) -> usize {
+        self.bank_size
+    }
+
+    /// Switches `window` to `bank`, as if the CPU wrote to its register.
+    pub fn select_bank(&mut self, window: usize, bank: usize) -> Result<(), BankError> {
+        let bank_count = self.bank_count();
+        if bank >= bank_count {
+            return Err(BankError::BankOutOfRange { bank, bank_count });
+        }
+        let window_count = self.windows.len();
+        let Some(window) = self.windows.get_mut(window) else {
+            return Err(BankError::WindowOutOfRange {
+                window,
+                window_count,
+            });
+        };
+        window.bank = bank;
+        Ok(())
+    }
+
+    /// Where a CPU address currently lands in the backing store, `None` outside windows.
+    pub fn physical_address(&self, address: Word) -> Option<PhysicalAddress> {
+        let window = &self.windows[self.window_index(address)?];
+        Some(PhysicalAddress {
+            bank: window.bank,
+            offset: (address - window.start) as usize,
+        })
+    }
+
+    /// Reads a physical location whether or not its bank is switched in.
+    pub fn peek_physical(&self, address: PhysicalAddress) -> Result<Byte, BankError> {
+        Ok(self.store[self.store_index(address)?])
+    }
+
+    pub fn write_physical(
+        &mut self,
+        address: PhysicalAddress,
+        value: Byte,
+    ) -> Result<(), BankError> {
+        let index = self.store_index(address)?;
+        self.store[index] = value;
+        Ok(())
+    }
+
+    /// Copies `data` into the backing store starting at `address`, running on into the
+    /// following banks.
+    pub fn load_physical(
+        &mut self,
+        address: PhysicalAddress,
+        data: &[Byte],
+    ) -> Result<(), BankError> {
+        let index = self.store_index(address)?;
+        if index + data.len() > self.store.len() {
+            return Err(BankError::BankOutOfRange {
+                bank: (index + data.len() - 1) / self.bank_size,
+                bank_count: self.bank_count(),
+            });
+        }
+        self.store[index..index + data.len()].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn store_index(&self, address: PhysicalAddress) -> Result<usize, BankError> {
+        let bank_count = self.bank_count();
+        if address.bank >= bank_count {
+            return Err(BankError::BankOutOfRange {
+                bank: address.bank,
+                bank_count,
+            });
+        }
+        if address.offset >= self.bank_size {
+            return Err(BankError::OffsetOutOfRange {
+                offset: address.offset,
+                bank_size: self.bank_size,
+            });
+        }
+        Ok(address.bank * self.bank_size + address.offset)
+    }
+
+    fn window_end(&self, window: &BankWindow) -> usize {
+        window.start as usize + self.bank_size - 1
+    }
+
+    fn window_index(&self, address: Word) -> Option<usize> {
+        self.windows.iter().position(|window| {
+            window.start <= address && address as usize <= self.window_end(window)
+        })
+    }
+
+    fn register_index(&self, address: Word) -> Option<usize> {
+        self.windows
+            .iter()
+            .position(|window| window.register == address)
+    }
+}
+
+impl<B: Bus> Bus for BankedMemory<B> {
+    fn read(&mut self, address: Word) -> Byte {
+        if self.window_index(address).is_some() || self.register_index(address).is_some() {
+            self.peek(address)
+        } else {
+            self.inner.read(address)
+        }
+    }
+
+    fn write(&mut self, address: Word, value: Byte) {
+        if let Some(physical) = self.physical_address(address) {
+            let index = physical.bank * self.bank_size + physical.offset;
+            self.store[index] = value;
+        } else if let Some(window) = self.register_index(address) {
+            self.windows[window].bank = value as usize % self.bank_count();
+        } else {
+            self.inner.write(address, value);
+        }
+    }
+
+    fn peek(&self, address: Word) -> Byte {
+        if let Some(physical) = self.physical_address(address) {
+            self.store[physical.bank * self.bank_size + physical.offset]
+        } else if let Some(window) = self.register_index(address) {
+            self.windows[window].bank as Byte
+        } else {
+            self.inner.peek(address)
+        }
+    }
+
+    fn fetch(&mut self, address: Word) -> Byte {
+        if self.window_index(address).is_some() || self.register_index(address).is_some() {
+            self.peek(address)
+        } else {
+            self.inner.fetch(address)
+        }
+    }
+
+    fn take_fault(&mut self) -> Option<BusFault> {
+        self.inner.take_fault()
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{
+    cpu::{Byte, Word},
+    watchpoints::WatchpointHit,
+};
+
+/// A bus access that should stop the CPU, reported through `Bus::take_fault`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusFault {
+    WriteToRom { address: Word, value: Byte },
+    Watchpoint(WatchpointHit),
+}
+
+/// Everything the CPU can address. Reads take `&mut self` because reading a device
+/// register can have side effects, `peek` must not have any so debuggers can look at
+/// memory without disturbing it.
+pub trait Bus {
+    fn read(&mut self, address: Word) -> Byte;
+    fn write(&mut self, address: Word, value: Byte);
+    fn peek(&self, address: Word) -> Byte;
+
+    /// Reads an opcode, buses that care about instruction fetches can tell them apart
+    /// from data reads here.
+    fn fetch(&mut self, address: Word) -> Byte {
+        self.read(address)
+    }
+
+    /// Takes the fault raised by the last accesses, if any. The CPU checks this after
+    /// every instruction and stops with an error when there is one.
+    fn take_fault(&mut self) -> Option<BusFault> {
+        None
+    }
+}
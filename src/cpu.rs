@@ -1,12 +1,12 @@
 #![allow(unused)]
 use crate::{
+    bus::{Bus, BusFault},
     graphics_adapter::GraphicsAdapter,
-    instructions::{Instruction, InstructionsError},
+    instructions::{opcode_table, AddressingMode, Instruction, InstructionsError, Operation},
+    power_on::PowerOnPattern,
 };
 use std::{fmt::Display, ops::BitOrAssign};
 
-use crate::memory::Memory;
-
 pub type Byte = u8;
 pub type SByte = i8;
 pub type Word = u16;
@@ -26,6 +26,12 @@ pub struct ProcessorFlags {
 impl ProcessorFlags {
     pub const NEGATIVE_FLAG_BIT: Byte = 0b10000000;
     pub const OVERFLOW_FLAG_BIT: Byte = 0b01000000;
+    pub const UNUSED_FLAG_BIT: Byte = 0b00100000;
+    pub const BREAK_FLAG_BIT: Byte = 0b00010000;
+    pub const DECIMAL_MODE_FLAG_BIT: Byte = 0b00001000;
+    pub const INTERRUPT_DISABLE_FLAG_BIT: Byte = 0b00000100;
+    pub const ZERO_FLAG_BIT: Byte = 0b00000010;
+    pub const CARRY_FLAG_BIT: Byte = 0b00000001;
     pub fn into_u8(&self) -> u8 {
         let mut value = 0;
         value |= self.carry as u8;
@@ -94,7 +100,75 @@ impl From<ProcessorFlags> for u8 {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CpuVariant {
+    #[default]
+    Nmos6502,
+    Cmos65C02,
+}
+
+/// An instruction operand after its addressing mode has been resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Implied,
+    Accumulator,
+    Immediate(Byte),
+    ImmediateWord(Word),
+    Address(Word),
+    Relative(SByte),
+    ZeroPageRelative(Word, SByte),
+}
+
+impl Operand {
+    /// Effective address of the operand, if it has one.
+    pub fn effective_address(self) -> Option<Word> {
+        match self {
+            Operand::Address(address) | Operand::ZeroPageRelative(address, _) => Some(address),
+            _ => None,
+        }
+    }
+
+    fn address(self) -> Word {
+        match self.effective_address() {
+            Some(address) => address,
+            None => unreachable!("{:?} has no address", self),
+        }
+    }
+
+    fn offset(self) -> SByte {
+        match self {
+            Operand::Relative(offset) | Operand::ZeroPageRelative(_, offset) => offset,
+            _ => unreachable!("{:?} has no branch offset", self),
+        }
+    }
+}
+
+/// Report of a single instruction run by `CPU::step`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepResult {
+    /// Address the instruction was fetched from
+    pub program_counter: Word,
+    pub opcode: Byte,
+    /// `None` for opcodes the 65C02 runs as undefined NOPs
+    pub instruction: Option<Instruction>,
+    pub operand: [Byte; 2],
+    pub operand_length: usize,
+    /// Address read, written or jumped to, or the target of a branch
+    pub effective_address: Option<Word>,
+    /// Cycles taken, including the interrupt sequence when one was serviced first
+    pub cycles: i32,
+    pub interrupt_taken: bool,
+    pub branch_taken: bool,
+    pub page_crossed: bool,
+}
+
+impl StepResult {
+    pub fn operand_bytes(&self) -> &[Byte] {
+        &self.operand[..self.operand_length]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct CPU {
     // Addresses
     pub program_counter: Word,
@@ -108,34 +182,158 @@ pub struct CPU {
     // Status flags
     pub status: ProcessorFlags,
 
+    // Interrupt lines
+    pub irq_line: bool,
+    pub nmi_line: bool,
+    pub nmi_pending: bool,
+
+    // Instruction set
+    pub variant: CpuVariant,
+    // Opt-in emulation of the undocumented NMOS opcodes
+    pub undocumented_opcodes: bool,
+
+    // Set by the 65C02 WAI and STP instructions
+    pub waiting_for_interrupt: bool,
+    pub stopped: bool,
+
+    // Clock
+    // Cycles the last instruction ran past the budget given to `execute`
+    pub cycle_debt: i32,
+    pub total_cycles: u64,
+    pub instructions_retired: u64,
+
     // Graphics adapter reference
     pub graphics_adapter: Option<GraphicsAdapter>,
 }
 
 impl CPU {
-    pub fn new_graphics(graphics_adapter: GraphicsAdapter, reset_vector: Option<Word>) -> CPU {
-        let program_counter = reset_vector.unwrap_or(0xFFFC);
+    pub const NMI_VECTOR: Word = 0xFFFA;
+    pub const RESET_VECTOR: Word = 0xFFFC;
+    pub const IRQ_VECTOR: Word = 0xFFFE;
+
+    /// Creates a CPU in its power-on state. Call [`CPU::reset`] before executing so the
+    /// program counter is loaded from the reset vector.
+    pub fn new() -> CPU {
         CPU {
-            program_counter,
-            stack_pointer: 0xFF,
+            program_counter: 0,
+            stack_pointer: 0,
             a_register: 0,
             x_register: 0,
             y_register: 0,
             status: ProcessorFlags::default(),
-            graphics_adapter: Some(graphics_adapter),
+            irq_line: false,
+            nmi_line: false,
+            nmi_pending: false,
+            variant: CpuVariant::Nmos6502,
+            undocumented_opcodes: false,
+            waiting_for_interrupt: false,
+            stopped: false,
+            cycle_debt: 0,
+            total_cycles: 0,
+            instructions_retired: 0,
+            graphics_adapter: None,
         }
     }
-    pub fn reset(reset_vector: Option<Word>) -> CPU {
-        let program_counter = reset_vector.unwrap_or(0xFFFC);
+
+    /// Creates a CPU that starts executing at `program_counter` with an empty stack,
+    /// skipping the reset sequence.
+    pub fn start_at(program_counter: Word) -> CPU {
         CPU {
             program_counter,
             stack_pointer: 0xFF,
-            a_register: 0,
-            x_register: 0,
-            y_register: 0,
-            status: ProcessorFlags::default(),
-            graphics_adapter: None,
+            ..CPU::new()
+        }
+    }
+
+    /// Creates a CPU whose A, X, Y, stack pointer and status hold what `pattern` says,
+    /// in that order. Call `reset` afterwards to start it like real hardware.
+    pub fn power_on(pattern: PowerOnPattern) -> CPU {
+        let mut registers = [0; 5];
+        pattern.fill(&mut registers, 1);
+        let [a_register, x_register, y_register, stack_pointer, status] = registers;
+        CPU {
+            a_register,
+            x_register,
+            y_register,
+            stack_pointer,
+            status: ProcessorFlags::from(status),
+            ..CPU::new()
+        }
+    }
+
+    pub fn with_variant(self, variant: CpuVariant) -> CPU {
+        CPU { variant, ..self }
+    }
+
+    pub fn new_graphics(graphics_adapter: GraphicsAdapter, program_counter: Word) -> CPU {
+        CPU {
+            graphics_adapter: Some(graphics_adapter),
+            ..CPU::start_at(program_counter)
+        }
+    }
+
+    /// Runs the 7 cycle reset sequence. The three stack accesses are reads on real
+    /// hardware, so the stack pointer is decremented without writing to bus.
+    pub fn reset<B: Bus>(&mut self, bus: &mut B) -> i32 {
+        let mut cycles = 0;
+        cycles -= 2;
+        self.stack_pointer = self.stack_pointer.wrapping_sub(3);
+        cycles -= 3;
+        self.status.interupt_disable = true;
+        if self.variant == CpuVariant::Cmos65C02 {
+            self.status.decimal_mode = false;
+        }
+        self.nmi_pending = false;
+        self.waiting_for_interrupt = false;
+        self.stopped = false;
+        self.program_counter = self.read_word_absolute(&mut cycles, bus, CPU::RESET_VECTOR);
+        -cycles
+    }
+
+    /// Drives the IRQ line. The line is level triggered, so the interrupt keeps being
+    /// taken between instructions until the device releases it or `SEI` masks it.
+    pub fn set_irq(&mut self, asserted: bool) {
+        self.irq_line = asserted;
+    }
+
+    /// Drives the NMI line. Only a transition from released to asserted latches an
+    /// interrupt, holding the line asserted does not retrigger it.
+    pub fn set_nmi(&mut self, asserted: bool) {
+        if asserted && !self.nmi_line {
+            self.nmi_pending = true;
+        }
+        self.nmi_line = asserted;
+    }
+
+    fn poll_interrupts<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B) -> bool {
+        if self.waiting_for_interrupt && (self.nmi_pending || self.irq_line) {
+            // WAI resumes on any interrupt, even one masked by the I flag
+            self.waiting_for_interrupt = false;
+        }
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            self.interrupt(cycles, bus, CPU::NMI_VECTOR);
+            return true;
+        }
+        if self.irq_line && !self.status.interupt_disable {
+            self.interrupt(cycles, bus, CPU::IRQ_VECTOR);
+            return true;
         }
+        false
+    }
+
+    fn interrupt<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B, vector: Word) {
+        let mut pushed_status = self.status;
+        pushed_status.break_command = false;
+        pushed_status.unused = true;
+        self.push_program_counter_to_stack(cycles, bus);
+        self.push_byte_to_stack(pushed_status.into_u8(), cycles, bus);
+        self.status.interupt_disable = true;
+        if self.variant == CpuVariant::Cmos65C02 {
+            self.status.decimal_mode = false;
+        }
+        self.program_counter = self.read_word_absolute(cycles, bus, vector);
+        *cycles -= 1;
     }
 
     pub fn set_z_n_flags(&mut self, value: Byte) {
@@ -143,11 +341,10 @@ impl CPU {
         self.status.negative = (value & ProcessorFlags::NEGATIVE_FLAG_BIT) > 0;
     }
 
-    pub fn branch_if(&mut self, cycles: &mut i32, memory: &mut Memory, test: bool, expected: bool) {
-        let jump_offset = self.fetch_byte(cycles, memory) as SByte;
-        if test == expected {
+    pub fn branch(&mut self, cycles: &mut i32, offset: SByte, condition: bool) {
+        if condition {
             let program_counter_old = self.program_counter;
-            self.program_counter = self.program_counter.wrapping_add(jump_offset as Word);
+            self.program_counter = self.program_counter.wrapping_add(offset as Word);
             *cycles -= 1;
             if (!CPU::check_same_page(program_counter_old, self.program_counter)) {
                 *cycles -= 1;
@@ -155,11 +352,16 @@ impl CPU {
         }
     }
 
-    fn add_with_carry(&mut self, cycles: &mut i32, memory: &mut Memory, rhs: Byte) {
+    fn add_with_carry<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B, rhs: Byte) {
         if self.status.decimal_mode {
-            println!("Dec at {:4x}", self.program_counter);
+            self.add_with_carry_decimal(rhs);
+            if self.variant == CpuVariant::Cmos65C02 {
+                // The 65C02 spends an extra cycle to correct N and Z
+                self.set_z_n_flags(self.a_register);
+                *cycles -= 1;
+            }
+            return;
         }
-        assert!(!self.status.decimal_mode);
         let word_addition = self.a_register as Word + rhs as Word + self.status.carry as Word;
         let are_sign_bits_the_same =
             (self.a_register ^ rhs) & ProcessorFlags::NEGATIVE_FLAG_BIT == 0;
@@ -171,8 +373,94 @@ impl CPU {
             && (rhs ^ self.a_register) & ProcessorFlags::NEGATIVE_FLAG_BIT != 0;
     }
 
-    fn subtract_with_carry(&mut self, cycles: &mut i32, memory: &mut Memory, rhs: Byte) {
-        self.add_with_carry(cycles, memory, !rhs);
+    fn subtract_with_carry<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B, rhs: Byte) {
+        if self.status.decimal_mode {
+            match self.variant {
+                CpuVariant::Nmos6502 => self.subtract_with_carry_decimal(rhs),
+                CpuVariant::Cmos65C02 => {
+                    self.subtract_with_carry_decimal_65c02(rhs);
+                    *cycles -= 1;
+                }
+            }
+            return;
+        }
+        self.add_with_carry(cycles, bus, !rhs);
+    }
+
+    /// NMOS decimal mode addition. Z is taken from the binary sum, while N and V are
+    /// taken from the result after the low nibble has been adjusted but before the
+    /// high nibble has been.
+    fn add_with_carry_decimal(&mut self, rhs: Byte) {
+        let lhs = self.a_register;
+        let carry = self.status.carry as Word;
+
+        let mut low_nibble = (lhs & 0x0F) as Word + (rhs & 0x0F) as Word + carry;
+        if low_nibble > 0x09 {
+            low_nibble = ((low_nibble + 0x06) & 0x0F) + 0x10;
+        }
+        let mut sum = (lhs & 0xF0) as Word + (rhs & 0xF0) as Word + low_nibble;
+        let intermediate = sum as Byte;
+
+        self.status.zero = lhs.wrapping_add(rhs).wrapping_add(carry as Byte) == 0;
+        self.status.negative = intermediate & ProcessorFlags::NEGATIVE_FLAG_BIT != 0;
+        self.status.overflow =
+            (!(lhs ^ rhs) & (lhs ^ intermediate)) & ProcessorFlags::NEGATIVE_FLAG_BIT != 0;
+
+        if sum >= 0xA0 {
+            sum += 0x60;
+        }
+        self.status.carry = sum > 0xFF;
+        self.a_register = sum as Byte;
+    }
+
+    /// NMOS decimal mode subtraction. The flags are identical to a binary subtraction,
+    /// only the accumulator is adjusted.
+    fn subtract_with_carry_decimal(&mut self, rhs: Byte) {
+        let lhs = self.a_register;
+        let borrow = !self.status.carry as i16;
+
+        let binary_difference = lhs as i16 - rhs as i16 - borrow;
+        let binary_result = binary_difference as Byte;
+
+        let mut low_nibble = (lhs & 0x0F) as i16 - (rhs & 0x0F) as i16 - borrow;
+        if low_nibble < 0 {
+            low_nibble = ((low_nibble - 0x06) & 0x0F) - 0x10;
+        }
+        let mut difference = (lhs & 0xF0) as i16 - (rhs & 0xF0) as i16 + low_nibble;
+        if difference < 0 {
+            difference -= 0x60;
+        }
+
+        self.set_z_n_flags(binary_result);
+        self.status.carry = binary_difference >= 0;
+        self.status.overflow =
+            ((lhs ^ rhs) & (lhs ^ binary_result)) & ProcessorFlags::NEGATIVE_FLAG_BIT != 0;
+        self.a_register = difference as Byte;
+    }
+
+    /// 65C02 decimal mode subtraction. Unlike the NMOS version, N and Z reflect the
+    /// decimal result.
+    fn subtract_with_carry_decimal_65c02(&mut self, rhs: Byte) {
+        let lhs = self.a_register;
+        let borrow = !self.status.carry as i16;
+
+        let binary_difference = lhs as i16 - rhs as i16 - borrow;
+        let binary_result = binary_difference as Byte;
+
+        let low_nibble = (lhs & 0x0F) as i16 - (rhs & 0x0F) as i16 - borrow;
+        let mut difference = binary_difference;
+        if difference < 0 {
+            difference -= 0x60;
+        }
+        if low_nibble < 0 {
+            difference -= 0x06;
+        }
+
+        self.a_register = difference as Byte;
+        self.set_z_n_flags(self.a_register);
+        self.status.carry = binary_difference >= 0;
+        self.status.overflow =
+            ((lhs ^ rhs) & (lhs ^ binary_result)) & ProcessorFlags::NEGATIVE_FLAG_BIT != 0;
     }
 
     fn compare_register(&mut self, register: Byte, rhs: Byte) {
@@ -214,1174 +502,1009 @@ impl CPU {
         value
     }
 
-    pub fn execute(&mut self, cycles: i32, memory: &mut Memory) -> Result<i32, InstructionsError> {
-        let cycles_requested = cycles;
-        let mut cycles = cycles;
-        while cycles > 0 {
-            let instruction_byte = Instruction::try_from(self.fetch_byte(&mut cycles, memory))?;
-            match instruction_byte {
-                // LDA
-                Instruction::InsLdaIm => {
-                    let value = self.fetch_byte(&mut cycles, memory);
-                    self.a_register = value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsLdaZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.a_register = value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsLdaZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address_x as Word);
-                    self.a_register = value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsLdaAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, absolute_address);
-                    self.a_register = value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsLdaAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    self.a_register = value;
-                    if !CPU::check_same_page(absolute_address, absolute_address_x) {
-                        cycles -= 1;
-                    }
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsLdaAbsY => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_y = absolute_address + self.y_register as Word;
-
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_y);
-                    self.a_register = value;
-                    if !CPU::check_same_page(absolute_address, absolute_address_y) {
-                        cycles -= 1;
-                    }
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsLdaIndX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let absolute_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address_x);
-
-                    let value = self.read_byte(&mut cycles, memory, absolute_address);
-                    self.a_register = value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsLdaIndY => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let absolute_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address);
+    fn zero_page_address<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B) -> Word {
+        self.fetch_byte(cycles, bus) as Word
+    }
 
-                    let absolute_address_y = absolute_address + self.y_register as Word;
+    fn zero_page_indexed_address<B: Bus>(
+        &mut self,
+        cycles: &mut i32,
+        bus: &mut B,
+        index: Byte,
+    ) -> Word {
+        let zero_page_address = self.fetch_byte(cycles, bus);
+        *cycles -= 1;
+        zero_page_address.wrapping_add(index) as Word
+    }
 
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_y);
-                    self.a_register = value;
-                    if !CPU::check_same_page(absolute_address, absolute_address_y) {
-                        cycles -= 1;
-                    }
-                    self.set_z_n_flags(self.a_register);
-                }
-                // LDX
-                Instruction::InsLdxIm => {
-                    let value = self.fetch_byte(&mut cycles, memory);
-                    self.x_register = value;
-                    self.set_z_n_flags(self.x_register);
-                }
-                Instruction::InsLdxZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.x_register = value;
-                    self.set_z_n_flags(self.x_register);
-                }
-                Instruction::InsLdxZpy => {
-                    let mut zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    zero_page_address = zero_page_address.wrapping_add(self.y_register);
-                    cycles -= 1;
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.x_register = value;
-                    self.set_z_n_flags(self.x_register);
-                }
-                Instruction::InsLdxAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, absolute_address);
-                    self.x_register = value;
-                    self.set_z_n_flags(self.x_register);
-                }
-                Instruction::InsLdxAbsY => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_y = absolute_address + self.y_register as Word;
-
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_y);
-                    self.x_register = value;
-                    if !CPU::check_same_page(absolute_address, absolute_address_y) {
-                        cycles -= 1;
-                    }
-                    self.set_z_n_flags(self.x_register);
-                }
-                // LDY
-                Instruction::InsLdyIm => {
-                    let value = self.fetch_byte(&mut cycles, memory);
-                    self.y_register = value;
-                    self.set_z_n_flags(self.y_register);
-                }
-                Instruction::InsLdyZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.y_register = value;
-                    self.set_z_n_flags(self.y_register);
-                }
-                Instruction::InsLdyZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address_x as Word);
-                    self.y_register = value;
-                    self.set_z_n_flags(self.y_register);
-                }
-                Instruction::InsLdyAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, absolute_address);
-                    self.y_register = value;
-                    self.set_z_n_flags(self.y_register);
-                }
-                Instruction::InsLdyAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    self.y_register = value;
-                    if !CPU::check_same_page(absolute_address, absolute_address_x) {
-                        cycles -= 1;
-                    }
-                    self.set_z_n_flags(self.y_register);
-                }
-                // Jumps
-                Instruction::InsJsr => {
-                    let subroutine_address = self.fetch_word(&mut cycles, memory);
-                    self.push_program_counter_minus_one_to_stack(&mut cycles, memory);
-                    self.program_counter = subroutine_address;
-                    cycles -= 1;
-                }
-                Instruction::InsRts => {
-                    let return_address = self.pop_word_from_stack(&mut cycles, memory);
-                    self.program_counter = return_address + 1;
-                    cycles -= 2;
-                }
-                Instruction::InsJmpAbs => {
-                    let address = self.fetch_word(&mut cycles, memory);
-                    self.program_counter = address;
-                }
-                Instruction::InsJmpInd => {
-                    let indirect_address = self.fetch_word(&mut cycles, memory);
-                    let address = self.read_word_absolute(&mut cycles, memory, indirect_address);
-                    self.program_counter = address;
-                }
-                // STA
-                Instruction::InsStaZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    self.write_byte(
-                        self.a_register,
-                        zero_page_address as Word,
-                        &mut cycles,
-                        memory,
-                    );
-                }
-                Instruction::InsStaZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    self.write_byte(
-                        self.a_register,
-                        zero_page_address_x as Word,
-                        &mut cycles,
-                        memory,
-                    );
-                }
-                Instruction::InsStaAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    self.write_byte(self.a_register, absolute_address, &mut cycles, memory);
-                }
-                Instruction::InsStaAbsX => {
-                    let absolute_address =
-                        self.fetch_word(&mut cycles, memory) + self.x_register as Word;
-                    cycles -= 1;
-                    self.write_byte(self.a_register, absolute_address, &mut cycles, memory);
-                }
-                Instruction::InsStaAbsY => {
-                    let absolute_address =
-                        self.fetch_word(&mut cycles, memory) + self.y_register as Word;
-                    cycles -= 1;
-                    self.write_byte(self.a_register, absolute_address, &mut cycles, memory);
-                }
-                Instruction::InsStaIndX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let indexed_indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address_x);
-                    self.write_byte(
-                        self.a_register,
-                        indexed_indirect_address,
-                        &mut cycles,
-                        memory,
-                    );
-                }
-                Instruction::InsStaIndY => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let indirect_indexed_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address)
-                            + self.y_register as Word;
-                    cycles -= 1;
-                    self.write_byte(
-                        self.a_register,
-                        indirect_indexed_address,
-                        &mut cycles,
-                        memory,
-                    );
-                }
-                // STX
-                Instruction::InsStxZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    self.write_byte(
-                        self.x_register,
-                        zero_page_address as Word,
-                        &mut cycles,
-                        memory,
-                    );
-                }
-                Instruction::InsStxZpY => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_y = zero_page_address.wrapping_add(self.y_register);
-                    cycles -= 1;
-                    self.write_byte(
-                        self.x_register,
-                        zero_page_address_y as Word,
-                        &mut cycles,
-                        memory,
-                    );
-                }
-                Instruction::InsStxAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    self.write_byte(self.x_register, absolute_address, &mut cycles, memory);
-                }
-                // STY
-                Instruction::InsStyZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    self.write_byte(
-                        self.y_register,
-                        zero_page_address as Word,
-                        &mut cycles,
-                        memory,
-                    );
-                }
-                Instruction::InsStyZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    self.write_byte(
-                        self.y_register,
-                        zero_page_address_x as Word,
-                        &mut cycles,
-                        memory,
-                    );
-                }
-                Instruction::InsStyAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    self.write_byte(self.y_register, absolute_address, &mut cycles, memory);
-                }
-                Instruction::InsTsx => {
-                    self.x_register = self.stack_pointer;
-                    cycles -= 1;
-                    self.set_z_n_flags(self.x_register);
-                }
-                Instruction::InsTxs => {
-                    self.stack_pointer = self.x_register;
-                    cycles -= 1;
-                }
-                Instruction::InsPha => {
-                    self.push_byte_to_stack(self.a_register, &mut cycles, memory);
-                }
-                Instruction::InsPla => {
-                    self.a_register = self.pop_byte_from_stack(&mut cycles, memory);
-                    self.set_z_n_flags(self.a_register);
-                    cycles -= 1;
-                }
-                Instruction::InsPhp => {
-                    self.status.break_command = true;
-                    self.status.unused = true;
-                    self.push_byte_to_stack(self.status.into_u8(), &mut cycles, memory);
-                    self.status.interupt_disable = true;
-                }
-                Instruction::InsPlp => {
-                    self.status = self.pop_byte_from_stack(&mut cycles, memory).into();
-                    cycles -= 1;
-                }
-                // AND
-                Instruction::InsAndIm => {
-                    let value = self.fetch_byte(&mut cycles, memory);
-                    self.a_register &= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsAndZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.a_register &= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsAndZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address_x as Word);
-                    self.a_register &= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsAndAbs => {
-                    let address = self.fetch_word(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, address);
-                    self.a_register &= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsAndAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    if !CPU::check_same_page(absolute_address, absolute_address_x) {
-                        cycles -= 1;
-                    }
-                    self.a_register &= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsAndAbsY => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_y = absolute_address + self.y_register as Word;
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_y);
-                    if !CPU::check_same_page(absolute_address, absolute_address_y) {
-                        cycles -= 1;
-                    }
-                    self.a_register &= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsAndIndX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address_x);
-                    let value = self.read_byte(&mut cycles, memory, indirect_address);
-                    self.a_register &= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsAndIndY => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address);
-                    let indirect_address_y = indirect_address + self.y_register as Word;
-                    if !CPU::check_same_page(indirect_address, indirect_address_y) {
-                        cycles -= 1;
-                    }
-                    let value = self.read_byte(&mut cycles, memory, indirect_address_y);
-                    self.a_register &= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                // EOR
-                Instruction::InsEorIm => {
-                    let value = self.fetch_byte(&mut cycles, memory);
-                    self.a_register ^= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsEorZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.a_register ^= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsEorZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address_x as Word);
-                    self.a_register ^= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsEorAbs => {
-                    let address = self.fetch_word(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, address);
-                    self.a_register ^= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsEorAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    if !CPU::check_same_page(absolute_address, absolute_address_x) {
-                        cycles -= 1;
-                    }
-                    self.a_register ^= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsEorAbsY => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_y = absolute_address + self.y_register as Word;
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_y);
-                    if !CPU::check_same_page(absolute_address, absolute_address_y) {
-                        cycles -= 1;
-                    }
-                    self.a_register ^= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsEorIndX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address_x);
-                    let value = self.read_byte(&mut cycles, memory, indirect_address);
-                    self.a_register ^= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsEorIndY => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address);
-                    let indirect_address_y = indirect_address + self.y_register as Word;
-                    if !CPU::check_same_page(indirect_address, indirect_address_y) {
-                        cycles -= 1;
-                    }
-                    let value = self.read_byte(&mut cycles, memory, indirect_address_y);
-                    self.a_register ^= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                // ORA
-                Instruction::InsOraIm => {
-                    let value = self.fetch_byte(&mut cycles, memory);
-                    self.a_register |= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsOraZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.a_register |= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsOraZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address_x as Word);
-                    self.a_register |= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsOraAbs => {
-                    let address = self.fetch_word(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, address);
-                    self.a_register |= value;
-                    self.set_z_n_flags(self.a_register);
+    /// Absolute indexed addressing. Reads only pay for the extra cycle when a page is
+    /// crossed, writes and read-modify-writes always pay for it.
+    fn absolute_indexed_address<B: Bus>(
+        &mut self,
+        cycles: &mut i32,
+        bus: &mut B,
+        index: Byte,
+        always_penalty: bool,
+    ) -> Word {
+        let absolute_address = self.fetch_word(cycles, bus);
+        let absolute_address_indexed = absolute_address.wrapping_add(index as Word);
+        if always_penalty || !CPU::check_same_page(absolute_address, absolute_address_indexed) {
+            *cycles -= 1;
+        }
+        absolute_address_indexed
+    }
+
+    fn indexed_indirect_address<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B) -> Word {
+        let zero_page_address = self.fetch_byte(cycles, bus);
+        let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
+        *cycles -= 1;
+        self.read_word_from_zero_page(cycles, bus, zero_page_address_x)
+    }
+
+    fn indirect_indexed_address<B: Bus>(
+        &mut self,
+        cycles: &mut i32,
+        bus: &mut B,
+        always_penalty: bool,
+    ) -> Word {
+        let zero_page_address = self.fetch_byte(cycles, bus);
+        let indirect_address = self.read_word_from_zero_page(cycles, bus, zero_page_address);
+        let indirect_address_y = indirect_address.wrapping_add(self.y_register as Word);
+        if always_penalty || !CPU::check_same_page(indirect_address, indirect_address_y) {
+            *cycles -= 1;
+        }
+        indirect_address_y
+    }
+
+    fn zero_page_indirect_address<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B) -> Word {
+        let zero_page_address = self.fetch_byte(cycles, bus);
+        self.read_word_from_zero_page(cycles, bus, zero_page_address)
+    }
+
+    fn test_and_modify_bits<B: Bus>(
+        &mut self,
+        cycles: &mut i32,
+        bus: &mut B,
+        address: Word,
+        set: bool,
+    ) {
+        let value = self.read_byte(cycles, bus, address);
+        self.status.zero = (self.a_register & value) == 0;
+        let value = match set {
+            true => value | self.a_register,
+            false => value & !self.a_register,
+        };
+        *cycles -= 1;
+        self.write_byte(value, address, cycles, bus);
+    }
+
+    // Undocumented NMOS operations
+    fn shift_left_or<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B, address: Word) {
+        let lhs = self.read_byte(cycles, bus, address);
+        let value = self.shift_left(cycles, lhs);
+        self.write_byte(value, address, cycles, bus);
+        self.a_register |= value;
+        self.set_z_n_flags(self.a_register);
+    }
+
+    fn roll_left_and<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B, address: Word) {
+        let lhs = self.read_byte(cycles, bus, address);
+        let value = self.roll_left(cycles, lhs);
+        self.write_byte(value, address, cycles, bus);
+        self.a_register &= value;
+        self.set_z_n_flags(self.a_register);
+    }
+
+    fn shift_right_eor<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B, address: Word) {
+        let lhs = self.read_byte(cycles, bus, address);
+        let value = self.shift_right(cycles, lhs);
+        self.write_byte(value, address, cycles, bus);
+        self.a_register ^= value;
+        self.set_z_n_flags(self.a_register);
+    }
+
+    fn roll_right_add<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B, address: Word) {
+        let lhs = self.read_byte(cycles, bus, address);
+        let value = self.roll_right(cycles, lhs);
+        self.write_byte(value, address, cycles, bus);
+        self.add_with_carry(cycles, bus, value);
+    }
+
+    fn decrement_compare<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B, address: Word) {
+        let value = self.read_byte(cycles, bus, address).wrapping_sub(1);
+        *cycles -= 1;
+        self.write_byte(value, address, cycles, bus);
+        self.compare_register(self.a_register, value);
+    }
+
+    fn increment_subtract<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B, address: Word) {
+        let value = self.read_byte(cycles, bus, address).wrapping_add(1);
+        *cycles -= 1;
+        self.write_byte(value, address, cycles, bus);
+        self.subtract_with_carry(cycles, bus, value);
+    }
+
+    fn load_a_and_x(&mut self, value: Byte) {
+        self.a_register = value;
+        self.x_register = value;
+        self.set_z_n_flags(value);
+    }
+
+    fn and_roll_right(&mut self, rhs: Byte) {
+        let and_result = self.a_register & rhs;
+        let mut value = (and_result >> 1) | ((self.status.carry as Byte) << 7);
+        if self.status.decimal_mode {
+            self.status.negative = self.status.carry;
+            self.status.zero = value == 0;
+            self.status.overflow = (and_result ^ value) & 0x40 != 0;
+            if (and_result & 0x0F) + (and_result & 0x01) > 0x05 {
+                value = (value & 0xF0) | (value.wrapping_add(0x06) & 0x0F);
+            }
+            self.status.carry = (and_result & 0xF0) as Word + (and_result & 0x10) as Word > 0x50;
+            if self.status.carry {
+                value = value.wrapping_add(0x60);
+            }
+        } else {
+            self.set_z_n_flags(value);
+            self.status.carry = value & 0x40 != 0;
+            self.status.overflow = ((value >> 6) ^ (value >> 5)) & 1 != 0;
+        }
+        self.a_register = value;
+    }
+
+    /// SHA, SHX, SHY and TAS store `value & (high byte + 1)`. When the index crosses a
+    /// page the stored value also replaces the high byte of the target address.
+    fn store_and_high_byte<B: Bus>(
+        &mut self,
+        cycles: &mut i32,
+        bus: &mut B,
+        indexed_address: Word,
+        index: Byte,
+        value: Byte,
+    ) {
+        let base_address = indexed_address.wrapping_sub(index as Word);
+        let mut address = indexed_address;
+        let value = value & ((base_address >> 8) as Byte).wrapping_add(1);
+        if !CPU::check_same_page(base_address, address) {
+            address = ((value as Word) << 8) | (address & 0x00FF);
+        }
+        self.write_byte(value, address, cycles, bus);
+    }
+
+    /// Runs instructions until `cycles` have been spent and returns the cycles actually
+    /// run. The last instruction always completes, and the cycles it runs past the budget
+    /// are taken off the budget of the next call.
+    pub fn execute<B: Bus>(&mut self, cycles: i32, bus: &mut B) -> Result<i32, InstructionsError> {
+        let budget = cycles - self.cycle_debt;
+        let (cycles_executed, cycles_left) = self.run(budget, bus)?;
+        // Only an instruction running past the budget leaves debt, idling never does
+        self.cycle_debt = (-cycles_left).max(0);
+        Ok(cycles_executed)
+    }
+
+    /// Returns the cycles run and what is left of the budget, negative when the last
+    /// instruction ran past it.
+    fn run<B: Bus>(&mut self, cycles: i32, bus: &mut B) -> Result<(i32, i32), InstructionsError> {
+        let mut cycles_left = cycles;
+        let result = self.run_instructions(&mut cycles_left, bus);
+        // A stopped or waiting CPU idles away the rest of the budget
+        let idle_cycles = match result {
+            Ok(()) if self.stopped || self.waiting_for_interrupt => cycles_left.max(0),
+            _ => 0,
+        };
+        let cycles_executed = cycles - cycles_left + idle_cycles;
+        self.total_cycles += cycles_executed as u64;
+        result.map(|()| (cycles_executed, cycles_left))
+    }
+
+    fn run_instructions<B: Bus>(
+        &mut self,
+        cycles: &mut i32,
+        bus: &mut B,
+    ) -> Result<(), InstructionsError> {
+        let opcodes = opcode_table(self.variant, self.undocumented_opcodes);
+        while *cycles > 0 {
+            if self.stopped {
+                break;
+            }
+            let interrupted_at = self.program_counter;
+            if self.poll_interrupts(cycles, bus) {
+                // Entering the handler pushes to the stack and reads the vector
+                if let Some(fault) = bus.take_fault() {
+                    return Err(CPU::fault_error(interrupted_at, fault));
                 }
-                Instruction::InsOraAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    if !CPU::check_same_page(absolute_address, absolute_address_x) {
-                        cycles -= 1;
+                continue;
+            }
+            if self.waiting_for_interrupt {
+                break;
+            }
+            let program_counter = self.program_counter;
+            let opcode = self.fetch_opcode(cycles, bus);
+            if let Some(fault) = bus.take_fault() {
+                self.program_counter = program_counter;
+                *cycles += 1;
+                return Err(CPU::fault_error(program_counter, fault));
+            }
+            let entry = match &opcodes[opcode as usize] {
+                Some(entry) => entry,
+                None if self.variant == CpuVariant::Cmos65C02 => {
+                    let (length, nop_cycles) = Instruction::undefined_65c02_nop(opcode);
+                    self.program_counter = self.program_counter.wrapping_add(length - 1);
+                    *cycles -= nop_cycles - 1;
+                    self.instructions_retired += 1;
+                    continue;
+                }
+                None => return Err(InstructionsError::InstructionDoesntExist(opcode)),
+            };
+            let operand = self.resolve_operand(cycles, bus, entry.operation, entry.mode);
+            self.perform(cycles, bus, opcode, entry.operation, operand)?;
+            self.instructions_retired += 1;
+            if let Some(fault) = bus.take_fault() {
+                return Err(CPU::fault_error(program_counter, fault));
+            }
+        }
+        Ok(())
+    }
+
+    fn fault_error(program_counter: Word, fault: BusFault) -> InstructionsError {
+        match fault {
+            BusFault::WriteToRom { address, value } => InstructionsError::WriteToRom {
+                program_counter,
+                address,
+                value,
+            },
+            BusFault::Watchpoint(hit) => InstructionsError::WatchpointHit {
+                program_counter,
+                hit,
+            },
+        }
+    }
+
+    /// Runs exactly one instruction, servicing a pending interrupt first. Returns `None`
+    /// without doing anything when the CPU is stopped or waiting for an interrupt. Cycle
+    /// debt left by `execute` is not paid by stepping.
+    pub fn step<B: Bus>(&mut self, bus: &mut B) -> Result<Option<StepResult>, InstructionsError> {
+        if self.stopped {
+            return Ok(None);
+        }
+        let mut interrupt_cycles = 0;
+        let interrupted_at = self.program_counter;
+        let interrupt_taken = self.poll_interrupts(&mut interrupt_cycles, bus);
+        if let Some(fault) = bus.take_fault() {
+            self.total_cycles += -interrupt_cycles as u64;
+            return Err(CPU::fault_error(interrupted_at, fault));
+        }
+        if self.waiting_for_interrupt {
+            return Ok(None);
+        }
+
+        let program_counter = self.program_counter;
+        let opcode = bus.peek(program_counter);
+        let operand = [
+            bus.peek(program_counter.wrapping_add(1)),
+            bus.peek(program_counter.wrapping_add(2)),
+        ];
+        let entry = opcode_table(self.variant, self.undocumented_opcodes)[opcode as usize];
+        let (effective_address, base_address) = match &entry {
+            Some(entry) => self.peek_effective_address(bus, entry.mode, operand),
+            None => (None, None),
+        };
+
+        self.total_cycles += -interrupt_cycles as u64;
+        let (cycles, _) = self.run(1, bus)?;
+
+        let mut result = StepResult {
+            program_counter,
+            opcode,
+            instruction: None,
+            operand,
+            operand_length: 0,
+            effective_address,
+            cycles: cycles - interrupt_cycles,
+            interrupt_taken,
+            branch_taken: false,
+            page_crossed: false,
+        };
+        let Some(entry) = entry else {
+            let (length, _) = Instruction::undefined_65c02_nop(opcode);
+            result.operand_length = length as usize - 1;
+            return Ok(Some(result));
+        };
+        result.instruction = Some(entry.instruction);
+        result.operand_length = entry.length as usize - 1;
+        if entry.operation.is_branch() {
+            result.branch_taken = cycles > entry.cycles;
+            result.page_crossed = result.branch_taken
+                && !CPU::check_same_page(
+                    program_counter.wrapping_add(entry.length),
+                    self.program_counter,
+                );
+        } else if let (Some(base_address), Some(address)) = (base_address, effective_address) {
+            result.page_crossed = !CPU::check_same_page(base_address, address);
+        }
+        Ok(Some(result))
+    }
+
+    /// Works out the address an instruction will access without running it or spending
+    /// cycles. Indexed modes also return the address before indexing.
+    fn peek_effective_address<B: Bus>(
+        &self,
+        bus: &B,
+        mode: AddressingMode,
+        operand: [Byte; 2],
+    ) -> (Option<Word>, Option<Word>) {
+        let zero_page = operand[0];
+        let absolute = Word::from_le_bytes(operand);
+        let read_zero_page_word = |address: Byte| {
+            Word::from_le_bytes([
+                bus.peek(address as Word),
+                bus.peek(address.wrapping_add(1) as Word),
+            ])
+        };
+        let read_word = |address: Word| {
+            Word::from_le_bytes([bus.peek(address), bus.peek(address.wrapping_add(1))])
+        };
+        let indexed =
+            |base: Word, index: Byte| (Some(base.wrapping_add(index as Word)), Some(base));
+        let next_instruction = self.program_counter.wrapping_add(mode.length());
+        match mode {
+            AddressingMode::Implied
+            | AddressingMode::Accumulator
+            | AddressingMode::Immediate
+            | AddressingMode::ImmediateWord => (None, None),
+            AddressingMode::ZeroPage => (Some(zero_page as Word), None),
+            AddressingMode::ZeroPageX => {
+                (Some(zero_page.wrapping_add(self.x_register) as Word), None)
+            }
+            AddressingMode::ZeroPageY => {
+                (Some(zero_page.wrapping_add(self.y_register) as Word), None)
+            }
+            AddressingMode::Absolute => (Some(absolute), None),
+            AddressingMode::AbsoluteX => indexed(absolute, self.x_register),
+            AddressingMode::AbsoluteY => indexed(absolute, self.y_register),
+            AddressingMode::Indirect => {
+                let high_address = match self.variant {
+                    CpuVariant::Nmos6502 => {
+                        (absolute & 0xFF00) | (absolute.wrapping_add(1) & 0x00FF)
                     }
-                    self.a_register |= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsOraAbsY => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_y = absolute_address + self.y_register as Word;
-                    let value = self.read_byte(&mut cycles, memory, absolute_address_y);
-                    if !CPU::check_same_page(absolute_address, absolute_address_y) {
-                        cycles -= 1;
+                    CpuVariant::Cmos65C02 => absolute.wrapping_add(1),
+                };
+                (
+                    Some(Word::from_le_bytes([
+                        bus.peek(absolute),
+                        bus.peek(high_address),
+                    ])),
+                    None,
+                )
+            }
+            AddressingMode::IndexedIndirect => (
+                Some(read_zero_page_word(zero_page.wrapping_add(self.x_register))),
+                None,
+            ),
+            AddressingMode::IndirectIndexed => {
+                indexed(read_zero_page_word(zero_page), self.y_register)
+            }
+            AddressingMode::ZeroPageIndirect => (Some(read_zero_page_word(zero_page)), None),
+            AddressingMode::AbsoluteIndexedIndirect => (
+                Some(read_word(absolute.wrapping_add(self.x_register as Word))),
+                None,
+            ),
+            AddressingMode::Relative => (
+                Some(next_instruction.wrapping_add(zero_page as SByte as Word)),
+                None,
+            ),
+            AddressingMode::ZeroPageRelative => (
+                Some(next_instruction.wrapping_add(operand[1] as SByte as Word)),
+                None,
+            ),
+        }
+    }
+
+    /// Fetches the operand bytes for an addressing mode and computes the effective
+    /// address, spending the cycles the addressing mode takes.
+    fn resolve_operand<B: Bus>(
+        &mut self,
+        cycles: &mut i32,
+        bus: &mut B,
+        operation: Operation,
+        mode: AddressingMode,
+    ) -> Operand {
+        match mode {
+            AddressingMode::Implied => Operand::Implied,
+            AddressingMode::Accumulator => Operand::Accumulator,
+            AddressingMode::Immediate => Operand::Immediate(self.fetch_byte(cycles, bus)),
+            AddressingMode::ImmediateWord => Operand::ImmediateWord(self.fetch_word(cycles, bus)),
+            AddressingMode::ZeroPage => Operand::Address(self.zero_page_address(cycles, bus)),
+            AddressingMode::ZeroPageX => {
+                Operand::Address(self.zero_page_indexed_address(cycles, bus, self.x_register))
+            }
+            AddressingMode::ZeroPageY => {
+                Operand::Address(self.zero_page_indexed_address(cycles, bus, self.y_register))
+            }
+            AddressingMode::Absolute => Operand::Address(self.fetch_word(cycles, bus)),
+            AddressingMode::AbsoluteX => Operand::Address(self.absolute_indexed_address(
+                cycles,
+                bus,
+                self.x_register,
+                operation.always_pays_index_penalty(self.variant),
+            )),
+            AddressingMode::AbsoluteY => Operand::Address(self.absolute_indexed_address(
+                cycles,
+                bus,
+                self.y_register,
+                operation.always_pays_index_penalty(self.variant),
+            )),
+            AddressingMode::Indirect => {
+                let indirect_address = self.fetch_word(cycles, bus);
+                let address = match self.variant {
+                    CpuVariant::Nmos6502 => {
+                        self.read_word_within_page(cycles, bus, indirect_address)
                     }
-                    self.a_register |= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsOraIndX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address_x);
-                    let value = self.read_byte(&mut cycles, memory, indirect_address);
-                    self.a_register |= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsOraIndY => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address);
-                    let indirect_address_y = indirect_address + self.y_register as Word;
-                    if !CPU::check_same_page(indirect_address, indirect_address_y) {
-                        cycles -= 1;
+                    CpuVariant::Cmos65C02 => {
+                        *cycles -= 1;
+                        self.read_word_absolute(cycles, bus, indirect_address)
                     }
-                    let value = self.read_byte(&mut cycles, memory, indirect_address_y);
-                    self.a_register |= value;
-                    self.set_z_n_flags(self.a_register);
-                }
-                // BIT
-                Instruction::InsBitZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    println!("{:0b}", value);
-                    self.status.zero = (self.a_register & value) == 0;
-                    self.status.negative = (value & ProcessorFlags::NEGATIVE_FLAG_BIT) != 0;
-                    self.status.overflow = (value & ProcessorFlags::OVERFLOW_FLAG_BIT) != 0;
-                }
-                Instruction::InsBitAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let value = self.read_byte(&mut cycles, memory, absolute_address);
-                    self.status.zero = (self.a_register & value) == 0;
-                    self.status.negative = (value & ProcessorFlags::NEGATIVE_FLAG_BIT) != 0;
-                    self.status.overflow = (value & ProcessorFlags::OVERFLOW_FLAG_BIT) != 0;
-                }
-                // Transfers
-                Instruction::InsTax => {
-                    self.x_register = self.a_register;
-                    cycles -= 1;
-                    self.set_z_n_flags(self.x_register);
-                }
-                Instruction::InsTay => {
-                    self.y_register = self.a_register;
-                    cycles -= 1;
-                    self.set_z_n_flags(self.y_register);
-                }
-                Instruction::InsTxa => {
-                    self.a_register = self.x_register;
-                    cycles -= 1;
-                    self.set_z_n_flags(self.a_register);
-                }
-                Instruction::InsTya => {
-                    self.a_register = self.y_register;
-                    cycles -= 1;
-                    self.set_z_n_flags(self.a_register);
-                }
-                // Increments
-                Instruction::InsInx => {
-                    self.x_register = self.x_register.wrapping_add(1);
-                    cycles -= 1;
-                    self.set_z_n_flags(self.x_register);
-                }
-                Instruction::InsIny => {
-                    self.y_register = self.y_register.wrapping_add(1);
-                    cycles -= 1;
-                    self.set_z_n_flags(self.y_register);
-                }
-                // Decrements
-                Instruction::InsDex => {
-                    self.x_register = self.x_register.wrapping_sub(1);
-                    cycles -= 1;
-                    self.set_z_n_flags(self.x_register);
-                }
-                Instruction::InsDey => {
-                    self.y_register = self.y_register.wrapping_sub(1);
-                    cycles -= 1;
-                    self.set_z_n_flags(self.y_register);
-                }
-                // DEC
-                Instruction::InsDecZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let value = self
-                        .read_byte(&mut cycles, memory, zero_page_address as Word)
-                        .wrapping_sub(1);
-                    cycles -= 1;
-                    self.write_byte(value, zero_page_address as Word, &mut cycles, memory);
-                    self.set_z_n_flags(value);
-                }
-                Instruction::InsDecZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let value = self
-                        .read_byte(&mut cycles, memory, zero_page_address_x as Word)
-                        .wrapping_sub(1);
-                    cycles -= 1;
-                    self.write_byte(value, zero_page_address_x as Word, &mut cycles, memory);
-                    self.set_z_n_flags(value);
-                }
-                Instruction::InsDecAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let value = self
-                        .read_byte(&mut cycles, memory, absolute_address)
-                        .wrapping_sub(1);
-                    cycles -= 1;
-                    self.write_byte(value, absolute_address, &mut cycles, memory);
-                    self.set_z_n_flags(value);
-                }
-                Instruction::InsDecAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    cycles -= 1;
-                    let value = self
-                        .read_byte(&mut cycles, memory, absolute_address_x)
-                        .wrapping_sub(1);
-                    cycles -= 1;
-                    self.write_byte(value, absolute_address_x, &mut cycles, memory);
-                    self.set_z_n_flags(value);
-                }
-                // INC
-                Instruction::InsIncZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let value = self
-                        .read_byte(&mut cycles, memory, zero_page_address as Word)
-                        .wrapping_add(1);
-                    cycles -= 1;
-                    self.write_byte(value, zero_page_address as Word, &mut cycles, memory);
-                    self.set_z_n_flags(value);
-                }
-                Instruction::InsIncZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let value = self
-                        .read_byte(&mut cycles, memory, zero_page_address_x as Word)
-                        .wrapping_add(1);
-                    cycles -= 1;
-                    self.write_byte(value, zero_page_address_x as Word, &mut cycles, memory);
-                    self.set_z_n_flags(value);
-                }
-                Instruction::InsIncAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let value = self
-                        .read_byte(&mut cycles, memory, absolute_address)
-                        .wrapping_add(1);
-                    cycles -= 1;
-                    self.write_byte(value, absolute_address, &mut cycles, memory);
-                    self.set_z_n_flags(value);
-                }
-                Instruction::InsIncAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    cycles -= 1;
-                    let value = self
-                        .read_byte(&mut cycles, memory, absolute_address_x)
-                        .wrapping_add(1);
-                    cycles -= 1;
-                    self.write_byte(value, absolute_address_x, &mut cycles, memory);
-                    self.set_z_n_flags(value);
-                }
-                // Branching
-                Instruction::InsBeq => {
-                    self.branch_if(&mut cycles, memory, self.status.zero, true);
-                }
-                Instruction::InsBne => {
-                    self.branch_if(&mut cycles, memory, self.status.zero, false);
-                }
-                Instruction::InsBcs => {
-                    self.branch_if(&mut cycles, memory, self.status.carry, true);
-                }
-                Instruction::InsBcc => {
-                    self.branch_if(&mut cycles, memory, self.status.carry, false);
-                }
-                Instruction::InsBmi => {
-                    self.branch_if(&mut cycles, memory, self.status.negative, true);
-                }
-                Instruction::InsBpl => {
-                    self.branch_if(&mut cycles, memory, self.status.negative, false);
-                }
-                Instruction::InsBvs => {
-                    self.branch_if(&mut cycles, memory, self.status.overflow, true);
-                }
-                Instruction::InsBvc => {
-                    self.branch_if(&mut cycles, memory, self.status.overflow, false);
-                }
-                // Status flags
-                Instruction::InsClc => {
-                    self.status.carry = false;
-                    cycles -= 1;
-                }
-                Instruction::InsSec => {
-                    self.status.carry = true;
-                    cycles -= 1;
+                };
+                Operand::Address(address)
+            }
+            AddressingMode::IndexedIndirect => {
+                Operand::Address(self.indexed_indirect_address(cycles, bus))
+            }
+            AddressingMode::IndirectIndexed => Operand::Address(self.indirect_indexed_address(
+                cycles,
+                bus,
+                operation.always_pays_index_penalty(self.variant),
+            )),
+            AddressingMode::ZeroPageIndirect => {
+                Operand::Address(self.zero_page_indirect_address(cycles, bus))
+            }
+            AddressingMode::AbsoluteIndexedIndirect => {
+                let absolute_address = self.fetch_word(cycles, bus);
+                let indirect_address = absolute_address.wrapping_add(self.x_register as Word);
+                *cycles -= 1;
+                Operand::Address(self.read_word_absolute(cycles, bus, indirect_address))
+            }
+            AddressingMode::Relative => Operand::Relative(self.fetch_byte(cycles, bus) as SByte),
+            AddressingMode::ZeroPageRelative => {
+                let address = self.zero_page_address(cycles, bus);
+                let offset = self.fetch_byte(cycles, bus) as SByte;
+                Operand::ZeroPageRelative(address, offset)
+            }
+        }
+    }
+
+    fn read_operand<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B, operand: Operand) -> Byte {
+        match operand {
+            Operand::Immediate(value) => value,
+            Operand::Accumulator => self.a_register,
+            Operand::Address(address) => self.read_byte(cycles, bus, address),
+            _ => unreachable!("{:?} has no byte value", operand),
+        }
+    }
+
+    fn write_operand<B: Bus>(
+        &mut self,
+        cycles: &mut i32,
+        bus: &mut B,
+        operand: Operand,
+        value: Byte,
+    ) {
+        match operand {
+            Operand::Accumulator => self.a_register = value,
+            Operand::Address(address) => self.write_byte(value, address, cycles, bus),
+            _ => unreachable!("{:?} can't be written", operand),
+        }
+    }
+
+    /// Read-modify-write on bus or the accumulator, accumulator updates don't spend
+    /// cycles on the bus.
+    fn modify_operand<B: Bus>(
+        &mut self,
+        cycles: &mut i32,
+        bus: &mut B,
+        operand: Operand,
+        modify: impl FnOnce(&mut Self, &mut i32, Byte) -> Byte,
+    ) -> Byte {
+        let lhs = self.read_operand(cycles, bus, operand);
+        let value = modify(self, cycles, lhs);
+        self.write_operand(cycles, bus, operand, value);
+        value
+    }
+
+    fn bit_test(&mut self, value: Byte) {
+        self.status.zero = (self.a_register & value) == 0;
+        self.status.negative = (value & ProcessorFlags::NEGATIVE_FLAG_BIT) != 0;
+        self.status.overflow = (value & ProcessorFlags::OVERFLOW_FLAG_BIT) != 0;
+    }
+
+    fn perform<B: Bus>(
+        &mut self,
+        cycles: &mut i32,
+        bus: &mut B,
+        opcode: Byte,
+        operation: Operation,
+        operand: Operand,
+    ) -> Result<(), InstructionsError> {
+        match operation {
+            // Loads
+            Operation::Lda => {
+                self.a_register = self.read_operand(cycles, bus, operand);
+                self.set_z_n_flags(self.a_register);
+            }
+            Operation::Ldx => {
+                self.x_register = self.read_operand(cycles, bus, operand);
+                self.set_z_n_flags(self.x_register);
+            }
+            Operation::Ldy => {
+                self.y_register = self.read_operand(cycles, bus, operand);
+                self.set_z_n_flags(self.y_register);
+            }
+            // Stores
+            Operation::Sta => self.write_operand(cycles, bus, operand, self.a_register),
+            Operation::Stx => self.write_operand(cycles, bus, operand, self.x_register),
+            Operation::Sty => self.write_operand(cycles, bus, operand, self.y_register),
+            Operation::Stz => self.write_operand(cycles, bus, operand, 0),
+            // Jumps and calls
+            Operation::Jsr => {
+                self.push_program_counter_minus_one_to_stack(cycles, bus);
+                self.program_counter = operand.address();
+                *cycles -= 1;
+            }
+            Operation::Rts => {
+                let return_address = self.pop_word_from_stack(cycles, bus);
+                self.program_counter = return_address.wrapping_add(1);
+                *cycles -= 2;
+            }
+            Operation::Jmp => {
+                self.program_counter = operand.address();
+            }
+            // Stack operations
+            Operation::Tsx => {
+                self.x_register = self.stack_pointer;
+                *cycles -= 1;
+                self.set_z_n_flags(self.x_register);
+            }
+            Operation::Txs => {
+                self.stack_pointer = self.x_register;
+                *cycles -= 1;
+            }
+            Operation::Pha => self.push_byte_to_stack(self.a_register, cycles, bus),
+            Operation::Phx => self.push_byte_to_stack(self.x_register, cycles, bus),
+            Operation::Phy => self.push_byte_to_stack(self.y_register, cycles, bus),
+            Operation::Php => {
+                // Only the pushed copy has B and the unused bit set
+                let mut pushed_status = self.status;
+                pushed_status.break_command = true;
+                pushed_status.unused = true;
+                self.push_byte_to_stack(pushed_status.into_u8(), cycles, bus);
+            }
+            Operation::Pla => {
+                self.a_register = self.pop_byte_from_stack(cycles, bus);
+                self.set_z_n_flags(self.a_register);
+                *cycles -= 1;
+            }
+            Operation::Plx => {
+                self.x_register = self.pop_byte_from_stack(cycles, bus);
+                self.set_z_n_flags(self.x_register);
+                *cycles -= 1;
+            }
+            Operation::Ply => {
+                self.y_register = self.pop_byte_from_stack(cycles, bus);
+                self.set_z_n_flags(self.y_register);
+                *cycles -= 1;
+            }
+            Operation::Plp => {
+                self.status = self.pop_byte_from_stack(cycles, bus).into();
+                *cycles -= 1;
+            }
+            // Logical
+            Operation::And => {
+                self.a_register &= self.read_operand(cycles, bus, operand);
+                self.set_z_n_flags(self.a_register);
+            }
+            Operation::Eor => {
+                self.a_register ^= self.read_operand(cycles, bus, operand);
+                self.set_z_n_flags(self.a_register);
+            }
+            Operation::Ora => {
+                self.a_register |= self.read_operand(cycles, bus, operand);
+                self.set_z_n_flags(self.a_register);
+            }
+            Operation::Bit => {
+                let value = self.read_operand(cycles, bus, operand);
+                match operand {
+                    // Immediate BIT only affects the zero flag
+                    Operand::Immediate(_) => self.status.zero = (self.a_register & value) == 0,
+                    _ => self.bit_test(value),
                 }
-                Instruction::InsCld => {
+            }
+            // Transfers
+            Operation::Tax => {
+                self.x_register = self.a_register;
+                *cycles -= 1;
+                self.set_z_n_flags(self.x_register);
+            }
+            Operation::Tay => {
+                self.y_register = self.a_register;
+                *cycles -= 1;
+                self.set_z_n_flags(self.y_register);
+            }
+            Operation::Txa => {
+                self.a_register = self.x_register;
+                *cycles -= 1;
+                self.set_z_n_flags(self.a_register);
+            }
+            Operation::Tya => {
+                self.a_register = self.y_register;
+                *cycles -= 1;
+                self.set_z_n_flags(self.a_register);
+            }
+            // Increments and decrements
+            Operation::Inx => {
+                self.x_register = self.x_register.wrapping_add(1);
+                *cycles -= 1;
+                self.set_z_n_flags(self.x_register);
+            }
+            Operation::Iny => {
+                self.y_register = self.y_register.wrapping_add(1);
+                *cycles -= 1;
+                self.set_z_n_flags(self.y_register);
+            }
+            Operation::Dex => {
+                self.x_register = self.x_register.wrapping_sub(1);
+                *cycles -= 1;
+                self.set_z_n_flags(self.x_register);
+            }
+            Operation::Dey => {
+                self.y_register = self.y_register.wrapping_sub(1);
+                *cycles -= 1;
+                self.set_z_n_flags(self.y_register);
+            }
+            Operation::Inc => {
+                let value = self.modify_operand(cycles, bus, operand, |_, cycles, lhs| {
+                    *cycles -= 1;
+                    lhs.wrapping_add(1)
+                });
+                self.set_z_n_flags(value);
+            }
+            Operation::Dec => {
+                let value = self.modify_operand(cycles, bus, operand, |_, cycles, lhs| {
+                    *cycles -= 1;
+                    lhs.wrapping_sub(1)
+                });
+                self.set_z_n_flags(value);
+            }
+            // Branches
+            Operation::Beq => self.branch(cycles, operand.offset(), self.status.zero),
+            Operation::Bne => self.branch(cycles, operand.offset(), !self.status.zero),
+            Operation::Bcs => self.branch(cycles, operand.offset(), self.status.carry),
+            Operation::Bcc => self.branch(cycles, operand.offset(), !self.status.carry),
+            Operation::Bmi => self.branch(cycles, operand.offset(), self.status.negative),
+            Operation::Bpl => self.branch(cycles, operand.offset(), !self.status.negative),
+            Operation::Bvs => self.branch(cycles, operand.offset(), self.status.overflow),
+            Operation::Bvc => self.branch(cycles, operand.offset(), !self.status.overflow),
+            Operation::Bra => self.branch(cycles, operand.offset(), true),
+            // Status flags
+            Operation::Clc => {
+                self.status.carry = false;
+                *cycles -= 1;
+            }
+            Operation::Sec => {
+                self.status.carry = true;
+                *cycles -= 1;
+            }
+            Operation::Cld => {
+                self.status.decimal_mode = false;
+                *cycles -= 1;
+            }
+            Operation::Sed => {
+                self.status.decimal_mode = true;
+                *cycles -= 1;
+            }
+            Operation::Cli => {
+                self.status.interupt_disable = false;
+                *cycles -= 1;
+            }
+            Operation::Sei => {
+                self.status.interupt_disable = true;
+                *cycles -= 1;
+            }
+            Operation::Clv => {
+                self.status.overflow = false;
+                *cycles -= 1;
+            }
+            // Arithmetic
+            Operation::Adc => {
+                let rhs = self.read_operand(cycles, bus, operand);
+                self.add_with_carry(cycles, bus, rhs);
+            }
+            Operation::Sbc => {
+                let rhs = self.read_operand(cycles, bus, operand);
+                self.subtract_with_carry(cycles, bus, rhs);
+            }
+            Operation::Cmp => {
+                let rhs = self.read_operand(cycles, bus, operand);
+                self.compare_register(self.a_register, rhs);
+            }
+            Operation::Cpx => {
+                let rhs = self.read_operand(cycles, bus, operand);
+                self.compare_register(self.x_register, rhs);
+            }
+            Operation::Cpy => {
+                let rhs = self.read_operand(cycles, bus, operand);
+                self.compare_register(self.y_register, rhs);
+            }
+            // Shifts
+            Operation::Asl => {
+                self.modify_operand(cycles, bus, operand, CPU::shift_left);
+            }
+            Operation::Lsr => {
+                self.modify_operand(cycles, bus, operand, CPU::shift_right);
+            }
+            Operation::Rol => {
+                self.modify_operand(cycles, bus, operand, CPU::roll_left);
+            }
+            Operation::Ror => {
+                self.modify_operand(cycles, bus, operand, CPU::roll_right);
+            }
+            // Misc
+            Operation::Nop => match operand {
+                Operand::Implied => *cycles -= 1,
+                Operand::Address(address) => {
+                    self.read_byte(cycles, bus, address);
+                }
+                _ => {}
+            },
+            Operation::Brk => {
+                self.status.break_command = true;
+                self.status.unused = true;
+                let interrupt_vector = CPU::IRQ_VECTOR;
+                self.push_program_counter_plus_one_to_stack(cycles, bus);
+                self.push_byte_to_stack(self.status.into_u8(), cycles, bus);
+                self.status.interupt_disable = true;
+                if self.variant == CpuVariant::Cmos65C02 {
                     self.status.decimal_mode = false;
-                    cycles -= 1;
-                }
-                Instruction::InsSed => {
-                    self.status.decimal_mode = true;
-                    cycles -= 1;
-                }
-                Instruction::InsCli => {
-                    self.status.interupt_disable = false;
-                    cycles -= 1;
-                }
-                Instruction::InsSei => {
-                    self.status.interupt_disable = true;
-                    cycles -= 1;
-                }
-                Instruction::InsClv => {
-                    self.status.overflow = false;
-                    cycles -= 1;
-                }
-                // ADC
-                Instruction::InsAdcIm => {
-                    let rhs = self.fetch_byte(&mut cycles, memory);
-                    self.add_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsAdcZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let rhs = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.add_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsAdcZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let rhs = self.read_byte(&mut cycles, memory, zero_page_address_x as Word);
-                    self.add_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsAdcAbs => {
-                    let address = self.fetch_word(&mut cycles, memory);
-                    let rhs = self.read_byte(&mut cycles, memory, address);
-                    self.add_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsAdcAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    let rhs = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    if !CPU::check_same_page(absolute_address, absolute_address_x) {
-                        cycles -= 1;
-                    }
-                    self.add_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsAdcAbsY => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_y = absolute_address + self.y_register as Word;
-                    let rhs = self.read_byte(&mut cycles, memory, absolute_address_y);
-                    if !CPU::check_same_page(absolute_address, absolute_address_y) {
-                        cycles -= 1;
-                    }
-                    self.add_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsAdcIndX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let indirect_address =
-                        self.read_word_absolute(&mut cycles, memory, zero_page_address_x as Word);
-                    let rhs = self.read_byte(&mut cycles, memory, indirect_address);
-                    self.add_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsAdcIndY => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address);
-                    let indirect_address_y = indirect_address + self.y_register as Word;
-                    if !CPU::check_same_page(indirect_address, indirect_address_y) {
-                        cycles -= 1;
-                    }
-                    let rhs = self.read_byte(&mut cycles, memory, indirect_address_y);
-                    self.add_with_carry(&mut cycles, memory, rhs);
-                }
-                // SBC
-                Instruction::InsSbcIm => {
-                    let rhs = self.fetch_byte(&mut cycles, memory);
-                    self.subtract_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsSbcZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let rhs = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.subtract_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsSbcZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let rhs = self.read_byte(&mut cycles, memory, zero_page_address_x as Word);
-                    self.subtract_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsSbcAbs => {
-                    let address = self.fetch_word(&mut cycles, memory);
-                    let rhs = self.read_byte(&mut cycles, memory, address);
-                    self.subtract_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsSbcAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    let rhs = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    if !CPU::check_same_page(absolute_address, absolute_address_x) {
-                        cycles -= 1;
-                    }
-                    self.subtract_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsSbcAbsY => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_y = absolute_address + self.y_register as Word;
-                    let rhs = self.read_byte(&mut cycles, memory, absolute_address_y);
-                    if !CPU::check_same_page(absolute_address, absolute_address_y) {
-                        cycles -= 1;
-                    }
-                    self.subtract_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsSbcIndX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let indirect_address =
-                        self.read_word_absolute(&mut cycles, memory, zero_page_address_x as Word);
-                    let rhs = self.read_byte(&mut cycles, memory, indirect_address);
-                    self.subtract_with_carry(&mut cycles, memory, rhs);
-                }
-                Instruction::InsSbcIndY => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address);
-                    let indirect_address_y = indirect_address + self.y_register as Word;
-                    if !CPU::check_same_page(indirect_address, indirect_address_y) {
-                        cycles -= 1;
-                    }
-                    let rhs = self.read_byte(&mut cycles, memory, indirect_address_y);
-                    self.subtract_with_carry(&mut cycles, memory, rhs);
-                }
-                // CMP
-                Instruction::InsCmpIm => {
-                    let rhs = self.fetch_byte(&mut cycles, memory);
-                    self.compare_register(self.a_register, rhs);
-                }
-                Instruction::InsCmpZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let rhs = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.compare_register(self.a_register, rhs);
-                }
-                Instruction::InsCmpZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let rhs = self.read_byte(&mut cycles, memory, zero_page_address_x as Word);
-                    self.compare_register(self.a_register, rhs);
-                }
-                Instruction::InsCmpAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let rhs = self.read_byte(&mut cycles, memory, absolute_address);
-                    self.compare_register(self.a_register, rhs);
-                }
-                Instruction::InsCmpAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    if !CPU::check_same_page(absolute_address, absolute_address_x) {
-                        cycles -= 1;
-                    }
-                    let rhs = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    self.compare_register(self.a_register, rhs);
-                }
-                Instruction::InsCmpAbsY => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_y = absolute_address + self.y_register as Word;
-                    if !CPU::check_same_page(absolute_address, absolute_address_y) {
-                        cycles -= 1;
-                    }
-                    let rhs = self.read_byte(&mut cycles, memory, absolute_address_y);
-                    self.compare_register(self.a_register, rhs);
-                }
-                Instruction::InsCmpIndX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x = zero_page_address.wrapping_add(self.x_register);
-                    cycles -= 1;
-                    let indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address_x);
-                    let rhs = self.read_byte(&mut cycles, memory, indirect_address);
-                    self.compare_register(self.a_register, rhs);
-                }
-                Instruction::InsCmpIndY => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let indirect_address =
-                        self.read_word_from_zero_page(&mut cycles, memory, zero_page_address);
-                    let indirect_address_y = indirect_address + self.y_register as Word;
-                    if !CPU::check_same_page(indirect_address, indirect_address_y) {
-                        cycles -= 1;
-                    }
-                    let rhs = self.read_byte(&mut cycles, memory, indirect_address_y);
-                    self.compare_register(self.a_register, rhs);
-                }
-                // CPX
-                Instruction::InsCpxIm => {
-                    let rhs = self.fetch_byte(&mut cycles, memory);
-                    self.compare_register(self.x_register, rhs);
-                }
-                Instruction::InsCpxZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let rhs = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.compare_register(self.x_register, rhs);
-                }
-                Instruction::InsCpxAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let rhs = self.read_byte(&mut cycles, memory, absolute_address);
-                    self.compare_register(self.x_register, rhs);
-                }
-                // CPY
-                Instruction::InsCpyIm => {
-                    let rhs = self.fetch_byte(&mut cycles, memory);
-                    self.compare_register(self.y_register, rhs);
-                }
-                Instruction::InsCpyZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let rhs = self.read_byte(&mut cycles, memory, zero_page_address as Word);
-                    self.compare_register(self.y_register, rhs);
-                }
-                Instruction::InsCpyAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let rhs = self.read_byte(&mut cycles, memory, absolute_address);
-                    self.compare_register(self.y_register, rhs);
-                }
-                // ASL
-                Instruction::InsAslA => {
-                    self.a_register = self.shift_left(&mut cycles, self.a_register);
-                }
-                Instruction::InsAslZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory) as Word;
-                    let lhs = self.read_byte(&mut cycles, memory, zero_page_address);
-                    let value = self.shift_left(&mut cycles, lhs);
-                    self.write_byte(value, zero_page_address, &mut cycles, memory);
-                }
-                Instruction::InsAslZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x =
-                        zero_page_address.wrapping_add(self.x_register) as Word;
-                    cycles -= 1;
-                    let lhs = self.read_byte(&mut cycles, memory, zero_page_address_x);
-                    let value = self.shift_left(&mut cycles, lhs);
-                    self.write_byte(value, zero_page_address_x, &mut cycles, memory);
-                }
-                Instruction::InsAslAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let lhs = self.read_byte(&mut cycles, memory, absolute_address);
-                    let value = self.shift_left(&mut cycles, lhs);
-                    self.write_byte(value, absolute_address, &mut cycles, memory);
-                }
-                Instruction::InsAslAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    cycles -= 1;
-                    let lhs = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    let value = self.shift_left(&mut cycles, lhs);
-                    self.write_byte(value, absolute_address_x, &mut cycles, memory);
-                }
-                // LSR
-                Instruction::InsLsrA => {
-                    self.a_register = self.shift_right(&mut cycles, self.a_register);
-                }
-                Instruction::InsLsrZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory) as Word;
-                    let lhs = self.read_byte(&mut cycles, memory, zero_page_address);
-                    let value = self.shift_right(&mut cycles, lhs);
-                    self.write_byte(value, zero_page_address, &mut cycles, memory);
-                }
-                Instruction::InsLsrZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x =
-                        zero_page_address.wrapping_add(self.x_register) as Word;
-                    cycles -= 1;
-                    let lhs = self.read_byte(&mut cycles, memory, zero_page_address_x);
-                    let value = self.shift_right(&mut cycles, lhs);
-                    self.write_byte(value, zero_page_address_x, &mut cycles, memory);
-                }
-                Instruction::InsLsrAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let lhs = self.read_byte(&mut cycles, memory, absolute_address);
-                    let value = self.shift_right(&mut cycles, lhs);
-                    self.write_byte(value, absolute_address, &mut cycles, memory);
-                }
-                Instruction::InsLsrAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    cycles -= 1;
-                    let lhs = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    let value = self.shift_right(&mut cycles, lhs);
-                    self.write_byte(value, absolute_address_x, &mut cycles, memory);
-                }
-                // ROL
-                Instruction::InsRolA => {
-                    self.a_register = self.roll_left(&mut cycles, self.a_register);
-                }
-                Instruction::InsRolZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory) as Word;
-                    let lhs = self.read_byte(&mut cycles, memory, zero_page_address);
-                    let value = self.roll_left(&mut cycles, lhs);
-                    self.write_byte(value, zero_page_address, &mut cycles, memory);
                 }
-                Instruction::InsRolZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x =
-                        zero_page_address.wrapping_add(self.x_register) as Word;
-                    cycles -= 1;
-                    let lhs = self.read_byte(&mut cycles, memory, zero_page_address_x);
-                    let value = self.roll_left(&mut cycles, lhs);
-                    self.write_byte(value, zero_page_address_x, &mut cycles, memory);
-                }
-                Instruction::InsRolAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let lhs = self.read_byte(&mut cycles, memory, absolute_address);
-                    let value = self.roll_left(&mut cycles, lhs);
-                    self.write_byte(value, absolute_address, &mut cycles, memory);
-                }
-                Instruction::InsRolAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    cycles -= 1;
-                    let lhs = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    let value = self.roll_left(&mut cycles, lhs);
-                    self.write_byte(value, absolute_address_x, &mut cycles, memory);
-                }
-                // ROR
-                Instruction::InsRorA => {
-                    self.a_register = self.roll_right(&mut cycles, self.a_register);
-                }
-                Instruction::InsRorZp => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory) as Word;
-                    let lhs = self.read_byte(&mut cycles, memory, zero_page_address);
-                    let value = self.roll_right(&mut cycles, lhs);
-                    self.write_byte(value, zero_page_address, &mut cycles, memory);
-                }
-                Instruction::InsRorZpX => {
-                    let zero_page_address = self.fetch_byte(&mut cycles, memory);
-                    let zero_page_address_x =
-                        zero_page_address.wrapping_add(self.x_register) as Word;
-                    cycles -= 1;
-                    let lhs = self.read_byte(&mut cycles, memory, zero_page_address_x);
-                    let value = self.roll_right(&mut cycles, lhs);
-                    self.write_byte(value, zero_page_address_x, &mut cycles, memory);
-                }
-                Instruction::InsRorAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let lhs = self.read_byte(&mut cycles, memory, absolute_address);
-                    let value = self.roll_right(&mut cycles, lhs);
-                    self.write_byte(value, absolute_address, &mut cycles, memory);
-                }
-                Instruction::InsRorAbsX => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let absolute_address_x = absolute_address + self.x_register as Word;
-                    cycles -= 1;
-                    let lhs = self.read_byte(&mut cycles, memory, absolute_address_x);
-                    let value = self.roll_right(&mut cycles, lhs);
-                    self.write_byte(value, absolute_address_x, &mut cycles, memory);
-                }
-                // Misc
-                Instruction::InsNop => {
-                    cycles -= 1;
-                }
-                Instruction::InsBrk => {
-                    self.status.break_command = true;
-                    self.status.unused = true;
-                    let interrupt_vector = 0xFFFE;
-                    self.push_program_counter_plus_one_to_stack(&mut cycles, memory);
-                    self.push_byte_to_stack(self.status.into_u8(), &mut cycles, memory);
-                    self.status.interupt_disable = true;
-                    self.program_counter =
-                        self.read_word_absolute(&mut cycles, memory, interrupt_vector);
-                    self.status.break_command = true;
-                }
-                Instruction::InsRti => {
-                    self.status = self.pop_byte_from_stack(&mut cycles, memory).into();
-                    self.program_counter = self.pop_word_from_stack(&mut cycles, memory);
-                    self.status.break_command = false;
-                    self.status.unused = false;
-                }
-                // Custom
-                Instruction::InsDbgIm => {
-                    let data = self.fetch_word(&mut cycles, memory);
-                    if let Some(graphics) = self.graphics_adapter.as_mut() {
-                        graphics.get_data(data);
-                        cycles -= 1;
-                    }
-                }
-                Instruction::InsDbgAbs => {
-                    let absolute_address = self.fetch_word(&mut cycles, memory);
-                    let data = self.read_word_absolute(&mut cycles, memory, absolute_address);
-                    if let Some(graphics) = self.graphics_adapter.as_mut() {
-                        graphics.get_data(data);
-                        cycles -= 1;
-                    }
-                }
-                _ => {
-                    break;
+                self.program_counter = self.read_word_absolute(cycles, bus, interrupt_vector);
+                self.status.break_command = true;
+            }
+            Operation::Rti => {
+                self.status = self.pop_byte_from_stack(cycles, bus).into();
+                self.program_counter = self.pop_word_from_stack(cycles, bus);
+                self.status.break_command = false;
+                self.status.unused = false;
+            }
+            // Custom
+            Operation::Dbg => {
+                let data = match operand {
+                    Operand::ImmediateWord(data) => data,
+                    _ => self.read_word_absolute(cycles, bus, operand.address()),
+                };
+                if let Some(graphics) = self.graphics_adapter.as_mut() {
+                    graphics.get_data(data);
+                    *cycles -= 1;
                 }
             }
+            // Undocumented NMOS
+            Operation::Slo => self.shift_left_or(cycles, bus, operand.address()),
+            Operation::Rla => self.roll_left_and(cycles, bus, operand.address()),
+            Operation::Sre => self.shift_right_eor(cycles, bus, operand.address()),
+            Operation::Rra => self.roll_right_add(cycles, bus, operand.address()),
+            Operation::Dcp => self.decrement_compare(cycles, bus, operand.address()),
+            Operation::Isc => self.increment_subtract(cycles, bus, operand.address()),
+            Operation::Sax => {
+                self.write_operand(cycles, bus, operand, self.a_register & self.x_register)
+            }
+            Operation::Lax => {
+                let value = self.read_operand(cycles, bus, operand);
+                self.load_a_and_x(value);
+            }
+            Operation::Lxa => {
+                // Unstable, uses the common 0xEE magic constant
+                let value = self.read_operand(cycles, bus, operand);
+                self.load_a_and_x((self.a_register | 0xEE) & value);
+            }
+            Operation::Anc => {
+                self.a_register &= self.read_operand(cycles, bus, operand);
+                self.set_z_n_flags(self.a_register);
+                self.status.carry = self.status.negative;
+            }
+            Operation::Alr => {
+                let and_result = self.a_register & self.read_operand(cycles, bus, operand);
+                self.a_register = and_result >> 1;
+                self.set_z_n_flags(self.a_register);
+                self.status.carry = and_result & 1 != 0;
+            }
+            Operation::Arr => {
+                let value = self.read_operand(cycles, bus, operand);
+                self.and_roll_right(value);
+            }
+            Operation::Sbx => {
+                let value = self.read_operand(cycles, bus, operand);
+                let and_result = self.a_register & self.x_register;
+                self.x_register = and_result.wrapping_sub(value);
+                self.status.carry = and_result >= value;
+                self.set_z_n_flags(self.x_register);
+            }
+            Operation::Xaa => {
+                // Unstable, uses the common 0xEE magic constant
+                let value = self.read_operand(cycles, bus, operand);
+                self.a_register = (self.a_register | 0xEE) & self.x_register & value;
+                self.set_z_n_flags(self.a_register);
+            }
+            Operation::Sha => self.store_and_high_byte(
+                cycles,
+                bus,
+                operand.address(),
+                self.y_register,
+                self.a_register & self.x_register,
+            ),
+            Operation::Tas => {
+                self.stack_pointer = self.a_register & self.x_register;
+                self.store_and_high_byte(
+                    cycles,
+                    bus,
+                    operand.address(),
+                    self.y_register,
+                    self.stack_pointer,
+                );
+            }
+            Operation::Shy => self.store_and_high_byte(
+                cycles,
+                bus,
+                operand.address(),
+                self.x_register,
+                self.y_register,
+            ),
+            Operation::Shx => self.store_and_high_byte(
+                cycles,
+                bus,
+                operand.address(),
+                self.y_register,
+                self.x_register,
+            ),
+            Operation::Las => {
+                let value = self.read_operand(cycles, bus, operand) & self.stack_pointer;
+                self.stack_pointer = value;
+                self.load_a_and_x(value);
+            }
+            Operation::Jam => {
+                // The CPU locks up on the opcode until it is reset
+                self.program_counter = self.program_counter.wrapping_sub(1);
+                return Err(InstructionsError::CpuJammed(opcode));
+            }
+            // 65C02
+            Operation::Trb => {
+                self.test_and_modify_bits(cycles, bus, operand.address(), false);
+            }
+            Operation::Tsb => {
+                self.test_and_modify_bits(cycles, bus, operand.address(), true);
+            }
+            Operation::Bbr(bit) | Operation::Bbs(bit) => {
+                let value = self.read_byte(cycles, bus, operand.address());
+                *cycles -= 1;
+                let bit_set = value & (1 << bit) != 0;
+                let expected = matches!(operation, Operation::Bbs(_));
+                self.branch(cycles, operand.offset(), bit_set == expected);
+            }
+            Operation::Rmb(bit) => {
+                let address = operand.address();
+                let value = self.read_byte(cycles, bus, address) & !(1 << bit);
+                *cycles -= 1;
+                self.write_byte(value, address, cycles, bus);
+            }
+            Operation::Smb(bit) => {
+                let address = operand.address();
+                let value = self.read_byte(cycles, bus, address) | (1 << bit);
+                *cycles -= 1;
+                self.write_byte(value, address, cycles, bus);
+            }
+            Operation::Wai => {
+                self.waiting_for_interrupt = true;
+                *cycles -= 2;
+            }
+            Operation::Stp => {
+                self.stopped = true;
+                *cycles -= 2;
+            }
         }
-        Ok(cycles_requested - cycles)
+        Ok(())
     }
 
     pub fn check_same_page(address_a: Word, address_b: Word) -> bool {
         address_a >> 8 == address_b >> 8
     }
 
-    pub fn fetch_byte(&mut self, cycles: &mut i32, memory: &mut Memory) -> Byte {
-        let data: Byte = memory[self.program_counter];
-        self.program_counter += 1;
+    fn fetch_opcode<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B) -> Byte {
+        let opcode = bus.fetch(self.program_counter);
+        self.program_counter = self.program_counter.wrapping_add(1);
+        *cycles -= 1;
+        opcode
+    }
+
+    pub fn fetch_byte<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B) -> Byte {
+        let data: Byte = bus.read(self.program_counter);
+        self.program_counter = self.program_counter.wrapping_add(1);
         *cycles -= 1;
         data
     }
 
-    pub fn fetch_word(&mut self, cycles: &mut i32, memory: &mut Memory) -> Word {
+    pub fn fetch_word<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B) -> Word {
         // 6502 is little endian
-        let low_byte = memory[self.program_counter] as Word;
-        self.program_counter += 1;
+        let low_byte = bus.read(self.program_counter) as Word;
+        self.program_counter = self.program_counter.wrapping_add(1);
         *cycles -= 1;
 
-        let high_byte = (memory[self.program_counter] as Word) << 8;
-        self.program_counter += 1;
+        let high_byte = (bus.read(self.program_counter) as Word) << 8;
+        self.program_counter = self.program_counter.wrapping_add(1);
         *cycles -= 1;
 
         let data: Word = low_byte | high_byte;
         data
     }
 
-    pub fn read_byte(&self, cycles: &mut i32, memory: &mut Memory, address: Word) -> Byte {
-        let data: Byte = memory[address];
+    pub fn read_byte<B: Bus>(&self, cycles: &mut i32, bus: &mut B, address: Word) -> Byte {
+        let data: Byte = bus.read(address);
         *cycles -= 1;
         data
     }
 
-    pub fn read_word_from_zero_page(
+    pub fn read_word_from_zero_page<B: Bus>(
         &self,
         cycles: &mut i32,
-        memory: &mut Memory,
+        bus: &mut B,
         address: Byte,
     ) -> Word {
-        let low_byte = memory[address] as Word;
+        let low_byte = bus.read(address as Word) as Word;
         *cycles -= 1;
 
-        let high_byte = (memory[address.wrapping_add(1)] as Word) << 8;
+        let high_byte = (bus.read(address.wrapping_add(1) as Word) as Word) << 8;
         *cycles -= 1;
 
         let data: Word = low_byte | high_byte;
         data
     }
 
-    pub fn read_word_absolute(&self, cycles: &mut i32, memory: &mut Memory, address: Word) -> Word {
-        let low_byte = memory[address] as Word;
+    pub fn read_word_absolute<B: Bus>(&self, cycles: &mut i32, bus: &mut B, address: Word) -> Word {
+        let low_byte = bus.read(address) as Word;
         *cycles -= 1;
 
-        let high_byte = (memory[address + 1] as Word) << 8;
+        let high_byte = (bus.read(address.wrapping_add(1)) as Word) << 8;
         *cycles -= 1;
 
         let data: Word = low_byte | high_byte;
         data
     }
 
-    pub fn write_word(&mut self, data: Word, address: Word, cycles: &mut i32, memory: &mut Memory) {
+    /// Reads a word without carrying into the high byte of the address, so a word at
+    /// $xxFF takes its high byte from $xx00. This is how the NMOS `JMP ($xxFF)` behaves.
+    pub fn read_word_within_page<B: Bus>(
+        &self,
+        cycles: &mut i32,
+        bus: &mut B,
+        address: Word,
+    ) -> Word {
+        let low_byte = bus.read(address) as Word;
+        *cycles -= 1;
+
+        let high_address = (address & 0xFF00) | (address.wrapping_add(1) & 0x00FF);
+        let high_byte = (bus.read(high_address) as Word) << 8;
+        *cycles -= 1;
+
+        let data: Word = low_byte | high_byte;
+        data
+    }
+
+    pub fn write_word<B: Bus>(&mut self, data: Word, address: Word, cycles: &mut i32, bus: &mut B) {
         let data_bytes = data.to_le_bytes();
-        memory[address] = data_bytes[0];
+        bus.write(address, data_bytes[0]);
         *cycles -= 1;
-        memory[address + 1] = data_bytes[1];
+        bus.write(address.wrapping_add(1), data_bytes[1]);
         *cycles -= 1;
     }
 
-    pub fn write_byte(&mut self, data: Byte, address: Word, cycles: &mut i32, memory: &mut Memory) {
-        memory[address] = data;
+    pub fn write_byte<B: Bus>(&mut self, data: Byte, address: Word, cycles: &mut i32, bus: &mut B) {
+        bus.write(address, data);
         *cycles -= 1;
     }
 
@@ -1389,76 +1512,70 @@ impl CPU {
         0x100 | self.stack_pointer as Word
     }
 
-    pub fn push_program_counter_minus_one_to_stack(
+    pub fn push_program_counter_minus_one_to_stack<B: Bus>(
         &mut self,
         cycles: &mut i32,
-        memory: &mut Memory,
+        bus: &mut B,
     ) {
-        self.push_word_to_stack(self.program_counter - 1, cycles, memory);
+        self.push_word_to_stack(self.program_counter.wrapping_sub(1), cycles, bus);
     }
 
-    pub fn push_program_counter_to_stack(&mut self, cycles: &mut i32, memory: &mut Memory) {
-        self.push_word_to_stack(self.program_counter, cycles, memory);
+    pub fn push_program_counter_to_stack<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B) {
+        self.push_word_to_stack(self.program_counter, cycles, bus);
     }
 
-    pub fn push_program_counter_plus_one_to_stack(
+    pub fn push_program_counter_plus_one_to_stack<B: Bus>(
         &mut self,
         cycles: &mut i32,
-        memory: &mut Memory,
+        bus: &mut B,
     ) {
-        self.push_word_to_stack(self.program_counter + 1, cycles, memory);
+        self.push_word_to_stack(self.program_counter.wrapping_add(1), cycles, bus);
     }
 
-    pub fn pop_word_from_stack(&mut self, cycles: &mut i32, memory: &mut Memory) -> Word {
-        let return_address =
-            self.read_word_absolute(cycles, memory, self.stack_pointer_to_address() + 1);
-        self.stack_pointer += 2;
+    /// Pops a word, wrapping around within the stack page.
+    pub fn pop_word_from_stack<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B) -> Word {
+        self.stack_pointer = self.stack_pointer.wrapping_add(1);
+        let low_byte = self.read_byte(cycles, bus, self.stack_pointer_to_address());
+        self.stack_pointer = self.stack_pointer.wrapping_add(1);
+        let high_byte = self.read_byte(cycles, bus, self.stack_pointer_to_address());
         *cycles -= 1;
-        return_address
+        Word::from_le_bytes([low_byte, high_byte])
     }
 
-    pub fn push_byte_to_stack(&mut self, data: Byte, cycles: &mut i32, memory: &mut Memory) {
-        memory[self.stack_pointer_to_address()] = data;
+    pub fn push_byte_to_stack<B: Bus>(&mut self, data: Byte, cycles: &mut i32, bus: &mut B) {
+        bus.write(self.stack_pointer_to_address(), data);
         *cycles -= 1;
         // self.stack_pointer -= 1;
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
         *cycles -= 1;
     }
 
-    pub fn pop_byte_from_stack(&mut self, cycles: &mut i32, memory: &mut Memory) -> Byte {
+    pub fn pop_byte_from_stack<B: Bus>(&mut self, cycles: &mut i32, bus: &mut B) -> Byte {
         // self.stack_pointer += 1;
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
-        let data = memory[self.stack_pointer_to_address()];
+        let data = bus.read(self.stack_pointer_to_address());
         *cycles -= 2;
         data
     }
 
-    pub fn push_word_to_stack(&mut self, data: Word, cycles: &mut i32, memory: &mut Memory) {
+    pub fn push_word_to_stack<B: Bus>(&mut self, data: Word, cycles: &mut i32, bus: &mut B) {
         self.write_byte(
             data.overflowing_shr(8).0 as Byte,
             self.stack_pointer_to_address(),
             cycles,
-            memory,
+            bus,
         );
-        self.stack_pointer -= 1;
-        self.write_byte(
-            data as Byte,
-            self.stack_pointer_to_address(),
-            cycles,
-            memory,
-        );
-        self.stack_pointer -= 1;
+        self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+        self.write_byte(data as Byte, self.stack_pointer_to_address(), cycles, bus);
+        self.stack_pointer = self.stack_pointer.wrapping_sub(1);
     }
 
-    pub fn load_program(&self, program: &[Byte], num_bytes: u16, memory: &mut Memory) -> Word {
+    pub fn load_program<B: Bus>(&self, program: &[Byte], num_bytes: u16, bus: &mut B) -> Word {
         let mut load_address: Word = 0;
         if num_bytes > 2 {
-            let mut at = 0;
-            load_address = program[at] as Word | ((program[at + 1] as Word) << 8);
-            at += 2;
-            for i in load_address..(load_address + num_bytes - 2) {
-                memory[i] = program[at];
-                at += 1;
+            load_address = program[0] as Word | ((program[1] as Word) << 8);
+            for (offset, byte) in program[2..num_bytes as usize].iter().enumerate() {
+                bus.write(load_address.wrapping_add(offset as Word), *byte);
             }
         }
         load_address
@@ -1469,6 +1586,12 @@ impl CPU {
     }
 }
 
+impl Default for CPU {
+    fn default() -> Self {
+        CPU::new()
+    }
+}
+
 impl Display for CPU {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -0,0 +1,186 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use crate::{
+    bus::Bus,
+    cpu::{Byte, CpuVariant, Word},
+    instructions::{opcode_table, AddressingMode, OpcodeEntry, OpcodeTable},
+    loader::{parse_prg, LoaderError},
+    symbols::SymbolTable,
+};
+
+/// How far past a symbol an operand can be and still be shown as `name+offset`.
+const MAX_SYMBOL_OFFSET: Word = 8;
+
+/// One disassembled instruction, or a `.byte` for data that doesn't decode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisassembledLine {
+    pub address: Word,
+    pub bytes: Vec<Byte>,
+    pub label: Option<String>,
+    pub mnemonic: &'static str,
+    pub operand: String,
+    /// Where a branch goes when taken.
+    pub branch_target: Option<Word>,
+}
+
+impl Display for DisassembledLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bytes: Vec<String> = self
+            .bytes
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect();
+        let row = format!(
+            "{:04X}  {:<8}  {:<8}  {} {}",
+            self.address,
+            bytes.join(" "),
+            self.label.as_deref().unwrap_or(""),
+            self.mnemonic,
+            self.operand
+        );
+        write!(f, "{}", row.trim_end())
+    }
+}
+
+/// Turns bytes back into instructions using the opcode table of a CPU configuration,
+/// so the custom `DBG` instructions and 65C02 opcodes come out the way the CPU runs them.
+pub struct Disassembler<'a> {
+    table: &'static OpcodeTable,
+    symbols: Option<&'a SymbolTable>,
+}
+
+impl<'a> Disassembler<'a> {
+    pub fn new(variant: CpuVariant, undocumented_opcodes: bool) -> Disassembler<'a> {
+        Disassembler {
+            table: opcode_table(variant, undocumented_opcodes),
+            symbols: None,
+        }
+    }
+
+    /// Shows labels and operand addresses by name.
+    pub fn with_symbols(self, symbols: &'a SymbolTable) -> Disassembler<'a> {
+        Disassembler {
+            symbols: Some(symbols),
+            ..self
+        }
+    }
+
+    pub fn disassemble<B: Bus>(
+        &self,
+        bus: &B,
+        range: RangeInclusive<Word>,
+    ) -> Vec<DisassembledLine> {
+        let bytes: Vec<Byte> = range.clone().map(|address| bus.peek(address)).collect();
+        self.disassemble_bytes(&bytes, *range.start())
+    }
+
+    /// Disassembles `bytes` as if loaded at `address`.
+    pub fn disassemble_bytes(&self, bytes: &[Byte], address: Word) -> Vec<DisassembledLine> {
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let line_address = address.wrapping_add(offset as Word);
+            let entry = self.table[bytes[offset] as usize]
+                .as_ref()
+                .filter(|entry| offset + entry.length as usize <= bytes.len());
+            let line = match entry {
+                Some(entry) => self.instruction(
+                    entry,
+                    &bytes[offset..offset + entry.length as usize],
+                    line_address,
+                ),
+                None => DisassembledLine {
+                    address: line_address,
+                    bytes: vec![bytes[offset]],
+                    label: None,
+                    mnemonic: ".byte",
+                    operand: format!("${:02X}", bytes[offset]),
+                    branch_target: None,
+                },
+            };
+            offset += line.bytes.len();
+            lines.push(DisassembledLine {
+                label: self.label(line_address),
+                ..line
+            });
+        }
+        lines
+    }
+
+    /// Disassembles a PRG file, a load address followed by the program.
+    pub fn disassemble_prg(&self, data: &[Byte]) -> Result<Vec<DisassembledLine>, LoaderError> {
+        let image = parse_prg(data)?;
+        let segment = &image.segments[0];
+        Ok(self.disassemble_bytes(&segment.data, segment.address))
+    }
+
+    fn label(&self, address: Word) -> Option<String> {
+        self.symbols
+            .and_then(|symbols| symbols.name_at(address))
+            .map(str::to_string)
+    }
+
+    fn instruction(&self, entry: &OpcodeEntry, bytes: &[Byte], address: Word) -> DisassembledLine {
+        let byte = bytes.get(1).copied().unwrap_or(0);
+        let word = Word::from_le_bytes([byte, bytes.get(2).copied().unwrap_or(0)]);
+        let branch = |offset: Byte| {
+            address
+                .wrapping_add(bytes.len() as Word)
+                .wrapping_add(offset as i8 as Word)
+        };
+        let zero_page = self.address(byte as Word, 2);
+        let absolute = self.address(word, 4);
+
+        let (operand, branch_target) = match entry.mode {
+            AddressingMode::Implied => (String::new(), None),
+            AddressingMode::Accumulator => ("A".to_string(), None),
+            AddressingMode::Immediate => (format!("#${:02X}", byte), None),
+            AddressingMode::ImmediateWord => (format!("#${:04X}", word), None),
+            AddressingMode::ZeroPage => (zero_page, None),
+            AddressingMode::ZeroPageX => (format!("{},X", zero_page), None),
+            AddressingMode::ZeroPageY => (format!("{},Y", zero_page), None),
+            AddressingMode::Absolute => (absolute, None),
+            AddressingMode::AbsoluteX => (format!("{},X", absolute), None),
+            AddressingMode::AbsoluteY => (format!("{},Y", absolute), None),
+            AddressingMode::Indirect => (format!("({})", absolute), None),
+            AddressingMode::IndexedIndirect => (format!("({},X)", zero_page), None),
+            AddressingMode::IndirectIndexed => (format!("({}),Y", zero_page), None),
+            AddressingMode::ZeroPageIndirect => (format!("({})", zero_page), None),
+            AddressingMode::AbsoluteIndexedIndirect => (format!("({},X)", absolute), None),
+            AddressingMode::Relative => {
+                let target = branch(byte);
+                (self.address(target, 4), Some(target))
+            }
+            AddressingMode::ZeroPageRelative => {
+                let target = branch(bytes[2]);
+                (
+                    format!("{},{}", zero_page, self.address(target, 4)),
+                    Some(target),
+                )
+            }
+        };
+        DisassembledLine {
+            address,
+            bytes: bytes.to_vec(),
+            label: None,
+            mnemonic: entry.mnemonic,
+            operand,
+            branch_target,
+        }
+    }
+
+    /// An operand address, by name when a symbol is close enough.
+    fn address(&self, address: Word, digits: usize) -> String {
+        match self.symbols.and_then(|symbols| symbols.nearest(address)) {
+            Some((_, offset)) if offset <= MAX_SYMBOL_OFFSET => {
+                self.symbols.unwrap().describe(address, MAX_SYMBOL_OFFSET)
+            }
+            _ => format!("${:01$X}", address, digits),
+        }
+    }
+}
+
+/// The lines as text, one instruction per line.
+pub fn listing_text(lines: &[DisassembledLine]) -> String {
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
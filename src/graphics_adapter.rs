@@ -2,7 +2,7 @@ use crate::cpu::{Byte, Word, CPU};
 use crate::memory::Memory;
 use sdl2::{event::Event, pixels::Color, rect::Rect, render::Canvas};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct GraphicsAdapter {
     pixels: [[Color; 16]; 16],
     clear_color: Color,
@@ -16,10 +16,21 @@ impl GraphicsAdapter {
         }
     }
 
+    pub(crate) fn from_parts(pixels: [[Color; 16]; 16], clear_color: Color) -> Self {
+        Self {
+            pixels,
+            clear_color,
+        }
+    }
+
     pub fn get_pixels(&self) -> &[[Color; 16]; 16] {
         &self.pixels
     }
 
+    pub fn get_clear_color(&self) -> Color {
+        self.clear_color
+    }
+
     pub fn get_data(&mut self, data: Word) {
         // if data != 0 {
         //     println!("Instruction: {:02b}_{:08b}_{:06b}", data >> 14, data >> 6 & 0b11111111, data & 0b111111);
@@ -1,10 +1,26 @@
-use crate::cpu::Byte;
+use crate::{
+    cpu::{Byte, CpuVariant, ProcessorFlags, Word},
+    watchpoints::WatchpointHit,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum InstructionsError {
     InstructionDoesntExist(Byte),
+    CpuJammed(Byte),
+    /// The instruction at `program_counter` wrote to a ROM region set to fault.
+    WriteToRom {
+        program_counter: Word,
+        address: Word,
+        value: Byte,
+    },
+    /// A watchpoint fired, see `Watched`.
+    WatchpointHit {
+        program_counter: Word,
+        hit: WatchpointHit,
+    },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Instruction {
     // LDA
     InsLdaIm = 0xA9,
@@ -189,12 +205,793 @@ pub enum Instruction {
     // Custom
     InsDbgIm = 0x44,
     InsDbgAbs = 0x43,
+    // Undocumented NMOS
+    // SLO
+    InsSloZp = 0x07,
+    InsSloZpX = 0x17,
+    InsSloAbs = 0x0F,
+    InsSloAbsX = 0x1F,
+    InsSloAbsY = 0x1B,
+    InsSloIndX = 0x03,
+    InsSloIndY = 0x13,
+    // RLA
+    InsRlaZp = 0x27,
+    InsRlaZpX = 0x37,
+    InsRlaAbs = 0x2F,
+    InsRlaAbsX = 0x3F,
+    InsRlaAbsY = 0x3B,
+    InsRlaIndX = 0x23,
+    InsRlaIndY = 0x33,
+    // SRE (0x43 is taken by InsDbgAbs)
+    InsSreZp = 0x47,
+    InsSreZpX = 0x57,
+    InsSreAbs = 0x4F,
+    InsSreAbsX = 0x5F,
+    InsSreAbsY = 0x5B,
+    InsSreIndY = 0x53,
+    // RRA
+    InsRraZp = 0x67,
+    InsRraZpX = 0x77,
+    InsRraAbs = 0x6F,
+    InsRraAbsX = 0x7F,
+    InsRraAbsY = 0x7B,
+    InsRraIndX = 0x63,
+    InsRraIndY = 0x73,
+    // SAX
+    InsSaxZp = 0x87,
+    InsSaxZpY = 0x97,
+    InsSaxAbs = 0x8F,
+    InsSaxIndX = 0x83,
+    // LAX
+    InsLaxIm = 0xAB,
+    InsLaxZp = 0xA7,
+    InsLaxZpY = 0xB7,
+    InsLaxAbs = 0xAF,
+    InsLaxAbsY = 0xBF,
+    InsLaxIndX = 0xA3,
+    InsLaxIndY = 0xB3,
+    // DCP
+    InsDcpZp = 0xC7,
+    InsDcpZpX = 0xD7,
+    InsDcpAbs = 0xCF,
+    InsDcpAbsX = 0xDF,
+    InsDcpAbsY = 0xDB,
+    InsDcpIndX = 0xC3,
+    InsDcpIndY = 0xD3,
+    // ISC
+    InsIscZp = 0xE7,
+    InsIscZpX = 0xF7,
+    InsIscAbs = 0xEF,
+    InsIscAbsX = 0xFF,
+    InsIscAbsY = 0xFB,
+    InsIscIndX = 0xE3,
+    InsIscIndY = 0xF3,
+    // Immediate
+    InsAncIm0B = 0x0B,
+    InsAncIm2B = 0x2B,
+    InsAlrIm = 0x4B,
+    InsArrIm = 0x6B,
+    InsSbxIm = 0xCB,
+    InsSbcImEB = 0xEB,
+    InsXaaIm = 0x8B,
+    // Unstable stores and loads
+    InsShaIndY = 0x93,
+    InsShaAbsY = 0x9F,
+    InsTasAbsY = 0x9B,
+    InsShyAbsX = 0x9C,
+    InsShxAbsY = 0x9E,
+    InsLasAbsY = 0xBB,
+    // NOP (0x44 is taken by InsDbgIm)
+    InsNopImp1A = 0x1A,
+    InsNopImp3A = 0x3A,
+    InsNopImp5A = 0x5A,
+    InsNopImp7A = 0x7A,
+    InsNopImpDA = 0xDA,
+    InsNopImpFA = 0xFA,
+    InsNopIm80 = 0x80,
+    InsNopIm82 = 0x82,
+    InsNopIm89 = 0x89,
+    InsNopImC2 = 0xC2,
+    InsNopImE2 = 0xE2,
+    InsNopZp04 = 0x04,
+    InsNopZp64 = 0x64,
+    InsNopZpX14 = 0x14,
+    InsNopZpX34 = 0x34,
+    InsNopZpX54 = 0x54,
+    InsNopZpX74 = 0x74,
+    InsNopZpXD4 = 0xD4,
+    InsNopZpXF4 = 0xF4,
+    InsNopAbs0C = 0x0C,
+    InsNopAbsX1C = 0x1C,
+    InsNopAbsX3C = 0x3C,
+    InsNopAbsX5C = 0x5C,
+    InsNopAbsX7C = 0x7C,
+    InsNopAbsXDC = 0xDC,
+    InsNopAbsXFC = 0xFC,
+    // JAM
+    InsJam02 = 0x02,
+    InsJam12 = 0x12,
+    InsJam22 = 0x22,
+    InsJam32 = 0x32,
+    InsJam42 = 0x42,
+    InsJam52 = 0x52,
+    InsJam62 = 0x62,
+    InsJam72 = 0x72,
+    InsJam92 = 0x92,
+    InsJamB2 = 0xB2,
+    InsJamD2 = 0xD2,
+    InsJamF2 = 0xF2,
+    // 65C02
+    // These opcodes overlap the undocumented NMOS ones, so their discriminants are
+    // offset by 0x100. Use `Instruction::opcode` to get the encoded byte.
+    // Branch and stack
+    InsBra = 0x180,
+    InsPhx = 0x1DA,
+    InsPlx = 0x1FA,
+    InsPhy = 0x15A,
+    InsPly = 0x17A,
+    // STZ
+    InsStzZp = 0x164,
+    InsStzZpX = 0x174,
+    InsStzAbs = 0x19C,
+    InsStzAbsX = 0x19E,
+    // TRB and TSB
+    InsTrbZp = 0x114,
+    InsTrbAbs = 0x11C,
+    InsTsbZp = 0x104,
+    InsTsbAbs = 0x10C,
+    // Zero page indirect
+    InsOraIndZp = 0x112,
+    InsAndIndZp = 0x132,
+    InsEorIndZp = 0x152,
+    InsAdcIndZp = 0x172,
+    InsStaIndZp = 0x192,
+    InsLdaIndZp = 0x1B2,
+    InsCmpIndZp = 0x1D2,
+    InsSbcIndZp = 0x1F2,
+    // BIT
+    InsBitIm = 0x189,
+    InsBitZpX = 0x134,
+    InsBitAbsX = 0x13C,
+    // Accumulator increments
+    InsIncA = 0x11A,
+    InsDecA = 0x13A,
+    // Jumps
+    InsJmpAbsIndX = 0x17C,
+    // BBR
+    InsBbr0 = 0x10F,
+    InsBbr1 = 0x11F,
+    InsBbr2 = 0x12F,
+    InsBbr3 = 0x13F,
+    InsBbr4 = 0x14F,
+    InsBbr5 = 0x15F,
+    InsBbr6 = 0x16F,
+    InsBbr7 = 0x17F,
+    // BBS
+    InsBbs0 = 0x18F,
+    InsBbs1 = 0x19F,
+    InsBbs2 = 0x1AF,
+    InsBbs3 = 0x1BF,
+    InsBbs4 = 0x1CF,
+    InsBbs5 = 0x1DF,
+    InsBbs6 = 0x1EF,
+    InsBbs7 = 0x1FF,
+    // RMB
+    InsRmb0 = 0x107,
+    InsRmb1 = 0x117,
+    InsRmb2 = 0x127,
+    InsRmb3 = 0x137,
+    InsRmb4 = 0x147,
+    InsRmb5 = 0x157,
+    InsRmb6 = 0x167,
+    InsRmb7 = 0x177,
+    // SMB
+    InsSmb0 = 0x187,
+    InsSmb1 = 0x197,
+    InsSmb2 = 0x1A7,
+    InsSmb3 = 0x1B7,
+    InsSmb4 = 0x1C7,
+    InsSmb5 = 0x1D7,
+    InsSmb6 = 0x1E7,
+    InsSmb7 = 0x1F7,
+    // Processor
+    InsWai = 0x1CB,
+    InsStp = 0x1DB,
+}
+
+/// How an instruction finds its operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressingMode {
+    Implied,
+    Accumulator,
+    Immediate,
+    /// Two byte immediate operand, only used by the custom DBG instruction
+    ImmediateWord,
+    ZeroPage,
+    ZeroPageX,
+    ZeroPageY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    Indirect,
+    IndexedIndirect,
+    IndirectIndexed,
+    ZeroPageIndirect,
+    AbsoluteIndexedIndirect,
+    Relative,
+    /// Zero page address followed by a branch offset, used by BBR and BBS
+    ZeroPageRelative,
+}
+
+impl AddressingMode {
+    /// Instruction length in bytes, including the opcode.
+    pub const fn length(self) -> Word {
+        match self {
+            Self::Implied | Self::Accumulator => 1,
+            Self::Immediate
+            | Self::ZeroPage
+            | Self::ZeroPageX
+            | Self::ZeroPageY
+            | Self::IndexedIndirect
+            | Self::IndirectIndexed
+            | Self::ZeroPageIndirect
+            | Self::Relative => 2,
+            Self::ImmediateWord
+            | Self::Absolute
+            | Self::AbsoluteX
+            | Self::AbsoluteY
+            | Self::Indirect
+            | Self::AbsoluteIndexedIndirect
+            | Self::ZeroPageRelative => 3,
+        }
+    }
+}
+
+/// What an instruction does once its operand has been resolved. Operations are shared
+/// between all addressing modes of an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    // Official
+    Adc,
+    And,
+    Asl,
+    Bcc,
+    Bcs,
+    Beq,
+    Bit,
+    Bmi,
+    Bne,
+    Bpl,
+    Brk,
+    Bvc,
+    Bvs,
+    Clc,
+    Cld,
+    Cli,
+    Clv,
+    Cmp,
+    Cpx,
+    Cpy,
+    Dec,
+    Dex,
+    Dey,
+    Eor,
+    Inc,
+    Inx,
+    Iny,
+    Jmp,
+    Jsr,
+    Lda,
+    Ldx,
+    Ldy,
+    Lsr,
+    Nop,
+    Ora,
+    Pha,
+    Php,
+    Pla,
+    Plp,
+    Rol,
+    Ror,
+    Rti,
+    Rts,
+    Sbc,
+    Sec,
+    Sed,
+    Sei,
+    Sta,
+    Stx,
+    Sty,
+    Tax,
+    Tay,
+    Tsx,
+    Txa,
+    Txs,
+    Tya,
+    // Custom
+    Dbg,
+    // Undocumented NMOS
+    Alr,
+    Anc,
+    Arr,
+    Dcp,
+    Isc,
+    Jam,
+    Las,
+    Lax,
+    Lxa,
+    Rla,
+    Rra,
+    Sax,
+    Sbx,
+    Sha,
+    Shx,
+    Shy,
+    Slo,
+    Sre,
+    Tas,
+    Xaa,
+    // 65C02
+    Bbr(Byte),
+    Bbs(Byte),
+    Bra,
+    Phx,
+    Phy,
+    Plx,
+    Ply,
+    Rmb(Byte),
+    Smb(Byte),
+    Stp,
+    Stz,
+    Trb,
+    Tsb,
+    Wai,
+}
+
+impl Operation {
+    pub const fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Adc => "ADC",
+            Self::And => "AND",
+            Self::Asl => "ASL",
+            Self::Bcc => "BCC",
+            Self::Bcs => "BCS",
+            Self::Beq => "BEQ",
+            Self::Bit => "BIT",
+            Self::Bmi => "BMI",
+            Self::Bne => "BNE",
+            Self::Bpl => "BPL",
+            Self::Brk => "BRK",
+            Self::Bvc => "BVC",
+            Self::Bvs => "BVS",
+            Self::Clc => "CLC",
+            Self::Cld => "CLD",
+            Self::Cli => "CLI",
+            Self::Clv => "CLV",
+            Self::Cmp => "CMP",
+            Self::Cpx => "CPX",
+            Self::Cpy => "CPY",
+            Self::Dec => "DEC",
+            Self::Dex => "DEX",
+            Self::Dey => "DEY",
+            Self::Eor => "EOR",
+            Self::Inc => "INC",
+            Self::Inx => "INX",
+            Self::Iny => "INY",
+            Self::Jmp => "JMP",
+            Self::Jsr => "JSR",
+            Self::Lda => "LDA",
+            Self::Ldx => "LDX",
+            Self::Ldy => "LDY",
+            Self::Lsr => "LSR",
+            Self::Nop => "NOP",
+            Self::Ora => "ORA",
+            Self::Pha => "PHA",
+            Self::Php => "PHP",
+            Self::Pla => "PLA",
+            Self::Plp => "PLP",
+            Self::Rol => "ROL",
+            Self::Ror => "ROR",
+            Self::Rti => "RTI",
+            Self::Rts => "RTS",
+            Self::Sbc => "SBC",
+            Self::Sec => "SEC",
+            Self::Sed => "SED",
+            Self::Sei => "SEI",
+            Self::Sta => "STA",
+            Self::Stx => "STX",
+            Self::Sty => "STY",
+            Self::Tax => "TAX",
+            Self::Tay => "TAY",
+            Self::Tsx => "TSX",
+            Self::Txa => "TXA",
+            Self::Txs => "TXS",
+            Self::Tya => "TYA",
+            Self::Dbg => "DBG",
+            Self::Alr => "ALR",
+            Self::Anc => "ANC",
+            Self::Arr => "ARR",
+            Self::Dcp => "DCP",
+            Self::Isc => "ISC",
+            Self::Jam => "JAM",
+            Self::Las => "LAS",
+            Self::Lax => "LAX",
+            Self::Lxa => "LXA",
+            Self::Rla => "RLA",
+            Self::Rra => "RRA",
+            Self::Sax => "SAX",
+            Self::Sbx => "SBX",
+            Self::Sha => "SHA",
+            Self::Shx => "SHX",
+            Self::Shy => "SHY",
+            Self::Slo => "SLO",
+            Self::Sre => "SRE",
+            Self::Tas => "TAS",
+            Self::Xaa => "XAA",
+            Self::Bra => "BRA",
+            Self::Phx => "PHX",
+            Self::Phy => "PHY",
+            Self::Plx => "PLX",
+            Self::Ply => "PLY",
+            Self::Stp => "STP",
+            Self::Stz => "STZ",
+            Self::Trb => "TRB",
+            Self::Tsb => "TSB",
+            Self::Wai => "WAI",
+            Self::Bbr(bit) => match bit {
+                0 => "BBR0",
+                1 => "BBR1",
+                2 => "BBR2",
+                3 => "BBR3",
+                4 => "BBR4",
+                5 => "BBR5",
+                6 => "BBR6",
+                _ => "BBR7",
+            },
+            Self::Bbs(bit) => match bit {
+                0 => "BBS0",
+                1 => "BBS1",
+                2 => "BBS2",
+                3 => "BBS3",
+                4 => "BBS4",
+                5 => "BBS5",
+                6 => "BBS6",
+                _ => "BBS7",
+            },
+            Self::Rmb(bit) => match bit {
+                0 => "RMB0",
+                1 => "RMB1",
+                2 => "RMB2",
+                3 => "RMB3",
+                4 => "RMB4",
+                5 => "RMB5",
+                6 => "RMB6",
+                _ => "RMB7",
+            },
+            Self::Smb(bit) => match bit {
+                0 => "SMB0",
+                1 => "SMB1",
+                2 => "SMB2",
+                3 => "SMB3",
+                4 => "SMB4",
+                5 => "SMB5",
+                6 => "SMB6",
+                _ => "SMB7",
+            },
+        }
+    }
+
+    pub const fn is_store(self) -> bool {
+        matches!(
+            self,
+            Self::Sta
+                | Self::Stx
+                | Self::Sty
+                | Self::Stz
+                | Self::Sax
+                | Self::Sha
+                | Self::Tas
+                | Self::Shy
+                | Self::Shx
+        )
+    }
+
+    pub const fn is_read_modify_write(self) -> bool {
+        matches!(
+            self,
+            Self::Asl
+                | Self::Lsr
+                | Self::Rol
+                | Self::Ror
+                | Self::Inc
+                | Self::Dec
+                | Self::Slo
+                | Self::Rla
+                | Self::Sre
+                | Self::Rra
+                | Self::Dcp
+                | Self::Isc
+                | Self::Trb
+                | Self::Tsb
+        )
+    }
+
+    pub const fn is_branch(self) -> bool {
+        matches!(
+            self,
+            Self::Bcc
+                | Self::Bcs
+                | Self::Beq
+                | Self::Bmi
+                | Self::Bne
+                | Self::Bpl
+                | Self::Bvc
+                | Self::Bvs
+                | Self::Bra
+                | Self::Bbr(_)
+                | Self::Bbs(_)
+        )
+    }
+
+    /// Status flags the operation can change, as a mask of the `ProcessorFlags` bits.
+    pub const fn flags_affected(self) -> Byte {
+        const N: Byte = ProcessorFlags::NEGATIVE_FLAG_BIT;
+        const V: Byte = ProcessorFlags::OVERFLOW_FLAG_BIT;
+        const U: Byte = ProcessorFlags::UNUSED_FLAG_BIT;
+        const B: Byte = ProcessorFlags::BREAK_FLAG_BIT;
+        const D: Byte = ProcessorFlags::DECIMAL_MODE_FLAG_BIT;
+        const I: Byte = ProcessorFlags::INTERRUPT_DISABLE_FLAG_BIT;
+        const Z: Byte = ProcessorFlags::ZERO_FLAG_BIT;
+        const C: Byte = ProcessorFlags::CARRY_FLAG_BIT;
+        match self {
+            Self::Lda
+            | Self::Ldx
+            | Self::Ldy
+            | Self::Tax
+            | Self::Tay
+            | Self::Txa
+            | Self::Tya
+            | Self::Tsx
+            | Self::Inx
+            | Self::Iny
+            | Self::Dex
+            | Self::Dey
+            | Self::Inc
+            | Self::Dec
+            | Self::And
+            | Self::Eor
+            | Self::Ora
+            | Self::Pla
+            | Self::Plx
+            | Self::Ply
+            | Self::Lax
+            | Self::Lxa
+            | Self::Las
+            | Self::Xaa => N | Z,
+            Self::Asl
+            | Self::Lsr
+            | Self::Rol
+            | Self::Ror
+            | Self::Cmp
+            | Self::Cpx
+            | Self::Cpy
+            | Self::Slo
+            | Self::Rla
+            | Self::Sre
+            | Self::Dcp
+            | Self::Anc
+            | Self::Alr
+            | Self::Sbx => N | Z | C,
+            Self::Adc | Self::Sbc | Self::Rra | Self::Isc | Self::Arr => N | V | Z | C,
+            Self::Bit => N | V | Z,
+            Self::Trb | Self::Tsb => Z,
+            Self::Clc | Self::Sec => C,
+            Self::Cld | Self::Sed => D,
+            Self::Cli | Self::Sei => I,
+            Self::Clv => V,
+            Self::Brk => B | U | D | I,
+            Self::Plp | Self::Rti => 0xFF,
+            _ => 0,
+        }
+    }
+
+    /// Stores and read-modify-writes always spend the extra cycle on indexed
+    /// addressing, reads only spend it when a page is crossed. Shifts and rotates on
+    /// the 65C02 are timed like reads.
+    pub const fn always_pays_index_penalty(self, variant: CpuVariant) -> bool {
+        if let (CpuVariant::Cmos65C02, Self::Asl | Self::Lsr | Self::Rol | Self::Ror) =
+            (variant, self)
+        {
+            return false;
+        }
+        matches!(
+            self,
+            Self::Sta
+                | Self::Stx
+                | Self::Sty
+                | Self::Stz
+                | Self::Asl
+                | Self::Lsr
+                | Self::Rol
+                | Self::Ror
+                | Self::Inc
+                | Self::Dec
+                | Self::Slo
+                | Self::Rla
+                | Self::Sre
+                | Self::Rra
+                | Self::Dcp
+                | Self::Isc
+                | Self::Sha
+                | Self::Tas
+                | Self::Shy
+                | Self::Shx
+        )
+    }
+}
+
+/// A decoded opcode and its metadata.
+///
+/// `cycles` is the count for the common case: no page crossed and no branch taken.
+/// Reads with indexed addressing spend `page_cross_cycles` more when the index crosses
+/// a page. Branches spend `branch_taken_cycles` more when taken, plus
+/// `page_cross_cycles` when the target is on another page. Decimal ADC and SBC on the
+/// 65C02 and DBG with a graphics adapter attached take one extra cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpcodeEntry {
+    pub opcode: Byte,
+    pub instruction: Instruction,
+    pub mnemonic: &'static str,
+    pub operation: Operation,
+    pub mode: AddressingMode,
+    pub length: Word,
+    pub cycles: i32,
+    pub page_cross_cycles: i32,
+    pub branch_taken_cycles: i32,
+    pub flags_affected: Byte,
+}
+
+impl OpcodeEntry {
+    const fn new(instruction: Instruction, variant: CpuVariant) -> Self {
+        let (operation, mode) = instruction.operation_and_mode();
+        let flags_affected = match (operation, mode, variant) {
+            // Immediate BIT only affects the zero flag
+            (Operation::Bit, AddressingMode::Immediate, _) => ProcessorFlags::ZERO_FLAG_BIT,
+            (Operation::Brk, _, CpuVariant::Nmos6502) => {
+                operation.flags_affected() & !ProcessorFlags::DECIMAL_MODE_FLAG_BIT
+            }
+            _ => operation.flags_affected(),
+        };
+        let page_cross_cycles = match mode {
+            _ if operation.is_branch() => 1,
+            AddressingMode::AbsoluteX
+            | AddressingMode::AbsoluteY
+            | AddressingMode::IndirectIndexed
+                if !operation.always_pays_index_penalty(variant) =>
+            {
+                1
+            }
+            _ => 0,
+        };
+        OpcodeEntry {
+            opcode: instruction.opcode(),
+            instruction,
+            mnemonic: operation.mnemonic(),
+            operation,
+            mode,
+            length: mode.length(),
+            cycles: Self::base_cycles(operation, mode, variant),
+            page_cross_cycles,
+            branch_taken_cycles: operation.is_branch() as i32,
+            flags_affected,
+        }
+    }
+
+    const fn base_cycles(operation: Operation, mode: AddressingMode, variant: CpuVariant) -> i32 {
+        match operation {
+            Operation::Brk => return 7,
+            Operation::Jsr | Operation::Rts | Operation::Rti => return 6,
+            Operation::Pha | Operation::Php | Operation::Phx | Operation::Phy => return 3,
+            Operation::Pla | Operation::Plp | Operation::Plx | Operation::Ply => return 4,
+            Operation::Bbr(_) | Operation::Bbs(_) | Operation::Rmb(_) | Operation::Smb(_) => {
+                return 5
+            }
+            Operation::Wai | Operation::Stp => return 3,
+            // JAM never completes
+            Operation::Jam => return 0,
+            Operation::Dbg => {
+                return match mode {
+                    AddressingMode::ImmediateWord => 3,
+                    _ => 5,
+                }
+            }
+            Operation::Jmp => {
+                return match (mode, variant) {
+                    (AddressingMode::Absolute, _) => 3,
+                    (AddressingMode::Indirect, CpuVariant::Nmos6502) => 5,
+                    _ => 6,
+                }
+            }
+            _ => {}
+        }
+        let (read, store, read_modify_write) = match mode {
+            AddressingMode::Implied
+            | AddressingMode::Accumulator
+            | AddressingMode::Immediate
+            | AddressingMode::Relative => (2, 2, 2),
+            AddressingMode::ZeroPage => (3, 3, 5),
+            AddressingMode::ZeroPageX | AddressingMode::ZeroPageY => (4, 4, 6),
+            AddressingMode::Absolute => (4, 4, 6),
+            AddressingMode::AbsoluteX | AddressingMode::AbsoluteY => (4, 5, 7),
+            AddressingMode::IndexedIndirect => (6, 6, 8),
+            AddressingMode::IndirectIndexed => (5, 6, 8),
+            AddressingMode::ZeroPageIndirect => (5, 5, 5),
+            _ => (0, 0, 0),
+        };
+        if operation.is_read_modify_write() {
+            match mode {
+                AddressingMode::AbsoluteX | AddressingMode::AbsoluteY
+                    if !operation.always_pays_index_penalty(variant) =>
+                {
+                    read_modify_write - 1
+                }
+                _ => read_modify_write,
+            }
+        } else if operation.is_store() {
+            store
+        } else {
+            read
+        }
+    }
+}
+
+pub type OpcodeTable = [Option<OpcodeEntry>; 256];
+
+pub static NMOS_OPCODES: OpcodeTable = build_opcode_table(CpuVariant::Nmos6502, false);
+pub static NMOS_UNDOCUMENTED_OPCODES: OpcodeTable = build_opcode_table(CpuVariant::Nmos6502, true);
+pub static CMOS_OPCODES: OpcodeTable = build_opcode_table(CpuVariant::Cmos65C02, false);
+
+/// Opcode table for a CPU configuration, indexed by opcode. Opcodes that don't decode
+/// are `None`.
+pub fn opcode_table(variant: CpuVariant, undocumented_opcodes: bool) -> &'static OpcodeTable {
+    match (variant, undocumented_opcodes) {
+        (CpuVariant::Nmos6502, false) => &NMOS_OPCODES,
+        (CpuVariant::Nmos6502, true) => &NMOS_UNDOCUMENTED_OPCODES,
+        (CpuVariant::Cmos65C02, _) => &CMOS_OPCODES,
+    }
+}
+
+/// All opcodes defined for a CPU configuration, in opcode order.
+pub fn defined_opcodes(
+    variant: CpuVariant,
+    undocumented_opcodes: bool,
+) -> impl Iterator<Item = &'static OpcodeEntry> {
+    opcode_table(variant, undocumented_opcodes).iter().flatten()
+}
+
+const fn build_opcode_table(variant: CpuVariant, undocumented_opcodes: bool) -> OpcodeTable {
+    let mut table = [None; 256];
+    let mut opcode = 0;
+    while opcode < table.len() {
+        if let Ok(instruction) = Instruction::decode(opcode as Byte, variant, undocumented_opcodes)
+        {
+            table[opcode] = Some(OpcodeEntry::new(instruction, variant));
+        }
+        opcode += 1;
+    }
+    table
 }
 
 impl TryFrom<Byte> for Instruction {
     type Error = InstructionsError;
 
     fn try_from(value: Byte) -> Result<Self, Self::Error> {
+        Self::decode_official(value)
+    }
+}
+
+impl Instruction {
+    const fn decode_official(value: Byte) -> Result<Self, InstructionsError> {
         match value {
             // LDA
             0xA9 => Ok(Self::InsLdaIm),
@@ -382,4 +1179,562 @@ impl TryFrom<Byte> for Instruction {
             _ => Err(InstructionsError::InstructionDoesntExist(value)),
         }
     }
+
+    pub const fn opcode(self) -> Byte {
+        (self as u16 & 0x00FF) as Byte
+    }
+
+    /// Decodes an opcode for the given CPU variant. On the NMOS 6502 the undocumented
+    /// opcodes are only decoded when `undocumented_opcodes` is set.
+    pub const fn decode(
+        value: Byte,
+        variant: CpuVariant,
+        undocumented_opcodes: bool,
+    ) -> Result<Self, InstructionsError> {
+        match variant {
+            CpuVariant::Nmos6502 => match Self::decode_official(value) {
+                Err(_) if undocumented_opcodes => Self::decode_undocumented(value),
+                result => result,
+            },
+            CpuVariant::Cmos65C02 => match Self::decode_65c02(value) {
+                Err(_) => Self::decode_official(value),
+                result => result,
+            },
+        }
+    }
+
+    /// The operation and addressing mode an instruction is executed with.
+    pub const fn operation_and_mode(self) -> (Operation, AddressingMode) {
+        match self {
+            Self::InsLdaIm => (Operation::Lda, AddressingMode::Immediate),
+            Self::InsLdaZp => (Operation::Lda, AddressingMode::ZeroPage),
+            Self::InsLdaZpX => (Operation::Lda, AddressingMode::ZeroPageX),
+            Self::InsLdaAbs => (Operation::Lda, AddressingMode::Absolute),
+            Self::InsLdaAbsX => (Operation::Lda, AddressingMode::AbsoluteX),
+            Self::InsLdaAbsY => (Operation::Lda, AddressingMode::AbsoluteY),
+            Self::InsLdaIndX => (Operation::Lda, AddressingMode::IndexedIndirect),
+            Self::InsLdaIndY => (Operation::Lda, AddressingMode::IndirectIndexed),
+            Self::InsLdxIm => (Operation::Ldx, AddressingMode::Immediate),
+            Self::InsLdxZp => (Operation::Ldx, AddressingMode::ZeroPage),
+            Self::InsLdxZpy => (Operation::Ldx, AddressingMode::ZeroPageY),
+            Self::InsLdxAbs => (Operation::Ldx, AddressingMode::Absolute),
+            Self::InsLdxAbsY => (Operation::Ldx, AddressingMode::AbsoluteY),
+            Self::InsLdyIm => (Operation::Ldy, AddressingMode::Immediate),
+            Self::InsLdyZp => (Operation::Ldy, AddressingMode::ZeroPage),
+            Self::InsLdyZpX => (Operation::Ldy, AddressingMode::ZeroPageX),
+            Self::InsLdyAbs => (Operation::Ldy, AddressingMode::Absolute),
+            Self::InsLdyAbsX => (Operation::Ldy, AddressingMode::AbsoluteX),
+            Self::InsJsr => (Operation::Jsr, AddressingMode::Absolute),
+            Self::InsRts => (Operation::Rts, AddressingMode::Implied),
+            Self::InsJmpAbs => (Operation::Jmp, AddressingMode::Absolute),
+            Self::InsJmpInd => (Operation::Jmp, AddressingMode::Indirect),
+            Self::InsStaZp => (Operation::Sta, AddressingMode::ZeroPage),
+            Self::InsStaZpX => (Operation::Sta, AddressingMode::ZeroPageX),
+            Self::InsStaAbs => (Operation::Sta, AddressingMode::Absolute),
+            Self::InsStaAbsX => (Operation::Sta, AddressingMode::AbsoluteX),
+            Self::InsStaAbsY => (Operation::Sta, AddressingMode::AbsoluteY),
+            Self::InsStaIndX => (Operation::Sta, AddressingMode::IndexedIndirect),
+            Self::InsStaIndY => (Operation::Sta, AddressingMode::IndirectIndexed),
+            Self::InsStxZp => (Operation::Stx, AddressingMode::ZeroPage),
+            Self::InsStxZpY => (Operation::Stx, AddressingMode::ZeroPageY),
+            Self::InsStxAbs => (Operation::Stx, AddressingMode::Absolute),
+            Self::InsStyZp => (Operation::Sty, AddressingMode::ZeroPage),
+            Self::InsStyZpX => (Operation::Sty, AddressingMode::ZeroPageX),
+            Self::InsStyAbs => (Operation::Sty, AddressingMode::Absolute),
+            Self::InsTsx => (Operation::Tsx, AddressingMode::Implied),
+            Self::InsTxs => (Operation::Txs, AddressingMode::Implied),
+            Self::InsPha => (Operation::Pha, AddressingMode::Implied),
+            Self::InsPhp => (Operation::Php, AddressingMode::Implied),
+            Self::InsPla => (Operation::Pla, AddressingMode::Implied),
+            Self::InsPlp => (Operation::Plp, AddressingMode::Implied),
+            Self::InsAndIm => (Operation::And, AddressingMode::Immediate),
+            Self::InsAndZp => (Operation::And, AddressingMode::ZeroPage),
+            Self::InsAndZpX => (Operation::And, AddressingMode::ZeroPageX),
+            Self::InsAndAbs => (Operation::And, AddressingMode::Absolute),
+            Self::InsAndAbsX => (Operation::And, AddressingMode::AbsoluteX),
+            Self::InsAndAbsY => (Operation::And, AddressingMode::AbsoluteY),
+            Self::InsAndIndX => (Operation::And, AddressingMode::IndexedIndirect),
+            Self::InsAndIndY => (Operation::And, AddressingMode::IndirectIndexed),
+            Self::InsEorIm => (Operation::Eor, AddressingMode::Immediate),
+            Self::InsEorZp => (Operation::Eor, AddressingMode::ZeroPage),
+            Self::InsEorZpX => (Operation::Eor, AddressingMode::ZeroPageX),
+            Self::InsEorAbs => (Operation::Eor, AddressingMode::Absolute),
+            Self::InsEorAbsX => (Operation::Eor, AddressingMode::AbsoluteX),
+            Self::InsEorAbsY => (Operation::Eor, AddressingMode::AbsoluteY),
+            Self::InsEorIndX => (Operation::Eor, AddressingMode::IndexedIndirect),
+            Self::InsEorIndY => (Operation::Eor, AddressingMode::IndirectIndexed),
+            Self::InsOraIm => (Operation::Ora, AddressingMode::Immediate),
+            Self::InsOraZp => (Operation::Ora, AddressingMode::ZeroPage),
+            Self::InsOraZpX => (Operation::Ora, AddressingMode::ZeroPageX),
+            Self::InsOraAbs => (Operation::Ora, AddressingMode::Absolute),
+            Self::InsOraAbsX => (Operation::Ora, AddressingMode::AbsoluteX),
+            Self::InsOraAbsY => (Operation::Ora, AddressingMode::AbsoluteY),
+            Self::InsOraIndX => (Operation::Ora, AddressingMode::IndexedIndirect),
+            Self::InsOraIndY => (Operation::Ora, AddressingMode::IndirectIndexed),
+            Self::InsBitZp => (Operation::Bit, AddressingMode::ZeroPage),
+            Self::InsBitAbs => (Operation::Bit, AddressingMode::Absolute),
+            Self::InsTax => (Operation::Tax, AddressingMode::Implied),
+            Self::InsTay => (Operation::Tay, AddressingMode::Implied),
+            Self::InsTxa => (Operation::Txa, AddressingMode::Implied),
+            Self::InsTya => (Operation::Tya, AddressingMode::Implied),
+            Self::InsInx => (Operation::Inx, AddressingMode::Implied),
+            Self::InsIny => (Operation::Iny, AddressingMode::Implied),
+            Self::InsDex => (Operation::Dex, AddressingMode::Implied),
+            Self::InsDey => (Operation::Dey, AddressingMode::Implied),
+            Self::InsDecZp => (Operation::Dec, AddressingMode::ZeroPage),
+            Self::InsDecZpX => (Operation::Dec, AddressingMode::ZeroPageX),
+            Self::InsDecAbs => (Operation::Dec, AddressingMode::Absolute),
+            Self::InsDecAbsX => (Operation::Dec, AddressingMode::AbsoluteX),
+            Self::InsIncZp => (Operation::Inc, AddressingMode::ZeroPage),
+            Self::InsIncZpX => (Operation::Inc, AddressingMode::ZeroPageX),
+            Self::InsIncAbs => (Operation::Inc, AddressingMode::Absolute),
+            Self::InsIncAbsX => (Operation::Inc, AddressingMode::AbsoluteX),
+            Self::InsBeq => (Operation::Beq, AddressingMode::Relative),
+            Self::InsBne => (Operation::Bne, AddressingMode::Relative),
+            Self::InsBcs => (Operation::Bcs, AddressingMode::Relative),
+            Self::InsBcc => (Operation::Bcc, AddressingMode::Relative),
+            Self::InsBmi => (Operation::Bmi, AddressingMode::Relative),
+            Self::InsBpl => (Operation::Bpl, AddressingMode::Relative),
+            Self::InsBvs => (Operation::Bvs, AddressingMode::Relative),
+            Self::InsBvc => (Operation::Bvc, AddressingMode::Relative),
+            Self::InsClc => (Operation::Clc, AddressingMode::Implied),
+            Self::InsSec => (Operation::Sec, AddressingMode::Implied),
+            Self::InsCld => (Operation::Cld, AddressingMode::Implied),
+            Self::InsSed => (Operation::Sed, AddressingMode::Implied),
+            Self::InsCli => (Operation::Cli, AddressingMode::Implied),
+            Self::InsSei => (Operation::Sei, AddressingMode::Implied),
+            Self::InsClv => (Operation::Clv, AddressingMode::Implied),
+            Self::InsAdcIm => (Operation::Adc, AddressingMode::Immediate),
+            Self::InsAdcZp => (Operation::Adc, AddressingMode::ZeroPage),
+            Self::InsAdcZpX => (Operation::Adc, AddressingMode::ZeroPageX),
+            Self::InsAdcAbs => (Operation::Adc, AddressingMode::Absolute),
+            Self::InsAdcAbsX => (Operation::Adc, AddressingMode::AbsoluteX),
+            Self::InsAdcAbsY => (Operation::Adc, AddressingMode::AbsoluteY),
+            Self::InsAdcIndX => (Operation::Adc, AddressingMode::IndexedIndirect),
+            Self::InsAdcIndY => (Operation::Adc, AddressingMode::IndirectIndexed),
+            Self::InsSbcIm => (Operation::Sbc, AddressingMode::Immediate),
+            Self::InsSbcZp => (Operation::Sbc, AddressingMode::ZeroPage),
+            Self::InsSbcZpX => (Operation::Sbc, AddressingMode::ZeroPageX),
+            Self::InsSbcAbs => (Operation::Sbc, AddressingMode::Absolute),
+            Self::InsSbcAbsX => (Operation::Sbc, AddressingMode::AbsoluteX),
+            Self::InsSbcAbsY => (Operation::Sbc, AddressingMode::AbsoluteY),
+            Self::InsSbcIndX => (Operation::Sbc, AddressingMode::IndexedIndirect),
+            Self::InsSbcIndY => (Operation::Sbc, AddressingMode::IndirectIndexed),
+            Self::InsCmpIm => (Operation::Cmp, AddressingMode::Immediate),
+            Self::InsCmpZp => (Operation::Cmp, AddressingMode::ZeroPage),
+            Self::InsCmpZpX => (Operation::Cmp, AddressingMode::ZeroPageX),
+            Self::InsCmpAbs => (Operation::Cmp, AddressingMode::Absolute),
+            Self::InsCmpAbsX => (Operation::Cmp, AddressingMode::AbsoluteX),
+            Self::InsCmpAbsY => (Operation::Cmp, AddressingMode::AbsoluteY),
+            Self::InsCmpIndX => (Operation::Cmp, AddressingMode::IndexedIndirect),
+            Self::InsCmpIndY => (Operation::Cmp, AddressingMode::IndirectIndexed),
+            Self::InsCpxIm => (Operation::Cpx, AddressingMode::Immediate),
+            Self::InsCpxZp => (Operation::Cpx, AddressingMode::ZeroPage),
+            Self::InsCpxAbs => (Operation::Cpx, AddressingMode::Absolute),
+            Self::InsCpyIm => (Operation::Cpy, AddressingMode::Immediate),
+            Self::InsCpyZp => (Operation::Cpy, AddressingMode::ZeroPage),
+            Self::InsCpyAbs => (Operation::Cpy, AddressingMode::Absolute),
+            Self::InsAslA => (Operation::Asl, AddressingMode::Accumulator),
+            Self::InsAslZp => (Operation::Asl, AddressingMode::ZeroPage),
+            Self::InsAslZpX => (Operation::Asl, AddressingMode::ZeroPageX),
+            Self::InsAslAbs => (Operation::Asl, AddressingMode::Absolute),
+            Self::InsAslAbsX => (Operation::Asl, AddressingMode::AbsoluteX),
+            Self::InsLsrA => (Operation::Lsr, AddressingMode::Accumulator),
+            Self::InsLsrZp => (Operation::Lsr, AddressingMode::ZeroPage),
+            Self::InsLsrZpX => (Operation::Lsr, AddressingMode::ZeroPageX),
+            Self::InsLsrAbs => (Operation::Lsr, AddressingMode::Absolute),
+            Self::InsLsrAbsX => (Operation::Lsr, AddressingMode::AbsoluteX),
+            Self::InsRolA => (Operation::Rol, AddressingMode::Accumulator),
+            Self::InsRolZp => (Operation::Rol, AddressingMode::ZeroPage),
+            Self::InsRolZpX => (Operation::Rol, AddressingMode::ZeroPageX),
+            Self::InsRolAbs => (Operation::Rol, AddressingMode::Absolute),
+            Self::InsRolAbsX => (Operation::Rol, AddressingMode::AbsoluteX),
+            Self::InsRorA => (Operation::Ror, AddressingMode::Accumulator),
+            Self::InsRorZp => (Operation::Ror, AddressingMode::ZeroPage),
+            Self::InsRorZpX => (Operation::Ror, AddressingMode::ZeroPageX),
+            Self::InsRorAbs => (Operation::Ror, AddressingMode::Absolute),
+            Self::InsRorAbsX => (Operation::Ror, AddressingMode::AbsoluteX),
+            Self::InsNop => (Operation::Nop, AddressingMode::Implied),
+            Self::InsBrk => (Operation::Brk, AddressingMode::Implied),
+            Self::InsRti => (Operation::Rti, AddressingMode::Implied),
+            Self::InsDbgIm => (Operation::Dbg, AddressingMode::ImmediateWord),
+            Self::InsDbgAbs => (Operation::Dbg, AddressingMode::Absolute),
+            Self::InsSloZp => (Operation::Slo, AddressingMode::ZeroPage),
+            Self::InsSloZpX => (Operation::Slo, AddressingMode::ZeroPageX),
+            Self::InsSloAbs => (Operation::Slo, AddressingMode::Absolute),
+            Self::InsSloAbsX => (Operation::Slo, AddressingMode::AbsoluteX),
+            Self::InsSloAbsY => (Operation::Slo, AddressingMode::AbsoluteY),
+            Self::InsSloIndX => (Operation::Slo, AddressingMode::IndexedIndirect),
+            Self::InsSloIndY => (Operation::Slo, AddressingMode::IndirectIndexed),
+            Self::InsRlaZp => (Operation::Rla, AddressingMode::ZeroPage),
+            Self::InsRlaZpX => (Operation::Rla, AddressingMode::ZeroPageX),
+            Self::InsRlaAbs => (Operation::Rla, AddressingMode::Absolute),
+            Self::InsRlaAbsX => (Operation::Rla, AddressingMode::AbsoluteX),
+            Self::InsRlaAbsY => (Operation::Rla, AddressingMode::AbsoluteY),
+            Self::InsRlaIndX => (Operation::Rla, AddressingMode::IndexedIndirect),
+            Self::InsRlaIndY => (Operation::Rla, AddressingMode::IndirectIndexed),
+            Self::InsSreZp => (Operation::Sre, AddressingMode::ZeroPage),
+            Self::InsSreZpX => (Operation::Sre, AddressingMode::ZeroPageX),
+            Self::InsSreAbs => (Operation::Sre, AddressingMode::Absolute),
+            Self::InsSreAbsX => (Operation::Sre, AddressingMode::AbsoluteX),
+            Self::InsSreAbsY => (Operation::Sre, AddressingMode::AbsoluteY),
+            Self::InsSreIndY => (Operation::Sre, AddressingMode::IndirectIndexed),
+            Self::InsRraZp => (Operation::Rra, AddressingMode::ZeroPage),
+            Self::InsRraZpX => (Operation::Rra, AddressingMode::ZeroPageX),
+            Self::InsRraAbs => (Operation::Rra, AddressingMode::Absolute),
+            Self::InsRraAbsX => (Operation::Rra, AddressingMode::AbsoluteX),
+            Self::InsRraAbsY => (Operation::Rra, AddressingMode::AbsoluteY),
+            Self::InsRraIndX => (Operation::Rra, AddressingMode::IndexedIndirect),
+            Self::InsRraIndY => (Operation::Rra, AddressingMode::IndirectIndexed),
+            Self::InsSaxZp => (Operation::Sax, AddressingMode::ZeroPage),
+            Self::InsSaxZpY => (Operation::Sax, AddressingMode::ZeroPageY),
+            Self::InsSaxAbs => (Operation::Sax, AddressingMode::Absolute),
+            Self::InsSaxIndX => (Operation::Sax, AddressingMode::IndexedIndirect),
+            Self::InsLaxIm => (Operation::Lxa, AddressingMode::Immediate),
+            Self::InsLaxZp => (Operation::Lax, AddressingMode::ZeroPage),
+            Self::InsLaxZpY => (Operation::Lax, AddressingMode::ZeroPageY),
+            Self::InsLaxAbs => (Operation::Lax, AddressingMode::Absolute),
+            Self::InsLaxAbsY => (Operation::Lax, AddressingMode::AbsoluteY),
+            Self::InsLaxIndX => (Operation::Lax, AddressingMode::IndexedIndirect),
+            Self::InsLaxIndY => (Operation::Lax, AddressingMode::IndirectIndexed),
+            Self::InsDcpZp => (Operation::Dcp, AddressingMode::ZeroPage),
+            Self::InsDcpZpX => (Operation::Dcp, AddressingMode::ZeroPageX),
+            Self::InsDcpAbs => (Operation::Dcp, AddressingMode::Absolute),
+            Self::InsDcpAbsX => (Operation::Dcp, AddressingMode::AbsoluteX),
+            Self::InsDcpAbsY => (Operation::Dcp, AddressingMode::AbsoluteY),
+            Self::InsDcpIndX => (Operation::Dcp, AddressingMode::IndexedIndirect),
+            Self::InsDcpIndY => (Operation::Dcp, AddressingMode::IndirectIndexed),
+            Self::InsIscZp => (Operation::Isc, AddressingMode::ZeroPage),
+            Self::InsIscZpX => (Operation::Isc, AddressingMode::ZeroPageX),
+            Self::InsIscAbs => (Operation::Isc, AddressingMode::Absolute),
+            Self::InsIscAbsX => (Operation::Isc, AddressingMode::AbsoluteX),
+            Self::InsIscAbsY => (Operation::Isc, AddressingMode::AbsoluteY),
+            Self::InsIscIndX => (Operation::Isc, AddressingMode::IndexedIndirect),
+            Self::InsIscIndY => (Operation::Isc, AddressingMode::IndirectIndexed),
+            Self::InsAncIm0B => (Operation::Anc, AddressingMode::Immediate),
+            Self::InsAncIm2B => (Operation::Anc, AddressingMode::Immediate),
+            Self::InsAlrIm => (Operation::Alr, AddressingMode::Immediate),
+            Self::InsArrIm => (Operation::Arr, AddressingMode::Immediate),
+            Self::InsSbxIm => (Operation::Sbx, AddressingMode::Immediate),
+            Self::InsSbcImEB => (Operation::Sbc, AddressingMode::Immediate),
+            Self::InsXaaIm => (Operation::Xaa, AddressingMode::Immediate),
+            Self::InsShaIndY => (Operation::Sha, AddressingMode::IndirectIndexed),
+            Self::InsShaAbsY => (Operation::Sha, AddressingMode::AbsoluteY),
+            Self::InsTasAbsY => (Operation::Tas, AddressingMode::AbsoluteY),
+            Self::InsShyAbsX => (Operation::Shy, AddressingMode::AbsoluteX),
+            Self::InsShxAbsY => (Operation::Shx, AddressingMode::AbsoluteY),
+            Self::InsLasAbsY => (Operation::Las, AddressingMode::AbsoluteY),
+            Self::InsNopImp1A => (Operation::Nop, AddressingMode::Implied),
+            Self::InsNopImp3A => (Operation::Nop, AddressingMode::Implied),
+            Self::InsNopImp5A => (Operation::Nop, AddressingMode::Implied),
+            Self::InsNopImp7A => (Operation::Nop, AddressingMode::Implied),
+            Self::InsNopImpDA => (Operation::Nop, AddressingMode::Implied),
+            Self::InsNopImpFA => (Operation::Nop, AddressingMode::Implied),
+            Self::InsNopIm80 => (Operation::Nop, AddressingMode::Immediate),
+            Self::InsNopIm82 => (Operation::Nop, AddressingMode::Immediate),
+            Self::InsNopIm89 => (Operation::Nop, AddressingMode::Immediate),
+            Self::InsNopImC2 => (Operation::Nop, AddressingMode::Immediate),
+            Self::InsNopImE2 => (Operation::Nop, AddressingMode::Immediate),
+            Self::InsNopZp04 => (Operation::Nop, AddressingMode::ZeroPage),
+            Self::InsNopZp64 => (Operation::Nop, AddressingMode::ZeroPage),
+            Self::InsNopZpX14 => (Operation::Nop, AddressingMode::ZeroPageX),
+            Self::InsNopZpX34 => (Operation::Nop, AddressingMode::ZeroPageX),
+            Self::InsNopZpX54 => (Operation::Nop, AddressingMode::ZeroPageX),
+            Self::InsNopZpX74 => (Operation::Nop, AddressingMode::ZeroPageX),
+            Self::InsNopZpXD4 => (Operation::Nop, AddressingMode::ZeroPageX),
+            Self::InsNopZpXF4 => (Operation::Nop, AddressingMode::ZeroPageX),
+            Self::InsNopAbs0C => (Operation::Nop, AddressingMode::Absolute),
+            Self::InsNopAbsX1C => (Operation::Nop, AddressingMode::AbsoluteX),
+            Self::InsNopAbsX3C => (Operation::Nop, AddressingMode::AbsoluteX),
+            Self::InsNopAbsX5C => (Operation::Nop, AddressingMode::AbsoluteX),
+            Self::InsNopAbsX7C => (Operation::Nop, AddressingMode::AbsoluteX),
+            Self::InsNopAbsXDC => (Operation::Nop, AddressingMode::AbsoluteX),
+            Self::InsNopAbsXFC => (Operation::Nop, AddressingMode::AbsoluteX),
+            Self::InsJam02 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJam12 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJam22 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJam32 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJam42 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJam52 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJam62 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJam72 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJam92 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJamB2 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJamD2 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsJamF2 => (Operation::Jam, AddressingMode::Implied),
+            Self::InsBra => (Operation::Bra, AddressingMode::Relative),
+            Self::InsPhx => (Operation::Phx, AddressingMode::Implied),
+            Self::InsPlx => (Operation::Plx, AddressingMode::Implied),
+            Self::InsPhy => (Operation::Phy, AddressingMode::Implied),
+            Self::InsPly => (Operation::Ply, AddressingMode::Implied),
+            Self::InsStzZp => (Operation::Stz, AddressingMode::ZeroPage),
+            Self::InsStzZpX => (Operation::Stz, AddressingMode::ZeroPageX),
+            Self::InsStzAbs => (Operation::Stz, AddressingMode::Absolute),
+            Self::InsStzAbsX => (Operation::Stz, AddressingMode::AbsoluteX),
+            Self::InsTrbZp => (Operation::Trb, AddressingMode::ZeroPage),
+            Self::InsTrbAbs => (Operation::Trb, AddressingMode::Absolute),
+            Self::InsTsbZp => (Operation::Tsb, AddressingMode::ZeroPage),
+            Self::InsTsbAbs => (Operation::Tsb, AddressingMode::Absolute),
+            Self::InsOraIndZp => (Operation::Ora, AddressingMode::ZeroPageIndirect),
+            Self::InsAndIndZp => (Operation::And, AddressingMode::ZeroPageIndirect),
+            Self::InsEorIndZp => (Operation::Eor, AddressingMode::ZeroPageIndirect),
+            Self::InsAdcIndZp => (Operation::Adc, AddressingMode::ZeroPageIndirect),
+            Self::InsStaIndZp => (Operation::Sta, AddressingMode::ZeroPageIndirect),
+            Self::InsLdaIndZp => (Operation::Lda, AddressingMode::ZeroPageIndirect),
+            Self::InsCmpIndZp => (Operation::Cmp, AddressingMode::ZeroPageIndirect),
+            Self::InsSbcIndZp => (Operation::Sbc, AddressingMode::ZeroPageIndirect),
+            Self::InsBitIm => (Operation::Bit, AddressingMode::Immediate),
+            Self::InsBitZpX => (Operation::Bit, AddressingMode::ZeroPageX),
+            Self::InsBitAbsX => (Operation::Bit, AddressingMode::AbsoluteX),
+            Self::InsIncA => (Operation::Inc, AddressingMode::Accumulator),
+            Self::InsDecA => (Operation::Dec, AddressingMode::Accumulator),
+            Self::InsJmpAbsIndX => (Operation::Jmp, AddressingMode::AbsoluteIndexedIndirect),
+            Self::InsBbr0 => (Operation::Bbr(0), AddressingMode::ZeroPageRelative),
+            Self::InsBbr1 => (Operation::Bbr(1), AddressingMode::ZeroPageRelative),
+            Self::InsBbr2 => (Operation::Bbr(2), AddressingMode::ZeroPageRelative),
+            Self::InsBbr3 => (Operation::Bbr(3), AddressingMode::ZeroPageRelative),
+            Self::InsBbr4 => (Operation::Bbr(4), AddressingMode::ZeroPageRelative),
+            Self::InsBbr5 => (Operation::Bbr(5), AddressingMode::ZeroPageRelative),
+            Self::InsBbr6 => (Operation::Bbr(6), AddressingMode::ZeroPageRelative),
+            Self::InsBbr7 => (Operation::Bbr(7), AddressingMode::ZeroPageRelative),
+            Self::InsBbs0 => (Operation::Bbs(0), AddressingMode::ZeroPageRelative),
+            Self::InsBbs1 => (Operation::Bbs(1), AddressingMode::ZeroPageRelative),
+            Self::InsBbs2 => (Operation::Bbs(2), AddressingMode::ZeroPageRelative),
+            Self::InsBbs3 => (Operation::Bbs(3), AddressingMode::ZeroPageRelative),
+            Self::InsBbs4 => (Operation::Bbs(4), AddressingMode::ZeroPageRelative),
+            Self::InsBbs5 => (Operation::Bbs(5), AddressingMode::ZeroPageRelative),
+            Self::InsBbs6 => (Operation::Bbs(6), AddressingMode::ZeroPageRelative),
+            Self::InsBbs7 => (Operation::Bbs(7), AddressingMode::ZeroPageRelative),
+            Self::InsRmb0 => (Operation::Rmb(0), AddressingMode::ZeroPage),
+            Self::InsRmb1 => (Operation::Rmb(1), AddressingMode::ZeroPage),
+            Self::InsRmb2 => (Operation::Rmb(2), AddressingMode::ZeroPage),
+            Self::InsRmb3 => (Operation::Rmb(3), AddressingMode::ZeroPage),
+            Self::InsRmb4 => (Operation::Rmb(4), AddressingMode::ZeroPage),
+            Self::InsRmb5 => (Operation::Rmb(5), AddressingMode::ZeroPage),
+            Self::InsRmb6 => (Operation::Rmb(6), AddressingMode::ZeroPage),
+            Self::InsRmb7 => (Operation::Rmb(7), AddressingMode::ZeroPage),
+            Self::InsSmb0 => (Operation::Smb(0), AddressingMode::ZeroPage),
+            Self::InsSmb1 => (Operation::Smb(1), AddressingMode::ZeroPage),
+            Self::InsSmb2 => (Operation::Smb(2), AddressingMode::ZeroPage),
+            Self::InsSmb3 => (Operation::Smb(3), AddressingMode::ZeroPage),
+            Self::InsSmb4 => (Operation::Smb(4), AddressingMode::ZeroPage),
+            Self::InsSmb5 => (Operation::Smb(5), AddressingMode::ZeroPage),
+            Self::InsSmb6 => (Operation::Smb(6), AddressingMode::ZeroPage),
+            Self::InsSmb7 => (Operation::Smb(7), AddressingMode::ZeroPage),
+            Self::InsWai => (Operation::Wai, AddressingMode::Implied),
+            Self::InsStp => (Operation::Stp, AddressingMode::Implied),
+        }
+    }
+
+    /// Length in bytes and cycle count of an opcode the 65C02 leaves undefined. These
+    /// all behave as NOPs.
+    pub fn undefined_65c02_nop(value: Byte) -> (Word, i32) {
+        match value {
+            0x02 | 0x22 | 0x42 | 0x62 | 0x82 | 0xC2 | 0xE2 => (2, 2),
+            0x54 | 0xD4 | 0xF4 => (2, 4),
+            0x5C => (3, 8),
+            0xDC | 0xFC => (3, 4),
+            _ => (1, 1),
+        }
+    }
+
+    const fn decode_65c02(value: Byte) -> Result<Self, InstructionsError> {
+        match value {
+            // Branch and stack
+            0x80 => Ok(Self::InsBra),
+            0xDA => Ok(Self::InsPhx),
+            0xFA => Ok(Self::InsPlx),
+            0x5A => Ok(Self::InsPhy),
+            0x7A => Ok(Self::InsPly),
+            // STZ
+            0x64 => Ok(Self::InsStzZp),
+            0x74 => Ok(Self::InsStzZpX),
+            0x9C => Ok(Self::InsStzAbs),
+            0x9E => Ok(Self::InsStzAbsX),
+            // TRB and TSB
+            0x14 => Ok(Self::InsTrbZp),
+            0x1C => Ok(Self::InsTrbAbs),
+            0x04 => Ok(Self::InsTsbZp),
+            0x0C => Ok(Self::InsTsbAbs),
+            // Zero page indirect
+            0x12 => Ok(Self::InsOraIndZp),
+            0x32 => Ok(Self::InsAndIndZp),
+            0x52 => Ok(Self::InsEorIndZp),
+            0x72 => Ok(Self::InsAdcIndZp),
+            0x92 => Ok(Self::InsStaIndZp),
+            0xB2 => Ok(Self::InsLdaIndZp),
+            0xD2 => Ok(Self::InsCmpIndZp),
+            0xF2 => Ok(Self::InsSbcIndZp),
+            // BIT
+            0x89 => Ok(Self::InsBitIm),
+            0x34 => Ok(Self::InsBitZpX),
+            0x3C => Ok(Self::InsBitAbsX),
+            // Accumulator increments
+            0x1A => Ok(Self::InsIncA),
+            0x3A => Ok(Self::InsDecA),
+            // Jumps
+            0x7C => Ok(Self::InsJmpAbsIndX),
+            // BBR
+            0x0F => Ok(Self::InsBbr0),
+            0x1F => Ok(Self::InsBbr1),
+            0x2F => Ok(Self::InsBbr2),
+            0x3F => Ok(Self::InsBbr3),
+            0x4F => Ok(Self::InsBbr4),
+            0x5F => Ok(Self::InsBbr5),
+            0x6F => Ok(Self::InsBbr6),
+            0x7F => Ok(Self::InsBbr7),
+            // BBS
+            0x8F => Ok(Self::InsBbs0),
+            0x9F => Ok(Self::InsBbs1),
+            0xAF => Ok(Self::InsBbs2),
+            0xBF => Ok(Self::InsBbs3),
+            0xCF => Ok(Self::InsBbs4),
+            0xDF => Ok(Self::InsBbs5),
+            0xEF => Ok(Self::InsBbs6),
+            0xFF => Ok(Self::InsBbs7),
+            // RMB
+            0x07 => Ok(Self::InsRmb0),
+            0x17 => Ok(Self::InsRmb1),
+            0x27 => Ok(Self::InsRmb2),
+            0x37 => Ok(Self::InsRmb3),
+            0x47 => Ok(Self::InsRmb4),
+            0x57 => Ok(Self::InsRmb5),
+            0x67 => Ok(Self::InsRmb6),
+            0x77 => Ok(Self::InsRmb7),
+            // SMB
+            0x87 => Ok(Self::InsSmb0),
+            0x97 => Ok(Self::InsSmb1),
+            0xA7 => Ok(Self::InsSmb2),
+            0xB7 => Ok(Self::InsSmb3),
+            0xC7 => Ok(Self::InsSmb4),
+            0xD7 => Ok(Self::InsSmb5),
+            0xE7 => Ok(Self::InsSmb6),
+            0xF7 => Ok(Self::InsSmb7),
+            // Processor
+            0xCB => Ok(Self::InsWai),
+            0xDB => Ok(Self::InsStp),
+            _ => Err(InstructionsError::InstructionDoesntExist(value)),
+        }
+    }
+
+    const fn decode_undocumented(value: Byte) -> Result<Self, InstructionsError> {
+        match value {
+            // SLO
+            0x07 => Ok(Self::InsSloZp),
+            0x17 => Ok(Self::InsSloZpX),
+            0x0F => Ok(Self::InsSloAbs),
+            0x1F => Ok(Self::InsSloAbsX),
+            0x1B => Ok(Self::InsSloAbsY),
+            0x03 => Ok(Self::InsSloIndX),
+            0x13 => Ok(Self::InsSloIndY),
+            // RLA
+            0x27 => Ok(Self::InsRlaZp),
+            0x37 => Ok(Self::InsRlaZpX),
+            0x2F => Ok(Self::InsRlaAbs),
+            0x3F => Ok(Self::InsRlaAbsX),
+            0x3B => Ok(Self::InsRlaAbsY),
+            0x23 => Ok(Self::InsRlaIndX),
+            0x33 => Ok(Self::InsRlaIndY),
+            // SRE (0x43 is taken by InsDbgAbs)
+            0x47 => Ok(Self::InsSreZp),
+            0x57 => Ok(Self::InsSreZpX),
+            0x4F => Ok(Self::InsSreAbs),
+            0x5F => Ok(Self::InsSreAbsX),
+            0x5B => Ok(Self::InsSreAbsY),
+            0x53 => Ok(Self::InsSreIndY),
+            // RRA
+            0x67 => Ok(Self::InsRraZp),
+            0x77 => Ok(Self::InsRraZpX),
+            0x6F => Ok(Self::InsRraAbs),
+            0x7F => Ok(Self::InsRraAbsX),
+            0x7B => Ok(Self::InsRraAbsY),
+            0x63 => Ok(Self::InsRraIndX),
+            0x73 => Ok(Self::InsRraIndY),
+            // SAX
+            0x87 => Ok(Self::InsSaxZp),
+            0x97 => Ok(Self::InsSaxZpY),
+            0x8F => Ok(Self::InsSaxAbs),
+            0x83 => Ok(Self::InsSaxIndX),
+            // LAX
+            0xAB => Ok(Self::InsLaxIm),
+            0xA7 => Ok(Self::InsLaxZp),
+            0xB7 => Ok(Self::InsLaxZpY),
+            0xAF => Ok(Self::InsLaxAbs),
+            0xBF => Ok(Self::InsLaxAbsY),
+            0xA3 => Ok(Self::InsLaxIndX),
+            0xB3 => Ok(Self::InsLaxIndY),
+            // DCP
+            0xC7 => Ok(Self::InsDcpZp),
+            0xD7 => Ok(Self::InsDcpZpX),
+            0xCF => Ok(Self::InsDcpAbs),
+            0xDF => Ok(Self::InsDcpAbsX),
+            0xDB => Ok(Self::InsDcpAbsY),
+            0xC3 => Ok(Self::InsDcpIndX),
+            0xD3 => Ok(Self::InsDcpIndY),
+            // ISC
+            0xE7 => Ok(Self::InsIscZp),
+            0xF7 => Ok(Self::InsIscZpX),
+            0xEF => Ok(Self::InsIscAbs),
+            0xFF => Ok(Self::InsIscAbsX),
+            0xFB => Ok(Self::InsIscAbsY),
+            0xE3 => Ok(Self::InsIscIndX),
+            0xF3 => Ok(Self::InsIscIndY),
+            // Immediate
+            0x0B => Ok(Self::InsAncIm0B),
+            0x2B => Ok(Self::InsAncIm2B),
+            0x4B => Ok(Self::InsAlrIm),
+            0x6B => Ok(Self::InsArrIm),
+            0xCB => Ok(Self::InsSbxIm),
+            0xEB => Ok(Self::InsSbcImEB),
+            0x8B => Ok(Self::InsXaaIm),
+            // Unstable stores and loads
+            0x93 => Ok(Self::InsShaIndY),
+            0x9F => Ok(Self::InsShaAbsY),
+            0x9B => Ok(Self::InsTasAbsY),
+            0x9C => Ok(Self::InsShyAbsX),
+            0x9E => Ok(Self::InsShxAbsY),
+            0xBB => Ok(Self::InsLasAbsY),
+            // NOP (0x44 is taken by InsDbgIm)
+            0x1A => Ok(Self::InsNopImp1A),
+            0x3A => Ok(Self::InsNopImp3A),
+            0x5A => Ok(Self::InsNopImp5A),
+            0x7A => Ok(Self::InsNopImp7A),
+            0xDA => Ok(Self::InsNopImpDA),
+            0xFA => Ok(Self::InsNopImpFA),
+            0x80 => Ok(Self::InsNopIm80),
+            0x82 => Ok(Self::InsNopIm82),
+            0x89 => Ok(Self::InsNopIm89),
+            0xC2 => Ok(Self::InsNopImC2),
+            0xE2 => Ok(Self::InsNopImE2),
+            0x04 => Ok(Self::InsNopZp04),
+            0x64 => Ok(Self::InsNopZp64),
+            0x14 => Ok(Self::InsNopZpX14),
+            0x34 => Ok(Self::InsNopZpX34),
+            0x54 => Ok(Self::InsNopZpX54),
+            0x74 => Ok(Self::InsNopZpX74),
+            0xD4 => Ok(Self::InsNopZpXD4),
+            0xF4 => Ok(Self::InsNopZpXF4),
+            0x0C => Ok(Self::InsNopAbs0C),
+            0x1C => Ok(Self::InsNopAbsX1C),
+            0x3C => Ok(Self::InsNopAbsX3C),
+            0x5C => Ok(Self::InsNopAbsX5C),
+            0x7C => Ok(Self::InsNopAbsX7C),
+            0xDC => Ok(Self::InsNopAbsXDC),
+            0xFC => Ok(Self::InsNopAbsXFC),
+            // JAM
+            0x02 => Ok(Self::InsJam02),
+            0x12 => Ok(Self::InsJam12),
+            0x22 => Ok(Self::InsJam22),
+            0x32 => Ok(Self::InsJam32),
+            0x42 => Ok(Self::InsJam42),
+            0x52 => Ok(Self::InsJam52),
+            0x62 => Ok(Self::InsJam62),
+            0x72 => Ok(Self::InsJam72),
+            0x92 => Ok(Self::InsJam92),
+            0xB2 => Ok(Self::InsJamB2),
+            0xD2 => Ok(Self::InsJamD2),
+            0xF2 => Ok(Self::InsJamF2),
+            _ => Err(InstructionsError::InstructionDoesntExist(value)),
+        }
+    }
 }
@@ -0,0 +1,295 @@
+use std::{io, path::Path};
+
+use crate::{
+    bus::Bus,
+    cpu::{Byte, Word},
+};
+
+/// Errors carry the 1-based line of the record they were found on.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LoaderError {
+    MissingStartCode {
+        line: usize,
+    },
+    InvalidHexDigit {
+        line: usize,
+    },
+    RecordTooShort {
+        line: usize,
+    },
+    LengthMismatch {
+        line: usize,
+    },
+    ChecksumMismatch {
+        line: usize,
+        expected: Byte,
+        found: Byte,
+    },
+    UnsupportedRecordType {
+        line: usize,
+        record_type: Byte,
+    },
+    AddressOutOfRange {
+        line: usize,
+        address: u32,
+    },
+    MissingEndRecord,
+    ImageTooShort,
+    ImagePastEndOfMemory {
+        start: Word,
+        length: usize,
+    },
+    Io(io::ErrorKind),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Two byte little endian load address followed by the data.
+    Prg,
+    IntelHex,
+    SRecord,
+    /// The whole file loaded at the given base.
+    Raw(Word),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub address: Word,
+    pub data: Vec<Byte>,
+}
+
+/// A parsed program, segments are kept in file order and later ones win where they
+/// overlap.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Image {
+    pub segments: Vec<Segment>,
+    pub entry: Option<Word>,
+}
+
+impl Image {
+    /// The entry address from the file, or the start of the first segment.
+    pub fn start_address(&self) -> Option<Word> {
+        self.entry
+            .or_else(|| self.segments.first().map(|segment| segment.address))
+    }
+
+    /// Writes every segment to `bus` and returns `start_address`.
+    pub fn load_into<B: Bus>(&self, bus: &mut B) -> Option<Word> {
+        for segment in &self.segments {
+            for (offset, byte) in segment.data.iter().enumerate() {
+                bus.write(segment.address.wrapping_add(offset as Word), *byte);
+            }
+        }
+        self.start_address()
+    }
+
+    fn add_data(&mut self, line: usize, address: u32, data: &[Byte]) -> Result<(), LoaderError> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let last_address = address.checked_add(data.len() as u32 - 1);
+        match last_address {
+            Some(last_address) if last_address <= 0xFFFF => {}
+            _ => {
+                return Err(LoaderError::AddressOutOfRange {
+                    line,
+                    address: last_address.unwrap_or(address),
+                })
+            }
+        }
+        let address = address as Word;
+        match self.segments.last_mut() {
+            Some(segment) if segment.address as usize + segment.data.len() == address as usize => {
+                segment.data.extend_from_slice(data)
+            }
+            _ => self.segments.push(Segment {
+                address,
+                data: data.to_vec(),
+            }),
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_prg(data: &[Byte]) -> Result<Image, LoaderError> {
+    if data.len() < 2 {
+        return Err(LoaderError::ImageTooShort);
+    }
+    let address = Word::from_le_bytes([data[0], data[1]]);
+    let mut image = parse_raw(&data[2..], address)?;
+    image.entry = Some(address);
+    Ok(image)
+}
+
+pub fn parse_raw(data: &[Byte], base: Word) -> Result<Image, LoaderError> {
+    if base as usize + data.len() > 0x10000 {
+        return Err(LoaderError::ImagePastEndOfMemory {
+            start: base,
+            length: data.len(),
+        });
+    }
+    Ok(Image {
+        segments: vec![Segment {
+            address: base,
+            data: data.to_vec(),
+        }],
+        entry: Some(base),
+    })
+}
+
+/// Parses Intel HEX. Extended segment and linear address records are understood, but
+/// everything has to land below $10000.
+pub fn parse_intel_hex(text: &str) -> Result<Image, LoaderError> {
+    let mut image = Image::default();
+    let mut base: u32 = 0;
+    for (index, text_line) in text.lines().enumerate() {
+        let line = index + 1;
+        let text_line = text_line.trim();
+        if text_line.is_empty() {
+            continue;
+        }
+        let Some(digits) = text_line.strip_prefix(':') else {
+            return Err(LoaderError::MissingStartCode { line });
+        };
+        let bytes = decode_hex(line, digits)?;
+        if bytes.len() < 5 {
+            return Err(LoaderError::RecordTooShort { line });
+        }
+        let length = bytes[0] as usize;
+        if bytes.len() != length + 5 {
+            return Err(LoaderError::LengthMismatch { line });
+        }
+        let (record, checksum) = bytes.split_at(bytes.len() - 1);
+        let expected = record
+            .iter()
+            .fold(0, |sum: Byte, byte| sum.wrapping_add(*byte))
+            .wrapping_neg();
+        if checksum[0] != expected {
+            return Err(LoaderError::ChecksumMismatch {
+                line,
+                expected,
+                found: checksum[0],
+            });
+        }
+        let address = u16::from_be_bytes([record[1], record[2]]) as u32;
+        let record_type = record[3];
+        let data = &record[4..];
+        match (record_type, data.len()) {
+            (0x00, _) => {
+                let address = base
+                    .checked_add(address)
+                    .ok_or(LoaderError::AddressOutOfRange {
+                        line,
+                        address: base,
+                    })?;
+                image.add_data(line, address, data)?
+            }
+            (0x01, _) => return Ok(image),
+            (0x02, 2) => base = (u16::from_be_bytes([data[0], data[1]]) as u32) << 4,
+            (0x04, 2) => base = (u16::from_be_bytes([data[0], data[1]]) as u32) << 16,
+            (0x03, 4) => {
+                let segment = u16::from_be_bytes([data[0], data[1]]) as u32;
+                let offset = u16::from_be_bytes([data[2], data[3]]) as u32;
+                image.entry = Some(entry_address(line, (segment << 4) + offset)?);
+            }
+            (0x05, 4) => {
+                let address = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+                image.entry = Some(entry_address(line, address)?);
+            }
+            (0x02..=0x05, _) => return Err(LoaderError::LengthMismatch { line }),
+            _ => return Err(LoaderError::UnsupportedRecordType { line, record_type }),
+        }
+    }
+    Err(LoaderError::MissingEndRecord)
+}
+
+/// Parses Motorola S-records. S1/S2/S3 carry data, S7/S8/S9 the entry address, header
+/// and count records are checked but otherwise ignored.
+pub fn parse_srecord(text: &str) -> Result<Image, LoaderError> {
+    let mut image = Image::default();
+    for (index, text_line) in text.lines().enumerate() {
+        let line = index + 1;
+        let text_line = text_line.trim();
+        if text_line.is_empty() {
+            continue;
+        }
+        let mut characters = text_line.chars();
+        if characters.next() != Some('S') {
+            return Err(LoaderError::MissingStartCode { line });
+        }
+        let record_type = match characters.next().and_then(|digit| digit.to_digit(10)) {
+            Some(record_type) => record_type as Byte,
+            None => return Err(LoaderError::InvalidHexDigit { line }),
+        };
+        let bytes = decode_hex(line, characters.as_str())?;
+        let address_length = match record_type {
+            0 | 1 | 5 | 9 => 2,
+            2 | 6 | 8 => 3,
+            3 | 7 => 4,
+            _ => return Err(LoaderError::UnsupportedRecordType { line, record_type }),
+        };
+        if bytes.len() < address_length + 2 {
+            return Err(LoaderError::RecordTooShort { line });
+        }
+        if bytes.len() != bytes[0] as usize + 1 {
+            return Err(LoaderError::LengthMismatch { line });
+        }
+        let (record, checksum) = bytes.split_at(bytes.len() - 1);
+        let expected = !record
+            .iter()
+            .fold(0, |sum: Byte, byte| sum.wrapping_add(*byte));
+        if checksum[0] != expected {
+            return Err(LoaderError::ChecksumMismatch {
+                line,
+                expected,
+                found: checksum[0],
+            });
+        }
+        let address = record[1..=address_length]
+            .iter()
+            .fold(0, |address: u32, byte| (address << 8) | *byte as u32);
+        let data = &record[address_length + 1..];
+        match record_type {
+            1..=3 => image.add_data(line, address, data)?,
+            7..=9 => image.entry = Some(entry_address(line, address)?),
+            _ => {}
+        }
+    }
+    Ok(image)
+}
+
+pub fn parse(format: Format, data: &[Byte]) -> Result<Image, LoaderError> {
+    match format {
+        Format::Prg => parse_prg(data),
+        Format::Raw(base) => parse_raw(data, base),
+        Format::IntelHex => parse_intel_hex(&String::from_utf8_lossy(data)),
+        Format::SRecord => parse_srecord(&String::from_utf8_lossy(data)),
+    }
+}
+
+/// Reads and parses `path`, loads it into `bus` and returns the start address.
+pub fn load_file<B: Bus>(
+    path: impl AsRef<Path>,
+    format: Format,
+    bus: &mut B,
+) -> Result<Option<Word>, LoaderError> {
+    let data = std::fs::read(path).map_err(|error| LoaderError::Io(error.kind()))?;
+    Ok(parse(format, &data)?.load_into(bus))
+}
+
+fn entry_address(line: usize, address: u32) -> Result<Word, LoaderError> {
+    Word::try_from(address).map_err(|_| LoaderError::AddressOutOfRange { line, address })
+}
+
+fn decode_hex(line: usize, digits: &str) -> Result<Vec<Byte>, LoaderError> {
+    if digits.len() % 2 == 1 || !digits.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+        return Err(LoaderError::InvalidHexDigit { line });
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|index| {
+            Byte::from_str_radix(&digits[index..index + 2], 16)
+                .map_err(|_| LoaderError::InvalidHexDigit { line })
+        })
+        .collect()
+}
@@ -1,45 +1,98 @@
 #![allow(unused)]
-use cpu::{Byte, Word, CPU};
+use assembler::Assembler;
+use cpu::{Byte, CpuVariant, Word, CPU};
+use disassembler::Disassembler;
 use graphics_adapter::GraphicsAdapter;
 use instructions::{Instruction, InstructionsError};
 use memory::Memory;
-use sdl2::{event::{Event, WindowEvent}, pixels::Color, rect::Rect, render::Canvas};
+use memory_map::RomWritePolicy;
+use movie::{InputEvent, Movie, Recorder};
+use rewind::Rewind;
+use sdl2::{event::{Event, WindowEvent}, keyboard::Keycode, pixels::Color, rect::Rect, render::Canvas};
 
+pub mod assembler;
+pub mod banked_memory;
+pub mod bus;
 pub mod cpu;
+pub mod disassembler;
 pub mod graphics_adapter;
 pub mod instructions;
+pub mod loader;
 pub mod memory;
+pub mod memory_map;
+pub mod movie;
+pub mod o65;
+pub mod power_on;
+pub mod rewind;
+pub mod save_state;
+pub mod serial_port;
+pub mod symbols;
+pub mod watchpoints;
 
 #[cfg(test)]
+#[allow(clippy::items_after_test_module)]
 mod tests {
     pub mod add_subtract_with_carry_tests;
+    pub mod assembler_tests;
+    pub mod banked_memory_tests;
+    pub mod benchmark;
+    pub mod bus_tests;
     pub mod branch_tests;
+    pub mod cmos_65c02_tests;
     pub mod compare_register_tests;
+    pub mod cycle_counter_tests;
+    pub mod disassembler_tests;
     pub mod inc_dec_tests;
+    pub mod interrupt_tests;
     pub mod jumps_and_calls_tests;
     pub mod load_tests;
+    pub mod loader_tests;
     pub mod loading_program;
     pub mod logical_ops_tests;
+    pub mod memory_map_tests;
     pub mod miscellaneous_tests;
+    pub mod movie_tests;
+    pub mod o65_tests;
+    pub mod opcode_table_tests;
+    pub mod power_on_tests;
+    pub mod reset_tests;
+    pub mod rewind_tests;
+    pub mod save_state_tests;
     pub mod shifts_tests;
     pub mod stack_operations_tests;
     pub mod status_changes_tests;
+    pub mod step_tests;
     pub mod store_tests;
+    pub mod symbols_tests;
     pub mod transfer_register_tests;
+    pub mod undocumented_opcodes_tests;
+    pub mod watchpoint_tests;
+    pub mod wraparound_tests;
 }
 
-fn main() {
-    let context = sdl2::init().unwrap();
-    let mut event_pump = context.event_pump().unwrap();
-    let video = context.video().unwrap();
+/// Key presses land here, as the ASCII code of the key.
+const KEYBOARD_ADDRESS: Word = 0x00FF;
 
-    let window = video
-        .window("6502 Emulator", 400, 400)
-        .resizable()
-        .build()
-        .unwrap();
-
-    let mut canvas = window.into_canvas().present_vsync().build().unwrap();
+fn main() {
+    // `asm input.ms output.prg [listing.lst]` assembles a program instead of running one,
+    // `dis program.prg` prints its disassembly
+    let args: Vec<String> = std::env::args().collect();
+    match &args[..] {
+        [_, command, input, output, listing @ ..] if command == "asm" => {
+            assemble(input, output, listing.first());
+            return;
+        }
+        [_, command, input] if command == "dis" => {
+            let prg = std::fs::read(input).unwrap();
+            let lines = Disassembler::new(CpuVariant::Nmos6502, false).disassemble_prg(&prg);
+            match lines {
+                Ok(lines) => print!("{}", disassembler::listing_text(&lines)),
+                Err(error) => eprintln!("{}: {:?}", input, error),
+            }
+            return;
+        }
+        _ => {}
+    }
 
     let graphics = GraphicsAdapter::new(Color {
         r: 100,
@@ -48,19 +101,53 @@ fn main() {
         a: 255,
     });
 
-    let mut cpu = CPU::new_graphics(graphics, Some(0xFF00));
+    let mut cpu = CPU::new_graphics(graphics, 0xFF00);
     let mut memory = Memory::initialize();
 
     let path =
         std::path::Path::new(&std::env::current_dir().unwrap()).join("assembly\\test_code.prg");
-    let file = std::fs::read(path).unwrap();
-    let slice = &mut memory[0xFF00..(0xFF00+file.len())];
-    slice.copy_from_slice(&file);
+    let image = std::fs::read(path).unwrap();
+    loader::parse_raw(&image, 0xFF00).unwrap().load_into(&mut memory);
+    // The program and the vectors live in the top page, a write there is a runaway program
+    memory.map_rom(0xFF00..=0xFFFF, RomWritePolicy::Fault);
+
+    // `--record movie.txt` saves the key presses on quit, `--replay movie.txt` plays them
+    // back without a window and checks the run ends the same way
+    let record_path = match &args[1..] {
+        [flag, path] if flag == "--record" => Some(path.clone()),
+        [flag, path] if flag == "--replay" => {
+            let result = Movie::load_from_file(path)
+                .and_then(|movie| movie.replay(&image, &mut cpu, &mut memory));
+            match result {
+                Ok(()) => println!("Replay matches the recording"),
+                Err(error) => {
+                    eprintln!("Replay failed: {:?}", error);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        _ => None,
+    };
+    let mut recorder = Recorder::new(&image);
+
+    let context = sdl2::init().unwrap();
+    let mut event_pump = context.event_pump().unwrap();
+    let video = context.video().unwrap();
+
+    let window = video
+        .window("6502 Emulator", 400, 400)
+        .resizable()
+        .build()
+        .unwrap();
+
+    let mut canvas = window.into_canvas().present_vsync().build().unwrap();
 
     let mut pixel_width = canvas.window().size().0 / 16;
     let mut pixel_height = canvas.window().size().1 / 16;
 
     let mut last_pixels = *cpu.get_graphics().unwrap().get_pixels();
+    let mut rewind = Rewind::new(1000, 256);
     'running: loop {
         /* println!("{}", cpu);
         println!(
@@ -73,7 +160,11 @@ fn main() {
         ); */
         // println!("Mem: {:02x}_{:02x}", memory[0x0100_u16], memory[0x0101_u16]);
         // println!("Mem: {}", memory[0x0001_u16]);
-        cpu.execute(1, &mut memory);
+        if let Err(error) = cpu.step(&mut memory) {
+            eprintln!("CPU stopped at {:04X}: {:?}", cpu.program_counter, error);
+            break 'running;
+        }
+        rewind.record(&cpu, &memory);
 
         for event in event_pump.poll_iter() {
             match event {
@@ -84,6 +175,29 @@ fn main() {
                     pixel_width = new_width as u32 / 16;
                     pixel_height = new_height as u32 / 16;
                 }
+                // F5 steps back one instruction, F6 jumps back to the oldest snapshot. F-keys
+                // never reach the program, and rewinding would break the movie, so it's off
+                // while recording
+                Event::KeyDown { keycode: Some(Keycode::F5), .. } if record_path.is_none() => {
+                    if let Err(error) = rewind.step_back(&mut cpu, &mut memory) {
+                        eprintln!("Step back failed: {:?}", error);
+                    }
+                }
+                Event::KeyDown { keycode: Some(Keycode::F6), .. } if record_path.is_none() => {
+                    if let Some(cycle) = rewind.oldest_cycle() {
+                        if let Err(error) = rewind.rewind_to_cycle(cycle, &mut cpu, &mut memory) {
+                            eprintln!("Rewind failed: {:?}", error);
+                        }
+                    }
+                }
+                Event::KeyDown { keycode: Some(keycode), .. } if (keycode as i32) < 0x80 => {
+                    let event = InputEvent::Write {
+                        address: KEYBOARD_ADDRESS,
+                        value: keycode as i32 as Byte,
+                    };
+                    recorder.record(event, &mut cpu, &mut memory);
+                    rewind.take_snapshot(&cpu, &memory);
+                }
                 _ => {}
             }
         }
@@ -98,10 +212,29 @@ fn main() {
 
         // std::thread::sleep(std::time::Duration::from_millis(30));
     }
+
+    if let Some(path) = record_path {
+        recorder.finish(&cpu, &memory).save_to_file(path).unwrap();
+    }
+}
+
+fn assemble(input: &str, output: &str, listing: Option<&String>) {
+    let source = std::fs::read_to_string(input).unwrap();
+    let assembly = match Assembler::new(CpuVariant::Nmos6502).assemble(&source) {
+        Ok(assembly) => assembly,
+        Err(error) => {
+            eprintln!("{}:{}", input, error);
+            std::process::exit(1);
+        }
+    };
+    std::fs::write(output, assembly.to_prg()).unwrap();
+    if let Some(listing) = listing {
+        std::fs::write(listing, assembly.listing_text()).unwrap();
+    }
 }
 
 /* fn main() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.program_counter = 0x400;
@@ -1,18 +1,74 @@
-use std::ops::{Index, IndexMut, Range};
+use std::ops::{Index, IndexMut, Range, RangeInclusive};
 
-use crate::cpu::{Byte, Word};
+use crate::{
+    bus::{Bus, BusFault},
+    cpu::{Byte, Word},
+    memory_map::RomWritePolicy,
+    power_on::PowerOnPattern,
+};
 const MAX_MEM: usize = 1024 * 64;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RomRange {
+    range: RangeInclusive<Word>,
+    write_policy: RomWritePolicy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Memory {
     data: [Byte; MAX_MEM],
+    rom: Vec<RomRange>,
+    fault: Option<BusFault>,
 }
 
 impl Memory {
     pub const fn initialize() -> Memory {
-        Memory { data: [0; MAX_MEM] }
+        Memory {
+            data: [0; MAX_MEM],
+            rom: Vec::new(),
+            fault: None,
+        }
+    }
+
+    /// Turns `range` into ROM, writes through the bus then follow `write_policy`.
+    /// Indexing still changes it, for loading what the ROM holds.
+    pub fn map_rom(&mut self, range: RangeInclusive<Word>, write_policy: RomWritePolicy) {
+        self.rom.push(RomRange {
+            range,
+            write_policy,
+        });
+    }
+
+    /// Creates memory filled the way `pattern` says RAM powers up.
+    pub fn power_on(pattern: PowerOnPattern) -> Memory {
+        let mut memory = Memory::initialize();
+        pattern.fill(&mut memory.data, 0x100);
+        memory
+    }
+}
+
+impl Bus for Memory {
+    fn read(&mut self, address: Word) -> Byte {
+        self.data[address as usize]
+    }
+
+    fn write(&mut self, address: Word, value: Byte) {
+        match self.rom.iter().find(|rom| rom.range.contains(&address)) {
+            None => self.data[address as usize] = value,
+            Some(rom) if rom.write_policy == RomWritePolicy::Fault => {
+                self.fault = Some(BusFault::WriteToRom { address, value })
+            }
+            Some(_) => {}
+        }
     }
 
+    fn peek(&self, address: Word) -> Byte {
+        self.data[address as usize]
+    }
+
+    fn take_fault(&mut self) -> Option<BusFault> {
+        self.fault.take()
+    }
 }
 
 impl Index<Word> for Memory {
@@ -0,0 +1,225 @@
+use std::{cell::RefCell, io, ops::RangeInclusive, path::Path, rc::Rc};
+
+use crate::{
+    bus::{Bus, BusFault},
+    cpu::{Byte, Word},
+    memory::Memory,
+};
+
+/// A peripheral that can be attached to a `MemoryMap`. Offsets are relative to the
+/// start of the region the device is mapped at.
+pub trait Device {
+    fn read(&mut self, offset: Word) -> Byte;
+    fn write(&mut self, offset: Word, value: Byte);
+    /// Reads without side effects, for debuggers.
+    fn peek(&self, offset: Word) -> Byte;
+}
+
+/// Lets the owner keep a handle to a device after mapping it.
+impl<D: Device + ?Sized> Device for Rc<RefCell<D>> {
+    fn read(&mut self, offset: Word) -> Byte {
+        self.borrow_mut().read(offset)
+    }
+
+    fn write(&mut self, offset: Word, value: Byte) {
+        self.borrow_mut().write(offset, value);
+    }
+
+    fn peek(&self, offset: Word) -> Byte {
+        self.borrow().peek(offset)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MemoryMapError {
+    EmptyRegion { start: Word, end: Word },
+    RegionOverlaps { start: Word, end: Word },
+    RegionPastEndOfMemory { start: Word, length: usize },
+    Io(io::ErrorKind),
+}
+
+/// What happens when something writes to a ROM region, chosen per region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RomWritePolicy {
+    /// The write is dropped, like on real hardware.
+    #[default]
+    Ignore,
+    /// The write is dropped and reported as a `BusFault`, which stops the CPU.
+    Fault,
+}
+
+/// Read-only memory, writes are ignored. A region larger than the data mirrors it.
+#[derive(Debug, Clone)]
+pub struct Rom {
+    data: Vec<Byte>,
+}
+
+impl Rom {
+    pub fn new(data: &[Byte]) -> Rom {
+        Rom {
+            data: data.to_vec(),
+        }
+    }
+}
+
+impl Device for Rom {
+    fn read(&mut self, offset: Word) -> Byte {
+        self.peek(offset)
+    }
+
+    fn write(&mut self, offset: Word, value: Byte) {}
+
+    fn peek(&self, offset: Word) -> Byte {
+        match self.data.len() {
+            0 => 0,
+            length => self.data[offset as usize % length],
+        }
+    }
+}
+
+struct Region {
+    start: Word,
+    end: Word,
+    /// `None` for devices that take writes.
+    rom_write_policy: Option<RomWritePolicy>,
+    device: Box<dyn Device>,
+}
+
+/// A bus made of flat RAM with devices mapped over parts of it. Accesses inside a
+/// mapped region go to its device, everything else goes to `ram`.
+pub struct MemoryMap {
+    pub ram: Memory,
+    regions: Vec<Region>,
+    fault: Option<BusFault>,
+}
+
+impl MemoryMap {
+    pub fn new() -> MemoryMap {
+        MemoryMap {
+            ram: Memory::initialize(),
+            regions: Vec::new(),
+            fault: None,
+        }
+    }
+
+    /// Routes accesses in `range` to `device`. Regions can't overlap.
+    pub fn map(
+        &mut self,
+        range: RangeInclusive<Word>,
+        device: impl Device + 'static,
+    ) -> Result<(), MemoryMapError> {
+        self.map_region(range, None, Box::new(device))
+    }
+
+    fn map_region(
+        &mut self,
+        range: RangeInclusive<Word>,
+        rom_write_policy: Option<RomWritePolicy>,
+        device: Box<dyn Device>,
+    ) -> Result<(), MemoryMapError> {
+        let (start, end) = (*range.start(), *range.end());
+        if start > end {
+            return Err(MemoryMapError::EmptyRegion { start, end });
+        }
+        if let Some(region) = self
+            .regions
+            .iter()
+            .find(|region| start <= region.end && region.start <= end)
+        {
+            return Err(MemoryMapError::RegionOverlaps {
+                start: region.start,
+                end: region.end,
+            });
+        }
+        self.regions.push(Region {
+            start,
+            end,
+            rom_write_policy,
+            device,
+        });
+        Ok(())
+    }
+
+    /// Maps `data` as ROM starting at `start`. Writes to it follow `write_policy`.
+    pub fn map_rom(
+        &mut self,
+        start: Word,
+        data: &[Byte],
+        write_policy: RomWritePolicy,
+    ) -> Result<(), MemoryMapError> {
+        let length = data.len();
+        if length == 0 {
+            return Err(MemoryMapError::EmptyRegion { start, end: start });
+        }
+        if start as usize + length > 0x10000 {
+            return Err(MemoryMapError::RegionPastEndOfMemory { start, length });
+        }
+        let end = start + (length - 1) as Word;
+        self.map_region(start..=end, Some(write_policy), Box::new(Rom::new(data)))
+    }
+
+    /// Reads a binary image from `path` and maps it as ROM starting at `start`.
+    pub fn load_rom(
+        &mut self,
+        start: Word,
+        path: impl AsRef<Path>,
+        write_policy: RomWritePolicy,
+    ) -> Result<(), MemoryMapError> {
+        let data = std::fs::read(path).map_err(|error| MemoryMapError::Io(error.kind()))?;
+        self.map_rom(start, &data, write_policy)
+    }
+
+    fn region_index(&self, address: Word) -> Option<usize> {
+        self.regions
+            .iter()
+            .position(|region| region.start <= address && address <= region.end)
+    }
+}
+
+impl Default for MemoryMap {
+    fn default() -> Self {
+        MemoryMap::new()
+    }
+}
+
+impl Bus for MemoryMap {
+    fn read(&mut self, address: Word) -> Byte {
+        match self.region_index(address) {
+            Some(index) => {
+                let region = &mut self.regions[index];
+                region.device.read(address - region.start)
+            }
+            None => self.ram.read(address),
+        }
+    }
+
+    fn write(&mut self, address: Word, value: Byte) {
+        match self.region_index(address) {
+            Some(index) => {
+                let region = &mut self.regions[index];
+                match region.rom_write_policy {
+                    None => region.device.write(address - region.start, value),
+                    Some(RomWritePolicy::Ignore) => {}
+                    Some(RomWritePolicy::Fault) => {
+                        self.fault = Some(BusFault::WriteToRom { address, value })
+                    }
+                }
+            }
+            None => self.ram.write(address, value),
+        }
+    }
+
+    fn peek(&self, address: Word) -> Byte {
+        match self.region_index(address) {
+            Some(index) => {
+                let region = &self.regions[index];
+                region.device.peek(address - region.start)
+            }
+            None => self.ram.peek(address),
+        }
+    }
+
+    fn take_fault(&mut self) -> Option<BusFault> {
+        self.fault.take()
+    }
+}
@@ -0,0 +1,246 @@
+use std::{fmt::Write as _, io, path::Path};
+
+use crate::{
+    bus::Bus,
+    cpu::{Byte, Word, CPU},
+    instructions::InstructionsError,
+    memory::Memory,
+    save_state,
+};
+
+const HEADER: &str = "6502MOVIE 1";
+
+/// Something from outside the machine that changes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEvent {
+    /// A device register taking a value, like a key press landing in a keyboard latch.
+    Write {
+        address: Word,
+        value: Byte,
+    },
+    Irq(bool),
+    Nmi(bool),
+}
+
+impl InputEvent {
+    pub fn apply<B: Bus>(self, cpu: &mut CPU, bus: &mut B) {
+        match self {
+            InputEvent::Write { address, value } => bus.write(address, value),
+            InputEvent::Irq(asserted) => cpu.set_irq(asserted),
+            InputEvent::Nmi(asserted) => cpu.set_nmi(asserted),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MovieEvent {
+    pub cycle: u64,
+    pub event: InputEvent,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MovieError {
+    InvalidHeader,
+    /// The 1-based line of a movie file that couldn't be read.
+    InvalidLine {
+        line: usize,
+    },
+    MissingEnd,
+    ImageMismatch {
+        expected: u64,
+        found: u64,
+    },
+    /// The CPU went past the cycle an event was recorded at, so it isn't running the same
+    /// way it did while recording.
+    Desync {
+        cycle: u64,
+    },
+    FinalStateMismatch {
+        expected: u64,
+        found: u64,
+    },
+    Execution(InstructionsError),
+    Io(io::ErrorKind),
+}
+
+/// Every input event of a run with the cycle it landed on. The image hash identifies the
+/// program it was recorded with, the final hash the state it ended in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Movie {
+    pub image_hash: u64,
+    pub events: Vec<MovieEvent>,
+    pub final_cycle: u64,
+    pub final_hash: u64,
+}
+
+/// FNV-1a, stable across builds unlike the standard library hasher.
+pub fn hash_bytes(bytes: &[Byte]) -> u64 {
+    bytes.iter().fold(0xCBF29CE484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001B3)
+    })
+}
+
+/// Hashes everything a save state holds.
+pub fn state_hash(cpu: &CPU, memory: &Memory) -> u64 {
+    hash_bytes(&save_state::save(cpu, memory))
+}
+
+/// Applies input events as they happen and remembers them.
+pub struct Recorder {
+    image_hash: u64,
+    events: Vec<MovieEvent>,
+}
+
+impl Recorder {
+    pub fn new(image: &[Byte]) -> Recorder {
+        Recorder {
+            image_hash: hash_bytes(image),
+            events: Vec::new(),
+        }
+    }
+
+    /// Call between instructions, the event lands at the current cycle count.
+    pub fn record<B: Bus>(&mut self, event: InputEvent, cpu: &mut CPU, bus: &mut B) {
+        event.apply(cpu, bus);
+        self.events.push(MovieEvent {
+            cycle: cpu.total_cycles,
+            event,
+        });
+    }
+
+    pub fn finish(self, cpu: &CPU, memory: &Memory) -> Movie {
+        Movie {
+            image_hash: self.image_hash,
+            events: self.events,
+            final_cycle: cpu.total_cycles,
+            final_hash: state_hash(cpu, memory),
+        }
+    }
+}
+
+impl Movie {
+    /// Runs from the state the recording started in, feeding the events at their
+    /// cycles, and checks the run ends in the recorded state.
+    pub fn replay(
+        &self,
+        image: &[Byte],
+        cpu: &mut CPU,
+        memory: &mut Memory,
+    ) -> Result<(), MovieError> {
+        let image_hash = hash_bytes(image);
+        if image_hash != self.image_hash {
+            return Err(MovieError::ImageMismatch {
+                expected: self.image_hash,
+                found: image_hash,
+            });
+        }
+        for event in &self.events {
+            run_until(event.cycle, cpu, memory)?;
+            event.event.apply(cpu, memory);
+        }
+        run_until(self.final_cycle, cpu, memory)?;
+        let final_hash = state_hash(cpu, memory);
+        if final_hash != self.final_hash {
+            return Err(MovieError::FinalStateMismatch {
+                expected: self.final_hash,
+                found: final_hash,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\nimage {:016x}\n", HEADER, self.image_hash);
+        for MovieEvent { cycle, event } in &self.events {
+            match event {
+                InputEvent::Write { address, value } => {
+                    writeln!(text, "{} write {:04x} {:02x}", cycle, address, value)
+                }
+                InputEvent::Irq(asserted) => writeln!(text, "{} irq {}", cycle, *asserted as u8),
+                InputEvent::Nmi(asserted) => writeln!(text, "{} nmi {}", cycle, *asserted as u8),
+            }
+            .unwrap();
+        }
+        writeln!(text, "end {} {:016x}", self.final_cycle, self.final_hash).unwrap();
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Movie, MovieError> {
+        let mut lines = text.lines().enumerate();
+        if lines.next().map(|(_, line)| line.trim()) != Some(HEADER) {
+            return Err(MovieError::InvalidHeader);
+        }
+        let mut image_hash = None;
+        let mut events = Vec::new();
+        for (index, text_line) in lines {
+            let line = index + 1;
+            let invalid = MovieError::InvalidLine { line };
+            let words: Vec<&str> = text_line.split_whitespace().collect();
+            let hex64 = |word: &str| {
+                u64::from_str_radix(word, 16).map_err(|_| MovieError::InvalidLine { line })
+            };
+            let flag = |word: &str| match word {
+                "0" => Ok(false),
+                "1" => Ok(true),
+                _ => Err(MovieError::InvalidLine { line }),
+            };
+            match words[..] {
+                [] => {}
+                ["image", hash] => image_hash = Some(hex64(hash)?),
+                ["end", cycle, hash] => {
+                    return Ok(Movie {
+                        image_hash: image_hash.ok_or(invalid)?,
+                        events,
+                        final_cycle: cycle
+                            .parse()
+                            .map_err(|_| MovieError::InvalidLine { line })?,
+                        final_hash: hex64(hash)?,
+                    })
+                }
+                [cycle, kind, ref arguments @ ..] => {
+                    let cycle = cycle
+                        .parse()
+                        .map_err(|_| MovieError::InvalidLine { line })?;
+                    let event = match (kind, arguments) {
+                        ("write", [address, value]) => InputEvent::Write {
+                            address: Word::from_str_radix(address, 16)
+                                .map_err(|_| MovieError::InvalidLine { line })?,
+                            value: Byte::from_str_radix(value, 16)
+                                .map_err(|_| MovieError::InvalidLine { line })?,
+                        },
+                        ("irq", [asserted]) => InputEvent::Irq(flag(asserted)?),
+                        ("nmi", [asserted]) => InputEvent::Nmi(flag(asserted)?),
+                        _ => return Err(invalid),
+                    };
+                    events.push(MovieEvent { cycle, event });
+                }
+                _ => return Err(invalid),
+            }
+        }
+        Err(MovieError::MissingEnd)
+    }
+
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), MovieError> {
+        std::fs::write(path, self.to_text()).map_err(|error| MovieError::Io(error.kind()))
+    }
+
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Movie, MovieError> {
+        let text = std::fs::read_to_string(path).map_err(|error| MovieError::Io(error.kind()))?;
+        Movie::from_text(&text)
+    }
+}
+
+/// Steps until `cycle`, which has to be an instruction boundary of this run.
+fn run_until(cycle: u64, cpu: &mut CPU, memory: &mut Memory) -> Result<(), MovieError> {
+    while cpu.total_cycles < cycle {
+        match cpu.step(memory) {
+            Ok(Some(_)) => {}
+            Ok(None) => break,
+            Err(error) => return Err(MovieError::Execution(error)),
+        }
+    }
+    if cpu.total_cycles != cycle {
+        return Err(MovieError::Desync { cycle });
+    }
+    Ok(())
+}
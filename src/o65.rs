@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+
+use crate::{
+    bus::Bus,
+    cpu::{Byte, Word},
+    loader::{Image, Segment},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum O65Error {
+    NotO65,
+    UnsupportedVersion(Byte),
+    /// 32 bit and 65816 files are not supported.
+    UnsupportedMode(Word),
+    UnexpectedEnd,
+    UnknownSegment(Byte),
+    UnsupportedRelocationType(Byte),
+    UndefinedSymbol(String),
+    UnknownSymbolIndex(Word),
+    RelocationOutOfSegment {
+        segment: Byte,
+        offset: usize,
+    },
+    SegmentPastEndOfMemory {
+        base: Word,
+        length: Word,
+    },
+}
+
+/// Segment ids used by relocation entries and exported symbols.
+pub mod segment {
+    use crate::cpu::Byte;
+
+    pub const UNDEFINED: Byte = 0;
+    pub const ABSOLUTE: Byte = 1;
+    pub const TEXT: Byte = 2;
+    pub const DATA: Byte = 3;
+    pub const BSS: Byte = 4;
+    pub const ZERO: Byte = 5;
+}
+
+const MAGIC: [Byte; 5] = [0x01, 0x00, b'o', b'6', b'5'];
+
+const MODE_65816: Word = 0x8000;
+const MODE_PAGE_RELOCATION: Word = 0x4000;
+const MODE_32_BIT: Word = 0x2000;
+const MODE_BSS_ZERO: Word = 0x0200;
+
+const RELOCATE_WORD: Byte = 0x80;
+const RELOCATE_HIGH: Byte = 0x40;
+const RELOCATE_LOW: Byte = 0x20;
+
+/// Where each segment goes. `O65File::bases` gives the ones the file was assembled for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct O65Bases {
+    pub text: Word,
+    pub data: Word,
+    pub bss: Word,
+    pub zero: Word,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Relocation {
+    pub offset: usize,
+    pub kind: Byte,
+    pub segment: Byte,
+    /// Index into `O65File::undefined` when `segment` is `segment::UNDEFINED`.
+    pub symbol: Word,
+    /// Low byte of the full address for `HIGH` entries, needed to carry into the high byte.
+    pub low_byte: Byte,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    pub name: String,
+    pub segment: Byte,
+    pub value: Word,
+}
+
+/// A parsed o65 object, still at the addresses it was assembled for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct O65File {
+    pub mode: Word,
+    pub bases: O65Bases,
+    pub text: Vec<Byte>,
+    pub data: Vec<Byte>,
+    pub bss_length: Word,
+    pub zero_length: Word,
+    pub stack_size: Word,
+    pub options: Vec<(Byte, Vec<Byte>)>,
+    pub undefined: Vec<String>,
+    pub text_relocations: Vec<Relocation>,
+    pub data_relocations: Vec<Relocation>,
+    pub exports: Vec<Export>,
+}
+
+/// A module moved to its load addresses, ready to be written to a bus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelocatedO65 {
+    pub image: Image,
+    pub symbols: HashMap<String, Word>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [Byte],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<Byte, O65Error> {
+        let byte = *self
+            .bytes
+            .get(self.position)
+            .ok_or(O65Error::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn word(&mut self) -> Result<Word, O65Error> {
+        Ok(Word::from_le_bytes([self.byte()?, self.byte()?]))
+    }
+
+    fn slice(&mut self, length: usize) -> Result<&'a [Byte], O65Error> {
+        let end = self.position + length;
+        let slice = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(O65Error::UnexpectedEnd)?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn name(&mut self) -> Result<String, O65Error> {
+        let length = self.bytes[self.position..]
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or(O65Error::UnexpectedEnd)?;
+        let name = String::from_utf8_lossy(self.slice(length)?).into_owned();
+        self.position += 1;
+        Ok(name)
+    }
+
+    fn relocations(&mut self, mode: Word) -> Result<Vec<Relocation>, O65Error> {
+        let mut relocations = Vec::new();
+        let mut offset: isize = -1;
+        loop {
+            let mut step = self.byte()?;
+            while step == 255 {
+                offset += 254;
+                step = self.byte()?;
+            }
+            if step == 0 {
+                return Ok(relocations);
+            }
+            offset += step as isize;
+            let type_byte = self.byte()?;
+            let kind = type_byte & 0xE0;
+            let segment = type_byte & 0x0F;
+            if !matches!(kind, RELOCATE_WORD | RELOCATE_HIGH | RELOCATE_LOW) {
+                return Err(O65Error::UnsupportedRelocationType(type_byte));
+            }
+            let symbol = match segment {
+                segment::UNDEFINED => self.word()?,
+                _ => 0,
+            };
+            let low_byte = match kind {
+                RELOCATE_HIGH if mode & MODE_PAGE_RELOCATION == 0 => self.byte()?,
+                _ => 0,
+            };
+            relocations.push(Relocation {
+                offset: offset as usize,
+                kind,
+                segment,
+                symbol,
+                low_byte,
+            });
+        }
+    }
+}
+
+/// Parses an o65 file. Only the first file of a chain is read.
+pub fn parse_o65(bytes: &[Byte]) -> Result<O65File, O65Error> {
+    let mut reader = Reader { bytes, position: 0 };
+    if reader.slice(MAGIC.len()).map_err(|_| O65Error::NotO65)? != MAGIC {
+        return Err(O65Error::NotO65);
+    }
+    let version = reader.byte()?;
+    if version != 0 {
+        return Err(O65Error::UnsupportedVersion(version));
+    }
+    let mode = reader.word()?;
+    if mode & (MODE_65816 | MODE_32_BIT) != 0 {
+        return Err(O65Error::UnsupportedMode(mode));
+    }
+    let text_base = reader.word()?;
+    let text_length = reader.word()?;
+    let data_base = reader.word()?;
+    let data_length = reader.word()?;
+    let bss_base = reader.word()?;
+    let bss_length = reader.word()?;
+    let zero_base = reader.word()?;
+    let zero_length = reader.word()?;
+    let stack_size = reader.word()?;
+
+    let mut options = Vec::new();
+    loop {
+        let length = reader.byte()?;
+        if length == 0 {
+            break;
+        }
+        let option_type = reader.byte()?;
+        let data = reader.slice((length as usize).saturating_sub(2))?;
+        options.push((option_type, data.to_vec()));
+    }
+
+    let text = reader.slice(text_length as usize)?.to_vec();
+    let data = reader.slice(data_length as usize)?.to_vec();
+
+    let undefined_count = reader.word()?;
+    let undefined = (0..undefined_count)
+        .map(|_| reader.name())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let text_relocations = reader.relocations(mode)?;
+    let data_relocations = reader.relocations(mode)?;
+
+    let export_count = reader.word()?;
+    let mut exports = Vec::new();
+    for _ in 0..export_count {
+        exports.push(Export {
+            name: reader.name()?,
+            segment: reader.byte()?,
+            value: reader.word()?,
+        });
+    }
+
+    Ok(O65File {
+        mode,
+        bases: O65Bases {
+            text: text_base,
+            data: data_base,
+            bss: bss_base,
+            zero: zero_base,
+        },
+        text,
+        data,
+        bss_length,
+        zero_length,
+        stack_size,
+        options,
+        undefined,
+        text_relocations,
+        data_relocations,
+        exports,
+    })
+}
+
+impl O65File {
+    /// Moves the segments to `bases`. Undefined references are looked up in `imports`.
+    /// When the file asks for it the bss segment is included as zeros.
+    pub fn relocate(
+        &self,
+        bases: O65Bases,
+        imports: &HashMap<String, Word>,
+    ) -> Result<RelocatedO65, O65Error> {
+        let mut text = self.text.clone();
+        let mut data = self.data.clone();
+        self.apply(
+            &mut text,
+            segment::TEXT,
+            &self.text_relocations,
+            bases,
+            imports,
+        )?;
+        self.apply(
+            &mut data,
+            segment::DATA,
+            &self.data_relocations,
+            bases,
+            imports,
+        )?;
+
+        let mut image = Image::default();
+        let mut bss = Vec::new();
+        if self.mode & MODE_BSS_ZERO != 0 {
+            bss = vec![0; self.bss_length as usize];
+        }
+        for (base, contents) in [(bases.text, text), (bases.data, data), (bases.bss, bss)] {
+            if contents.is_empty() {
+                continue;
+            }
+            if base as usize + contents.len() > 0x10000 {
+                return Err(O65Error::SegmentPastEndOfMemory {
+                    base,
+                    length: contents.len() as Word,
+                });
+            }
+            image.segments.push(Segment {
+                address: base,
+                data: contents,
+            });
+        }
+
+        let mut symbols = HashMap::new();
+        for export in &self.exports {
+            let delta = self.delta(export.segment, 0, bases, imports)?;
+            symbols.insert(export.name.clone(), export.value.wrapping_add(delta));
+        }
+        Ok(RelocatedO65 { image, symbols })
+    }
+
+    /// Relocates the module, writes it to `bus` and returns the exported symbols.
+    pub fn load<B: Bus>(
+        &self,
+        bases: O65Bases,
+        imports: &HashMap<String, Word>,
+        bus: &mut B,
+    ) -> Result<HashMap<String, Word>, O65Error> {
+        let relocated = self.relocate(bases, imports)?;
+        relocated.image.load_into(bus);
+        Ok(relocated.symbols)
+    }
+
+    fn delta(
+        &self,
+        segment: Byte,
+        symbol: Word,
+        bases: O65Bases,
+        imports: &HashMap<String, Word>,
+    ) -> Result<Word, O65Error> {
+        let (new, old) = match segment {
+            segment::UNDEFINED => {
+                let name = self
+                    .undefined
+                    .get(symbol as usize)
+                    .ok_or(O65Error::UnknownSymbolIndex(symbol))?;
+                let address = imports
+                    .get(name)
+                    .ok_or_else(|| O65Error::UndefinedSymbol(name.clone()))?;
+                (*address, 0)
+            }
+            segment::ABSOLUTE => (0, 0),
+            segment::TEXT => (bases.text, self.bases.text),
+            segment::DATA => (bases.data, self.bases.data),
+            segment::BSS => (bases.bss, self.bases.bss),
+            segment::ZERO => (bases.zero, self.bases.zero),
+            _ => return Err(O65Error::UnknownSegment(segment)),
+        };
+        Ok(new.wrapping_sub(old))
+    }
+
+    fn apply(
+        &self,
+        contents: &mut [Byte],
+        segment: Byte,
+        relocations: &[Relocation],
+        bases: O65Bases,
+        imports: &HashMap<String, Word>,
+    ) -> Result<(), O65Error> {
+        for relocation in relocations {
+            let delta = self.delta(relocation.segment, relocation.symbol, bases, imports)?;
+            let offset = relocation.offset;
+            let width = if relocation.kind == RELOCATE_WORD {
+                2
+            } else {
+                1
+            };
+            if offset + width > contents.len() {
+                return Err(O65Error::RelocationOutOfSegment { segment, offset });
+            }
+            match relocation.kind {
+                RELOCATE_WORD => {
+                    let value = Word::from_le_bytes([contents[offset], contents[offset + 1]]);
+                    let bytes = value.wrapping_add(delta).to_le_bytes();
+                    contents[offset..offset + 2].copy_from_slice(&bytes);
+                }
+                RELOCATE_HIGH => {
+                    let value = Word::from_le_bytes([relocation.low_byte, contents[offset]]);
+                    contents[offset] = (value.wrapping_add(delta) >> 8) as Byte;
+                }
+                _ => contents[offset] = contents[offset].wrapping_add(delta as Byte),
+            }
+        }
+        Ok(())
+    }
+}
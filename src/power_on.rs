@@ -0,0 +1,45 @@
+use crate::cpu::Byte;
+
+/// What RAM or registers hold at power on. Real hardware powers up with garbage, the
+/// non zero patterns help find programs that read memory they never wrote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PowerOnPattern {
+    #[default]
+    Zero,
+    Fixed(Byte),
+    /// $00 and $FF in turn. RAM alternates per 256 byte page, registers per register.
+    AlternatingPages,
+    /// Pseudo-random bytes, the same seed always gives the same contents.
+    Random(u64),
+}
+
+impl PowerOnPattern {
+    /// Fills `data`, alternating patterns switch every `page_size` bytes.
+    pub(crate) fn fill(self, data: &mut [Byte], page_size: usize) {
+        match self {
+            PowerOnPattern::Zero => data.fill(0),
+            PowerOnPattern::Fixed(value) => data.fill(value),
+            PowerOnPattern::AlternatingPages => {
+                for (page, chunk) in data.chunks_mut(page_size).enumerate() {
+                    chunk.fill(if page % 2 == 0 { 0x00 } else { 0xFF });
+                }
+            }
+            PowerOnPattern::Random(seed) => {
+                let mut state = seed;
+                for chunk in data.chunks_mut(8) {
+                    let bytes = split_mix(&mut state).to_le_bytes();
+                    chunk.copy_from_slice(&bytes[..chunk.len()]);
+                }
+            }
+        }
+    }
+}
+
+/// SplitMix64, small and good enough for garbage that has to be reproducible.
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
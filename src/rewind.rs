@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+
+use crate::{
+    cpu::{Byte, Word, CPU},
+    instructions::InstructionsError,
+    memory::Memory,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RewindError {
+    /// Nothing recorded goes back far enough.
+    NoSnapshot,
+    /// Replaying from the snapshot failed.
+    Replay(InstructionsError),
+    /// The CPU stopped or started waiting for an interrupt before reaching the target.
+    Halted,
+}
+
+/// Bytes starting at `address` that differ from the next snapshot.
+#[derive(Debug, Clone)]
+struct DeltaRun {
+    address: Word,
+    bytes: Vec<Byte>,
+}
+
+#[derive(Debug, Clone)]
+struct Snapshot {
+    cpu: CPU,
+    /// Turns the memory of the next newer snapshot into this one's. Empty for the newest.
+    delta: Vec<DeltaRun>,
+}
+
+/// A bounded history of CPU and memory snapshots for stepping backwards.
+///
+/// Only the newest snapshot keeps a full copy of memory, older ones store the runs of
+/// bytes that differ from the snapshot after them, so going back a little is cheap and
+/// the oldest snapshot can be dropped without touching the others.
+pub struct Rewind {
+    interval: u64,
+    capacity: usize,
+    snapshots: VecDeque<Snapshot>,
+    newest_memory: Box<Memory>,
+    next_snapshot_at: u64,
+}
+
+impl Rewind {
+    /// Keeps up to `capacity` snapshots taken at least `interval` cycles apart.
+    pub fn new(interval: u64, capacity: usize) -> Rewind {
+        Rewind {
+            interval: interval.max(1),
+            capacity: capacity.max(1),
+            snapshots: VecDeque::new(),
+            newest_memory: Box::new(Memory::initialize()),
+            next_snapshot_at: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// The cycle count of the oldest state that can be restored.
+    pub fn oldest_cycle(&self) -> Option<u64> {
+        self.snapshots
+            .front()
+            .map(|snapshot| snapshot.cpu.total_cycles)
+    }
+
+    /// Call between runs, takes a snapshot once `interval` cycles have passed since
+    /// the last one.
+    pub fn record(&mut self, cpu: &CPU, memory: &Memory) {
+        if cpu.total_cycles >= self.next_snapshot_at {
+            self.take_snapshot(cpu, memory);
+        }
+    }
+
+    /// Takes a snapshot now, whatever the interval says. Call it after input from outside
+    /// changes the machine, replaying from an older snapshot only re-runs the CPU.
+    pub fn take_snapshot(&mut self, cpu: &CPU, memory: &Memory) {
+        if let Some(newest) = self.snapshots.back_mut() {
+            newest.delta = diff(&self.newest_memory, memory);
+        }
+        self.newest_memory[0..0x10000].copy_from_slice(&memory[0..0x10000]);
+        self.snapshots.push_back(Snapshot {
+            cpu: *cpu,
+            delta: Vec::new(),
+        });
+        if self.snapshots.len() > self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.next_snapshot_at = cpu.total_cycles + self.interval;
+    }
+
+    /// Goes back to the last instruction boundary at or before `cycle`.
+    pub fn rewind_to_cycle(
+        &mut self,
+        cycle: u64,
+        cpu: &mut CPU,
+        memory: &mut Memory,
+    ) -> Result<(), RewindError> {
+        let index = self
+            .snapshots
+            .iter()
+            .rposition(|snapshot| snapshot.cpu.total_cycles <= cycle)
+            .ok_or(RewindError::NoSnapshot)?;
+        self.restore(index, cpu, memory);
+        // Dry run to learn which instruction boundary is the last one before `cycle`, on a
+        // copy that keeps the ROM ranges so it runs like the real thing
+        let mut target = cpu.instructions_retired;
+        let (mut scratch_cpu, mut scratch_memory) = (*cpu, Box::new(memory.clone()));
+        loop {
+            match scratch_cpu.step(scratch_memory.as_mut()) {
+                Ok(Some(_)) if scratch_cpu.total_cycles <= cycle => {
+                    target = scratch_cpu.instructions_retired
+                }
+                _ => break,
+            }
+        }
+        self.replay(target, cpu, memory)
+    }
+
+    /// Goes back to the state right before the last instruction ran.
+    pub fn step_back(&mut self, cpu: &mut CPU, memory: &mut Memory) -> Result<(), RewindError> {
+        let target = cpu
+            .instructions_retired
+            .checked_sub(1)
+            .ok_or(RewindError::NoSnapshot)?;
+        let index = self
+            .snapshots
+            .iter()
+            .rposition(|snapshot| snapshot.cpu.instructions_retired <= target)
+            .ok_or(RewindError::NoSnapshot)?;
+        self.restore(index, cpu, memory);
+        self.replay(target, cpu, memory)
+    }
+
+    /// Restores snapshot `index` and forgets the newer ones, the run continues from
+    /// there.
+    fn restore(&mut self, index: usize, cpu: &mut CPU, memory: &mut Memory) {
+        for snapshot in self.snapshots.range_mut(index..).rev() {
+            for run in snapshot.delta.drain(..) {
+                let start = run.address as usize;
+                self.newest_memory[start..start + run.bytes.len()].copy_from_slice(&run.bytes);
+            }
+        }
+        self.snapshots.truncate(index + 1);
+        *cpu = self.snapshots[index].cpu;
+        memory[0..0x10000].copy_from_slice(&self.newest_memory[0..0x10000]);
+        self.next_snapshot_at = cpu.total_cycles + self.interval;
+    }
+
+    fn replay(&self, target: u64, cpu: &mut CPU, memory: &mut Memory) -> Result<(), RewindError> {
+        while cpu.instructions_retired < target {
+            match cpu.step(memory) {
+                Ok(Some(_)) => {}
+                Ok(None) => return Err(RewindError::Halted),
+                Err(error) => return Err(RewindError::Replay(error)),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The runs of `old` that differ from `new`.
+fn diff(old: &Memory, new: &Memory) -> Vec<DeltaRun> {
+    let (old, new) = (&old[0..0x10000], &new[0..0x10000]);
+    let mut runs: Vec<DeltaRun> = Vec::new();
+    for (address, (old_byte, new_byte)) in old.iter().zip(new).enumerate() {
+        if old_byte == new_byte {
+            continue;
+        }
+        match runs.last_mut() {
+            Some(run) if run.address as usize + run.bytes.len() == address => {
+                run.bytes.push(*old_byte)
+            }
+            _ => runs.push(DeltaRun {
+                address: address as Word,
+                bytes: vec![*old_byte],
+            }),
+        }
+    }
+    runs
+}
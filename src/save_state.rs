@@ -0,0 +1,199 @@
+use std::{io, path::Path};
+
+use sdl2::pixels::Color;
+
+use crate::{
+    cpu::{Byte, CpuVariant, ProcessorFlags, Word, CPU},
+    graphics_adapter::GraphicsAdapter,
+    memory::Memory,
+};
+
+const MAGIC: [Byte; 8] = *b"6502SAVE";
+
+/// Bump when the layout changes, and keep reading the old layouts in `load`.
+pub const VERSION: u16 = 1;
+
+const IRQ_LINE_BIT: Byte = 0b00000001;
+const NMI_LINE_BIT: Byte = 0b00000010;
+const NMI_PENDING_BIT: Byte = 0b00000100;
+const UNDOCUMENTED_OPCODES_BIT: Byte = 0b00001000;
+const WAITING_FOR_INTERRUPT_BIT: Byte = 0b00010000;
+const STOPPED_BIT: Byte = 0b00100000;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SaveStateError {
+    NotASaveState,
+    /// Saved by a newer build than this one.
+    UnsupportedVersion(u16),
+    Truncated,
+    TrailingData,
+    InvalidValue {
+        field: &'static str,
+        value: Byte,
+    },
+    Io(io::ErrorKind),
+}
+
+/// Serializes the CPU, including its graphics adapter, and all of memory.
+///
+/// Layout, little endian: magic, version, registers and flags, interrupt and run state,
+/// variant, cycle debt, total cycles, instructions retired, graphics adapter (a present
+/// byte, then the clear color and 16x16 pixels as RGBA), then 64 KiB of memory.
+pub fn save(cpu: &CPU, memory: &Memory) -> Vec<Byte> {
+    let mut bytes = Vec::with_capacity(0x10000 + 1100);
+    bytes.extend(MAGIC);
+    bytes.extend(VERSION.to_le_bytes());
+
+    bytes.extend(cpu.program_counter.to_le_bytes());
+    bytes.extend([
+        cpu.stack_pointer,
+        cpu.a_register,
+        cpu.x_register,
+        cpu.y_register,
+        cpu.status.into_u8(),
+    ]);
+    let mut state = 0;
+    for (set, bit) in [
+        (cpu.irq_line, IRQ_LINE_BIT),
+        (cpu.nmi_line, NMI_LINE_BIT),
+        (cpu.nmi_pending, NMI_PENDING_BIT),
+        (cpu.undocumented_opcodes, UNDOCUMENTED_OPCODES_BIT),
+        (cpu.waiting_for_interrupt, WAITING_FOR_INTERRUPT_BIT),
+        (cpu.stopped, STOPPED_BIT),
+    ] {
+        if set {
+            state |= bit;
+        }
+    }
+    bytes.push(state);
+    bytes.push(match cpu.variant {
+        CpuVariant::Nmos6502 => 0,
+        CpuVariant::Cmos65C02 => 1,
+    });
+    bytes.extend(cpu.cycle_debt.to_le_bytes());
+    bytes.extend(cpu.total_cycles.to_le_bytes());
+    bytes.extend(cpu.instructions_retired.to_le_bytes());
+
+    match cpu.get_graphics() {
+        Some(graphics) => {
+            bytes.push(1);
+            let colors = std::iter::once(graphics.get_clear_color())
+                .chain(graphics.get_pixels().iter().flatten().copied());
+            for color in colors {
+                bytes.extend([color.r, color.g, color.b, color.a]);
+            }
+        }
+        None => bytes.push(0),
+    }
+
+    bytes.extend_from_slice(&memory[0..0x10000]);
+    bytes
+}
+
+/// Restores a state written by `save`, versions this build doesn't know are rejected.
+pub fn load(bytes: &[Byte]) -> Result<(CPU, Memory), SaveStateError> {
+    let mut reader = Reader { bytes, position: 0 };
+    if reader.slice(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+        return Err(SaveStateError::NotASaveState);
+    }
+    let version = u16::from_le_bytes(reader.array()?);
+    if version == 0 || version > VERSION {
+        return Err(SaveStateError::UnsupportedVersion(version));
+    }
+
+    let mut cpu = CPU::new();
+    cpu.program_counter = Word::from_le_bytes(reader.array()?);
+    cpu.stack_pointer = reader.byte()?;
+    cpu.a_register = reader.byte()?;
+    cpu.x_register = reader.byte()?;
+    cpu.y_register = reader.byte()?;
+    cpu.status = ProcessorFlags::from(reader.byte()?);
+    let state = reader.byte()?;
+    cpu.irq_line = state & IRQ_LINE_BIT != 0;
+    cpu.nmi_line = state & NMI_LINE_BIT != 0;
+    cpu.nmi_pending = state & NMI_PENDING_BIT != 0;
+    cpu.undocumented_opcodes = state & UNDOCUMENTED_OPCODES_BIT != 0;
+    cpu.waiting_for_interrupt = state & WAITING_FOR_INTERRUPT_BIT != 0;
+    cpu.stopped = state & STOPPED_BIT != 0;
+    cpu.variant = match reader.byte()? {
+        0 => CpuVariant::Nmos6502,
+        1 => CpuVariant::Cmos65C02,
+        value => {
+            return Err(SaveStateError::InvalidValue {
+                field: "variant",
+                value,
+            })
+        }
+    };
+    cpu.cycle_debt = i32::from_le_bytes(reader.array()?);
+    cpu.total_cycles = u64::from_le_bytes(reader.array()?);
+    cpu.instructions_retired = u64::from_le_bytes(reader.array()?);
+
+    cpu.graphics_adapter = match reader.byte()? {
+        0 => None,
+        1 => {
+            let clear_color = reader.color()?;
+            let mut pixels = [[clear_color; 16]; 16];
+            for pixel in pixels.iter_mut().flatten() {
+                *pixel = reader.color()?;
+            }
+            Some(GraphicsAdapter::from_parts(pixels, clear_color))
+        }
+        value => {
+            return Err(SaveStateError::InvalidValue {
+                field: "graphics adapter",
+                value,
+            })
+        }
+    };
+
+    let mut memory = Memory::initialize();
+    memory[0..0x10000].copy_from_slice(reader.slice(0x10000)?);
+    if reader.position != bytes.len() {
+        return Err(SaveStateError::TrailingData);
+    }
+    Ok((cpu, memory))
+}
+
+pub fn save_to_file(
+    path: impl AsRef<Path>,
+    cpu: &CPU,
+    memory: &Memory,
+) -> Result<(), SaveStateError> {
+    std::fs::write(path, save(cpu, memory)).map_err(|error| SaveStateError::Io(error.kind()))
+}
+
+pub fn load_from_file(path: impl AsRef<Path>) -> Result<(CPU, Memory), SaveStateError> {
+    let bytes = std::fs::read(path).map_err(|error| SaveStateError::Io(error.kind()))?;
+    load(&bytes)
+}
+
+struct Reader<'a> {
+    bytes: &'a [Byte],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn slice(&mut self, length: usize) -> Result<&'a [Byte], SaveStateError> {
+        let end = self.position + length;
+        let slice = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(SaveStateError::Truncated)?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[Byte; N], SaveStateError> {
+        Ok(self.slice(N)?.try_into().unwrap())
+    }
+
+    fn byte(&mut self) -> Result<Byte, SaveStateError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn color(&mut self) -> Result<Color, SaveStateError> {
+        let [r, g, b, a] = self.array()?;
+        Ok(Color { r, g, b, a })
+    }
+}
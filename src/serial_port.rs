@@ -0,0 +1,70 @@
+use std::collections::VecDeque;
+
+use crate::{
+    cpu::{Byte, Word},
+    memory_map::Device,
+};
+
+/// Example memory mapped device, a minimal serial port with two registers.
+///
+/// - `DATA` (offset 0): reading takes the next received byte, writing transmits a byte.
+/// - `STATUS` (offset 1): bit 0 is set while received bytes are waiting. Bit 7 is set
+///   when a byte has been transmitted and is cleared by reading the status register.
+#[derive(Debug, Default)]
+pub struct SerialPort {
+    pub received: VecDeque<Byte>,
+    pub transmitted: Vec<Byte>,
+    transmit_done: bool,
+}
+
+impl SerialPort {
+    pub const DATA: Word = 0;
+    pub const STATUS: Word = 1;
+
+    pub const RECEIVE_READY_BIT: Byte = 0b00000001;
+    pub const TRANSMIT_DONE_BIT: Byte = 0b10000000;
+
+    pub fn new() -> SerialPort {
+        SerialPort::default()
+    }
+
+    fn status(&self) -> Byte {
+        let mut status = 0;
+        if !self.received.is_empty() {
+            status |= SerialPort::RECEIVE_READY_BIT;
+        }
+        if self.transmit_done {
+            status |= SerialPort::TRANSMIT_DONE_BIT;
+        }
+        status
+    }
+}
+
+impl Device for SerialPort {
+    fn read(&mut self, offset: Word) -> Byte {
+        match offset {
+            SerialPort::DATA => self.received.pop_front().unwrap_or(0),
+            SerialPort::STATUS => {
+                let status = self.status();
+                self.transmit_done = false;
+                status
+            }
+            _ => 0,
+        }
+    }
+
+    fn write(&mut self, offset: Word, value: Byte) {
+        if offset == SerialPort::DATA {
+            self.transmitted.push(value);
+            self.transmit_done = true;
+        }
+    }
+
+    fn peek(&self, offset: Word) -> Byte {
+        match offset {
+            SerialPort::DATA => self.received.front().copied().unwrap_or(0),
+            SerialPort::STATUS => self.status(),
+            _ => 0,
+        }
+    }
+}
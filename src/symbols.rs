@@ -0,0 +1,248 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::cpu::Word;
+
+/// Errors carry the 1-based line they were found on.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SymbolError {
+    InvalidLine { line: usize },
+    InvalidNumber { line: usize },
+    MissingField { line: usize, field: &'static str },
+    UnknownId { line: usize, id: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceLocation<'a> {
+    pub file: &'a str,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone)]
+struct LineRange {
+    start: Word,
+    end: Word,
+    file: usize,
+    line: usize,
+}
+
+/// Names for addresses and source lines for code, loaded from ld65 debug info or VICE
+/// label files. Several names can share an address, the first one added is preferred.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    by_name: HashMap<String, Word>,
+    by_address: BTreeMap<Word, Vec<String>>,
+    files: Vec<String>,
+    lines: Vec<LineRange>,
+}
+
+impl SymbolTable {
+    pub fn new() -> SymbolTable {
+        SymbolTable::default()
+    }
+
+    pub fn insert(&mut self, name: &str, address: Word) {
+        if let Some(old_address) = self.by_name.insert(name.to_string(), address) {
+            if let Some(names) = self.by_address.get_mut(&old_address) {
+                names.retain(|old_name| old_name != name);
+            }
+        }
+        self.by_address
+            .entry(address)
+            .or_default()
+            .push(name.to_string());
+    }
+
+    pub fn address_of(&self, name: &str) -> Option<Word> {
+        self.by_name.get(name).copied()
+    }
+
+    pub fn name_at(&self, address: Word) -> Option<&str> {
+        self.by_address
+            .get(&address)
+            .and_then(|names| names.first())
+            .map(String::as_str)
+    }
+
+    /// The closest symbol at or below `address` and how far past it `address` is.
+    pub fn nearest(&self, address: Word) -> Option<(&str, Word)> {
+        self.by_address
+            .range(..=address)
+            .rev()
+            .find_map(|(symbol_address, names)| {
+                names
+                    .first()
+                    .map(|name| (name.as_str(), address - symbol_address))
+            })
+    }
+
+    /// `name`, `name+3` or `$1234` when nothing is close enough.
+    pub fn describe(&self, address: Word, max_offset: Word) -> String {
+        match self.nearest(address) {
+            Some((name, 0)) => name.to_string(),
+            Some((name, offset)) if offset <= max_offset => format!("{}+{}", name, offset),
+            _ => format!("${:04X}", address),
+        }
+    }
+
+    /// The source line whose code covers `address`, the narrowest one if several do.
+    pub fn source_line(&self, address: Word) -> Option<SourceLocation<'_>> {
+        self.lines
+            .iter()
+            .filter(|range| range.start <= address && address <= range.end)
+            .min_by_key(|range| range.end - range.start)
+            .map(|range| SourceLocation {
+                file: &self.files[range.file],
+                line: range.line,
+            })
+    }
+
+    /// Reads VICE monitor labels, `al C:1000 .start`. Other monitor commands are
+    /// skipped.
+    pub fn load_vice_labels(&mut self, text: &str) -> Result<(), SymbolError> {
+        for (index, text_line) in text.lines().enumerate() {
+            let line = index + 1;
+            let mut words = text_line.split_whitespace();
+            if words.next() != Some("al") {
+                continue;
+            }
+            let (Some(address), Some(name)) = (words.next(), words.next()) else {
+                return Err(SymbolError::InvalidLine { line });
+            };
+            let address = address.strip_prefix("C:").unwrap_or(address);
+            let address = Word::from_str_radix(address, 16)
+                .map_err(|_| SymbolError::InvalidNumber { line })?;
+            self.insert(name.strip_prefix('.').unwrap_or(name), address);
+        }
+        Ok(())
+    }
+
+    /// Reads the output of `ld65 --dbginfo`. Labels become symbols and the spans of
+    /// `line` records become source lines. Everything else is skipped.
+    pub fn load_ld65_dbginfo(&mut self, text: &str) -> Result<(), SymbolError> {
+        let mut files = HashMap::new();
+        let mut segments = HashMap::new();
+        let mut spans = HashMap::new();
+        let mut line_records = Vec::new();
+
+        for (index, text_line) in text.lines().enumerate() {
+            let line = index + 1;
+            let Some((kind, attributes)) = text_line.split_once(char::is_whitespace) else {
+                continue;
+            };
+            let attributes = parse_attributes(line, attributes.trim())?;
+            let field = |name: &'static str| {
+                attributes
+                    .get(name)
+                    .map(String::as_str)
+                    .ok_or(SymbolError::MissingField { line, field: name })
+            };
+            let number = |name: &'static str| parse_number(line, field(name)?);
+            match kind {
+                "file" => {
+                    files.insert(number("id")?, field("name")?.to_string());
+                }
+                "seg" => {
+                    segments.insert(number("id")?, number("start")?);
+                }
+                "span" => {
+                    spans.insert(
+                        number("id")?,
+                        (number("seg")?, number("start")?, number("size")?),
+                    );
+                }
+                "line" => {
+                    if let Ok(span_list) = field("span") {
+                        line_records.push((
+                            line,
+                            number("file")?,
+                            number("line")?,
+                            span_list.to_string(),
+                        ));
+                    }
+                }
+                "sym" if attributes.get("type").map(String::as_str) == Some("lab") => {
+                    let address = number("val")?;
+                    let address =
+                        Word::try_from(address).map_err(|_| SymbolError::InvalidNumber { line })?;
+                    self.insert(field("name")?, address);
+                }
+                _ => {}
+            }
+        }
+
+        let mut file_indexes = HashMap::new();
+        for (line, file, source_line, span_list) in line_records {
+            let file_name = files
+                .get(&file)
+                .ok_or(SymbolError::UnknownId { line, id: file })?;
+            let file_index = *file_indexes.entry(file).or_insert_with(|| {
+                self.files.push(file_name.clone());
+                self.files.len() - 1
+            });
+            for span in span_list.split('+') {
+                let span = parse_number(line, span)?;
+                let (segment, start, size) = *spans
+                    .get(&span)
+                    .ok_or(SymbolError::UnknownId { line, id: span })?;
+                let segment_start = *segments
+                    .get(&segment)
+                    .ok_or(SymbolError::UnknownId { line, id: segment })?;
+                if size == 0 {
+                    continue;
+                }
+                let start = segment_start + start;
+                self.lines.push(LineRange {
+                    start: start as Word,
+                    end: (start + size - 1) as Word,
+                    file: file_index,
+                    line: source_line,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_number(line: usize, text: &str) -> Result<usize, SymbolError> {
+    let result = match text.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => text.parse(),
+    };
+    result.map_err(|_| SymbolError::InvalidNumber { line })
+}
+
+/// Splits `key=value,key="quoted, value"` into a map, removing the quotes.
+fn parse_attributes(line: usize, text: &str) -> Result<HashMap<&str, String>, SymbolError> {
+    let mut attributes = HashMap::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let (key, after_key) = rest
+            .split_once('=')
+            .ok_or(SymbolError::InvalidLine { line })?;
+        let (value, after_value) = match after_key.strip_prefix('"') {
+            Some(quoted) => {
+                let mut value = String::new();
+                let mut characters = quoted.char_indices();
+                let end = loop {
+                    match characters.next() {
+                        Some((index, '"')) => break index,
+                        Some((_, '\\')) => match characters.next() {
+                            Some((_, escaped)) => value.push(escaped),
+                            None => return Err(SymbolError::InvalidLine { line }),
+                        },
+                        Some((_, character)) => value.push(character),
+                        None => return Err(SymbolError::InvalidLine { line }),
+                    }
+                };
+                (value, &quoted[end + 1..])
+            }
+            None => {
+                let end = after_key.find(',').unwrap_or(after_key.len());
+                (after_key[..end].to_string(), &after_key[end..])
+            }
+        };
+        attributes.insert(key, value);
+        rest = after_value.strip_prefix(',').unwrap_or(after_value);
+    }
+    Ok(attributes)
+}
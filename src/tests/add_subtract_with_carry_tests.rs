@@ -26,7 +26,7 @@ struct AdcTestData {
 }
 
 fn test_im(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -58,7 +58,7 @@ fn test_im(data: AdcTestData, add: bool) {
 }
 
 fn test_zp(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -91,7 +91,7 @@ fn test_zp(data: AdcTestData, add: bool) {
 }
 
 fn test_zp_x(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -125,7 +125,7 @@ fn test_zp_x(data: AdcTestData, add: bool) {
 }
 
 fn test_abs(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -159,7 +159,7 @@ fn test_abs(data: AdcTestData, add: bool) {
 }
 
 fn test_abs_x(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -194,7 +194,7 @@ fn test_abs_x(data: AdcTestData, add: bool) {
 }
 
 fn test_abs_x_page_cross(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -229,7 +229,7 @@ fn test_abs_x_page_cross(data: AdcTestData, add: bool) {
 }
 
 fn test_abs_y(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -264,7 +264,7 @@ fn test_abs_y(data: AdcTestData, add: bool) {
 }
 
 fn test_abs_y_page_cross(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -299,7 +299,7 @@ fn test_abs_y_page_cross(data: AdcTestData, add: bool) {
 }
 
 fn test_ind_x(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -335,7 +335,7 @@ fn test_ind_x(data: AdcTestData, add: bool) {
 }
 
 fn test_ind_y(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -371,7 +371,7 @@ fn test_ind_y(data: AdcTestData, add: bool) {
 }
 
 fn test_ind_y_page_cross(data: AdcTestData, add: bool) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = data.carry_before;
@@ -406,6 +406,39 @@ fn test_ind_y_page_cross(data: AdcTestData, add: bool) {
     verify_unmodified_flags(&cpu, &cpu_copy);
 }
 
+fn test_decimal_im(data: AdcTestData, add: bool) {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+
+    cpu.status.decimal_mode = true;
+    cpu.status.carry = data.carry_before;
+    cpu.status.zero = !data.expect_z;
+    cpu.status.negative = !data.expect_n;
+    cpu.status.overflow = !data.expect_v;
+
+    cpu.a_register = data.lhs;
+
+    let cpu_copy = cpu;
+
+    memory[0xFF00] = if add {
+        Instruction::InsAdcIm
+    } else {
+        Instruction::InsSbcIm
+    } as Byte;
+    memory[0xFF01] = data.rhs;
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.a_register, data.expected_answer);
+    assert_eq!(cpu.status.carry, data.expect_c);
+    assert_eq!(cpu.status.zero, data.expect_z);
+    assert_eq!(cpu.status.negative, data.expect_n);
+    assert_eq!(cpu.status.overflow, data.expect_v);
+
+    verify_unmodified_flags(&cpu, &cpu_copy);
+}
+
 // ADC
 
 // Immediate
@@ -1467,3 +1500,134 @@ fn sbc_indirect_y_can_subtract_positive_and_negative_numbers_when_crossing_page(
         false,
     );
 }
+
+// Decimal mode
+#[test]
+fn adc_decimal_mode_can_add_two_bcd_numbers() {
+    test_decimal_im(
+        AdcTestData {
+            carry_before: false,
+            lhs: 0x09,
+            rhs: 0x01,
+            expected_answer: 0x10,
+            expect_c: false,
+            expect_z: false,
+            expect_n: false,
+            expect_v: false,
+        },
+        true,
+    );
+}
+
+#[test]
+fn adc_decimal_mode_sets_carry_and_nmos_n_v_flags() {
+    /*
+     * 58 + 46 + 1 = 105
+     * N and V come from the intermediate 0xA5, before the high nibble is adjusted
+     * */
+    test_decimal_im(
+        AdcTestData {
+            carry_before: true,
+            lhs: 0x58,
+            rhs: 0x46,
+            expected_answer: 0x05,
+            expect_c: true,
+            expect_z: false,
+            expect_n: true,
+            expect_v: true,
+        },
+        true,
+    );
+}
+
+#[test]
+fn adc_decimal_mode_zero_flag_comes_from_binary_sum() {
+    /*
+     * 99 + 01 = 100, the accumulator is 0x00 but the binary sum 0x9A is not zero
+     * */
+    test_decimal_im(
+        AdcTestData {
+            carry_before: false,
+            lhs: 0x99,
+            rhs: 0x01,
+            expected_answer: 0x00,
+            expect_c: true,
+            expect_z: false,
+            expect_n: true,
+            expect_v: false,
+        },
+        true,
+    );
+}
+
+#[test]
+fn sbc_decimal_mode_can_subtract_two_bcd_numbers() {
+    test_decimal_im(
+        AdcTestData {
+            carry_before: true,
+            lhs: 0x46,
+            rhs: 0x12,
+            expected_answer: 0x34,
+            expect_c: true,
+            expect_z: false,
+            expect_n: false,
+            expect_v: false,
+        },
+        false,
+    );
+}
+
+#[test]
+fn sbc_decimal_mode_can_borrow_from_high_nibble() {
+    test_decimal_im(
+        AdcTestData {
+            carry_before: false,
+            lhs: 0x32,
+            rhs: 0x02,
+            expected_answer: 0x29,
+            expect_c: true,
+            expect_z: false,
+            expect_n: false,
+            expect_v: false,
+        },
+        false,
+    );
+}
+
+#[test]
+fn sbc_decimal_mode_wraps_below_zero() {
+    /*
+     * 12 - 21 = -9, which is 91 with the carry cleared
+     * N comes from the binary difference 0xF1
+     * */
+    test_decimal_im(
+        AdcTestData {
+            carry_before: true,
+            lhs: 0x12,
+            rhs: 0x21,
+            expected_answer: 0x91,
+            expect_c: false,
+            expect_z: false,
+            expect_n: true,
+            expect_v: false,
+        },
+        false,
+    );
+}
+
+#[test]
+fn sbc_decimal_mode_can_produce_zero() {
+    test_decimal_im(
+        AdcTestData {
+            carry_before: true,
+            lhs: 0x50,
+            rhs: 0x50,
+            expected_answer: 0x00,
+            expect_c: true,
+            expect_z: true,
+            expect_n: false,
+            expect_v: false,
+        },
+        false,
+    );
+}
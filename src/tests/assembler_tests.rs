@@ -0,0 +1,339 @@
+use crate::{
+    assembler::{Assembler, AssemblyError, AssemblyErrorKind},
+    cpu::{Byte, CpuVariant, CPU},
+    memory::Memory,
+};
+
+fn assemble(source: &str) -> Vec<Byte> {
+    Assembler::new(CpuVariant::Nmos6502)
+        .assemble(source)
+        .unwrap()
+        .to_prg()
+}
+
+fn assemble_error(source: &str) -> AssemblyError {
+    Assembler::new(CpuVariant::Nmos6502)
+        .assemble(source)
+        .unwrap_err()
+}
+
+#[test]
+fn assembles_the_loading_program_sources() {
+    let program = "* = $1000\nlda #$FF\n\nstart\nsta $90\nsta $8000\neor #$CC\njmp start\n";
+    assert_eq!(
+        assemble(program),
+        [0x00, 0x10, 0xA9, 0xFF, 0x85, 0x90, 0x8D, 0x00, 0x80, 0x49, 0xCC, 0x4C, 0x02, 0x10]
+    );
+
+    let program = "*=$1000\n\nlda #00\nsta $42\n\nstart\ninc $42\nlda $42\ninx\njmp start\n";
+    assert_eq!(
+        assemble(program),
+        [0x00, 0x10, 0xA9, 0x00, 0x85, 0x42, 0xE6, 0x42, 0xA5, 0x42, 0xE8, 0x4C, 0x04, 0x10]
+    );
+
+    assert_eq!(
+        assemble("*=$1000\n\nloop\nlda #0\nbeq loop\n"),
+        [0x00, 0x10, 0xA9, 0x00, 0xF0, 0xFC]
+    );
+}
+
+#[test]
+fn assembles_the_test_code_source() {
+    let program = include_str!("../../assembly/test_code.ms");
+    assert_eq!(
+        assemble(program),
+        [0x00, 0x10, 0xA9, 0x00, 0x18, 0x69, 0x08, 0xC9, 0x18, 0xD0, 0xFA, 0xA2, 0x14]
+    );
+}
+
+#[test]
+fn addressing_modes() {
+    let program = "\
+*=$0200
+    asl a
+    asl
+    lda #1
+    lda $10
+    lda $10,x
+    ldx $10,y
+    lda $1234
+    lda $1234,x
+    lda $1234,y
+    lda $10,y
+    jmp ($1234)
+    lda ($10,x)
+    lda ($10),y
+";
+    assert_eq!(
+        assemble(program)[2..],
+        [
+            0x0A, 0x0A, 0xA9, 0x01, 0xA5, 0x10, 0xB5, 0x10, 0xB6, 0x10, 0xAD, 0x34, 0x12, 0xBD,
+            0x34, 0x12, 0xB9, 0x34, 0x12, 0xB9, 0x10, 0x00, 0x6C, 0x34, 0x12, 0xA1, 0x10, 0xB1,
+            0x10,
+        ]
+    );
+}
+
+#[test]
+fn cmos_addressing_modes() {
+    let program = "\
+*=$0200
+    lda ($10)
+    jmp ($1234,x)
+here
+    bbr3 $10,here
+";
+    let prg = Assembler::new(CpuVariant::Cmos65C02)
+        .assemble(program)
+        .unwrap()
+        .to_prg();
+    assert_eq!(prg[2..], [0xB2, 0x10, 0x7C, 0x34, 0x12, 0x3F, 0x10, 0xFD]);
+}
+
+#[test]
+fn forward_references_use_absolute_addressing() {
+    let program = "\
+*=$0010
+    lda value
+    jmp done
+value .byte 7
+done: nop
+";
+    assert_eq!(
+        assemble(program),
+        [0x10, 0x00, 0xAD, 0x16, 0x00, 0x4C, 0x17, 0x00, 0x07, 0xEA]
+    );
+}
+
+#[test]
+fn low_and_high_byte_operators() {
+    let program = "\
+*=$1000
+    lda #<table
+    ldx #>table
+    lda #<table+$1FF
+    lda #>*
+table
+";
+    assert_eq!(
+        assemble(program)[2..],
+        [0xA9, 0x08, 0xA2, 0x10, 0xA9, 0x07, 0xA9, 0x10]
+    );
+}
+
+#[test]
+fn byte_and_word_directives() {
+    let program = "\
+*=$2000
+start .byte 1, $FF, %1010, -1
+    .word start, $1234, start+2
+";
+    assert_eq!(
+        assemble(program)[2..],
+        [0x01, 0xFF, 0x0A, 0xFF, 0x00, 0x20, 0x34, 0x12, 0x02, 0x20]
+    );
+}
+
+#[test]
+fn graphics_instruction() {
+    let program = "*=$FF00\n    dbg #$8883\n    dbg $0300\n";
+    assert_eq!(
+        assemble(program),
+        [0x00, 0xFF, 0x44, 0x83, 0x88, 0x43, 0x00, 0x03]
+    );
+}
+
+#[test]
+fn origins_leave_zero_filled_gaps() {
+    let assembly = Assembler::new(CpuVariant::Nmos6502)
+        .assemble("*=$1000\nnop\n*=$1004\nrts\n")
+        .unwrap();
+    assert_eq!(assembly.image.segments.len(), 2);
+    assert_eq!(assembly.image.entry, Some(0x1000));
+    assert_eq!(
+        assembly.to_prg(),
+        [0x00, 0x10, 0xEA, 0x00, 0x00, 0x00, 0x60]
+    );
+}
+
+#[test]
+fn assembled_program_runs() {
+    let program = "\
+*=$1000
+    ldx #0
+loop
+    inx
+    cpx #5
+    bne loop
+    stx $40
+";
+    let assembly = Assembler::new(CpuVariant::Nmos6502)
+        .assemble(program)
+        .unwrap();
+    let mut memory = Memory::initialize();
+    let entry = assembly.image.load_into(&mut memory).unwrap();
+    let mut cpu = CPU::start_at(entry);
+    cpu.execute(2 + 5 * 7 + 3, &mut memory).unwrap();
+    assert_eq!(memory[0x0040_u16], 5);
+}
+
+#[test]
+fn listing_shows_addresses_and_bytes() {
+    let assembly = Assembler::new(CpuVariant::Nmos6502)
+        .assemble("*=$1000\nstart lda #0 ; clear\n    jmp start\n")
+        .unwrap();
+    assert_eq!(assembly.labels["start"], 0x1000);
+    assert_eq!(assembly.listing[1].address, Some(0x1000));
+    assert_eq!(assembly.listing[1].bytes, [0xA9, 0x00]);
+    assert_eq!(
+        assembly.listing_text(),
+        "    1                 *=$1000\n    2 1000  A9 00     start lda #0 ; clear\n    3 1002  4C 00 10      jmp start\n"
+    );
+}
+
+#[test]
+fn errors_carry_line_and_column() {
+    assert_eq!(
+        assemble_error("*=$1000\n    jmp nowhere\n"),
+        AssemblyError {
+            line: 2,
+            column: 9,
+            kind: AssemblyErrorKind::UndefinedLabel("nowhere".to_string()),
+        }
+    );
+    assert_eq!(
+        assemble_error("*=$1000\n  lda #$1G\n").kind,
+        AssemblyErrorKind::InvalidNumber
+    );
+    assert_eq!(
+        assemble_error("*=$1000\nx\nx\n"),
+        AssemblyError {
+            line: 3,
+            column: 1,
+            kind: AssemblyErrorKind::DuplicateLabel("x".to_string()),
+        }
+    );
+    assert_eq!(
+        assemble_error("*=$1000\n  jmp #1\n"),
+        AssemblyError {
+            line: 2,
+            column: 3,
+            kind: AssemblyErrorKind::UnsupportedAddressingMode("JMP".to_string()),
+        }
+    );
+    assert_eq!(
+        assemble_error("*=$1000\n  foo $10\n").kind,
+        AssemblyErrorKind::UnknownMnemonic("foo".to_string())
+    );
+    assert_eq!(
+        assemble_error("*=$1000\n  lda #256\n").kind,
+        AssemblyErrorKind::ValueOutOfRange(256)
+    );
+    assert_eq!(
+        assemble_error("*=$1000\n  lda ($10\n"),
+        AssemblyError {
+            line: 2,
+            column: 11,
+            kind: AssemblyErrorKind::ExpectedClosingParenthesis,
+        }
+    );
+    assert_eq!(
+        assemble_error("  nop\n").kind,
+        AssemblyErrorKind::MissingOrigin
+    );
+    assert_eq!(
+        assemble_error("*=$1000\n  .fill 3\n").kind,
+        AssemblyErrorKind::UnknownDirective(".fill".to_string())
+    );
+    assert_eq!(
+        assemble_error("*=$1000\n  lda #1 2\n").kind,
+        AssemblyErrorKind::ExpectedEndOfLine
+    );
+    assert_eq!(
+        assemble_error("*=$FFFF\n  lda $1234\n").kind,
+        AssemblyErrorKind::PastEndOfMemory
+    );
+    assert_eq!(
+        assemble_error("*=$FFFE\n  .byte 1, 2\n  nop\n"),
+        AssemblyError {
+            line: 3,
+            column: 3,
+            kind: AssemblyErrorKind::PastEndOfMemory,
+        }
+    );
+    assert_eq!(
+        assemble_error("*=$FFFD\n  jmp end\nend\n"),
+        AssemblyError {
+            line: 3,
+            column: 1,
+            kind: AssemblyErrorKind::PastEndOfMemory,
+        }
+    );
+    assert_eq!(
+        assemble_error("*=$1000\n  lda @\n"),
+        AssemblyError {
+            line: 2,
+            column: 7,
+            kind: AssemblyErrorKind::UnexpectedCharacter('@'),
+        }
+    );
+}
+
+#[test]
+fn code_can_end_at_the_top_of_memory() {
+    assert_eq!(
+        assemble("*=$FFFC\n  .word $1000, $2000\n"),
+        [0xFC, 0xFF, 0x00, 0x10, 0x00, 0x20]
+    );
+    assert_eq!(
+        assemble("*=$FFFE\n  .byte 1, 2\n*=$1000\n  nop\n")[..5],
+        [0x00, 0x10, 0xEA, 0x00, 0x00]
+    );
+}
+
+#[test]
+fn arithmetic_overflow_is_an_error() {
+    assert_eq!(
+        assemble_error("*=$1000\n  .word $7FFFFFFF+1\n"),
+        AssemblyError {
+            line: 2,
+            column: 3,
+            kind: AssemblyErrorKind::ValueOutOfRange(i32::MAX),
+        }
+    );
+    assert_eq!(
+        assemble_error("*=$1000\n  .word 0-$7FFFFFFF-2\n").kind,
+        AssemblyErrorKind::ValueOutOfRange(i32::MIN)
+    );
+    assert_eq!(
+        assemble_error("*=$1000\nstart lda -$7FFFFFFF-start\n"),
+        AssemblyError {
+            line: 2,
+            column: 22,
+            kind: AssemblyErrorKind::ValueOutOfRange(i32::MIN),
+        }
+    );
+}
+
+#[test]
+fn branch_out_of_range() {
+    let mut program = String::from("*=$1000\nstart\n");
+    for _ in 0..130 {
+        program += "    nop\n";
+    }
+    program += "    bne start\n";
+    assert_eq!(
+        assemble_error(&program),
+        AssemblyError {
+            line: 133,
+            column: 9,
+            kind: AssemblyErrorKind::BranchOutOfRange(-132),
+        }
+    );
+}
+
+#[test]
+fn error_display() {
+    let error = assemble_error("*=$1000\n    jmp nowhere\n");
+    assert_eq!(error.to_string(), "2:9: undefined label 'nowhere'");
+}
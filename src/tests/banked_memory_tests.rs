@@ -0,0 +1,240 @@
+use crate::{
+    banked_memory::{BankError, BankedMemory, PhysicalAddress},
+    bus::Bus,
+    cpu::{Byte, CPU},
+    instructions::{Instruction, InstructionsError},
+    memory::Memory,
+    memory_map::{MemoryMap, RomWritePolicy},
+};
+
+fn physical(bank: usize, offset: usize) -> PhysicalAddress {
+    PhysicalAddress { bank, offset }
+}
+
+#[test]
+fn writing_register_switches_bank() {
+    let mut bus =
+        BankedMemory::single_window(Memory::initialize(), 4, 0x1000, 0x8000, 0x7FFF).unwrap();
+
+    bus.write(0x8010, 0x11);
+    bus.write(0x7FFF, 2);
+    bus.write(0x8010, 0x22);
+
+    assert_eq!(bus.read(0x7FFF), 2);
+    assert_eq!(bus.read(0x8010), 0x22);
+    bus.write(0x7FFF, 0);
+    assert_eq!(bus.read(0x8010), 0x11);
+    assert_eq!(bus.peek_physical(physical(2, 0x10)), Ok(0x22));
+    assert_eq!(bus.inner[0x8010_u16], 0x00);
+}
+
+#[test]
+fn bank_numbers_wrap_around() {
+    let mut bus =
+        BankedMemory::single_window(Memory::initialize(), 4, 0x1000, 0x8000, 0x7FFF).unwrap();
+
+    bus.write(0x7FFF, 6);
+
+    assert_eq!(bus.windows()[0].bank, 2);
+}
+
+#[test]
+fn two_windows_switch_independently() {
+    let mut bus = BankedMemory::two_windows(
+        Memory::initialize(),
+        8,
+        0x2000,
+        [(0x8000, 0x7FF0), (0xA000, 0x7FF1)],
+    )
+    .unwrap();
+    bus.write_physical(physical(3, 0x0005), 0x33).unwrap();
+    bus.write_physical(physical(5, 0x0005), 0x55).unwrap();
+
+    bus.write(0x7FF0, 3);
+    bus.write(0x7FF1, 5);
+
+    assert_eq!(bus.read(0x8005), 0x33);
+    assert_eq!(bus.read(0xA005), 0x55);
+
+    bus.write(0x7FF1, 3);
+    bus.write(0xA005, 0x44);
+
+    assert_eq!(bus.read(0x8005), 0x44);
+}
+
+#[test]
+fn addresses_outside_windows_go_to_inner_bus() {
+    let mut bus =
+        BankedMemory::single_window(Memory::initialize(), 2, 0x1000, 0x8000, 0x7FFF).unwrap();
+
+    bus.write(0x9000, 0x42);
+    bus.write(0x7FFE, 0x43);
+
+    assert_eq!(bus.inner[0x9000_u16], 0x42);
+    assert_eq!(bus.inner[0x7FFE_u16], 0x43);
+    assert_eq!(bus.physical_address(0x9000), None);
+}
+
+#[test]
+fn cpu_address_translates_to_physical() {
+    let mut bus = BankedMemory::two_windows(
+        Memory::initialize(),
+        8,
+        0x2000,
+        [(0x8000, 0x7FF0), (0xA000, 0x7FF1)],
+    )
+    .unwrap();
+
+    bus.select_bank(1, 6).unwrap();
+
+    assert_eq!(bus.physical_address(0x8123), Some(physical(0, 0x0123)));
+    assert_eq!(bus.physical_address(0xBFFF), Some(physical(6, 0x1FFF)));
+}
+
+#[test]
+fn selecting_a_missing_window_is_an_error() {
+    let mut bus = BankedMemory::two_windows(
+        Memory::initialize(),
+        8,
+        0x2000,
+        [(0x8000, 0x7FF0), (0xA000, 0x7FF1)],
+    )
+    .unwrap();
+
+    assert_eq!(
+        bus.select_bank(2, 0),
+        Err(BankError::WindowOutOfRange {
+            window: 2,
+            window_count: 2
+        })
+    );
+    assert_eq!(bus.physical_address(0xA000), Some(physical(0, 0)));
+}
+
+#[test]
+fn physical_access_is_checked() {
+    let mut bus = BankedMemory::new(Memory::initialize(), 2, 0x100).unwrap();
+
+    assert_eq!(
+        bus.peek_physical(physical(2, 0)),
+        Err(BankError::BankOutOfRange {
+            bank: 2,
+            bank_count: 2
+        })
+    );
+    assert_eq!(
+        bus.write_physical(physical(0, 0x100), 0),
+        Err(BankError::OffsetOutOfRange {
+            offset: 0x100,
+            bank_size: 0x100
+        })
+    );
+    assert_eq!(
+        bus.select_bank(0, 2),
+        Err(BankError::BankOutOfRange {
+            bank: 2,
+            bank_count: 2
+        })
+    );
+    assert_eq!(
+        bus.load_physical(physical(1, 0xFF), &[1, 2]),
+        Err(BankError::BankOutOfRange {
+            bank: 2,
+            bank_count: 2
+        })
+    );
+}
+
+#[test]
+fn invalid_windows_are_rejected() {
+    let mut bus = BankedMemory::new(Memory::initialize(), 2, 0x1000).unwrap();
+    bus.add_window(0x8000, 0x7FFF).unwrap();
+
+    assert_eq!(
+        bus.add_window(0x8800, 0x7FFE),
+        Err(BankError::WindowOverlaps { start: 0x8000 })
+    );
+    assert_eq!(
+        bus.add_window(0xF001, 0x7FFE),
+        Err(BankError::WindowPastEndOfMemory { start: 0xF001 })
+    );
+    assert_eq!(
+        bus.add_window(0x9000, 0x7FFF),
+        Err(BankError::RegisterInUse(0x7FFF))
+    );
+    assert_eq!(
+        bus.add_window(0x9000, 0x9010),
+        Err(BankError::RegisterInUse(0x9010))
+    );
+    assert_eq!(
+        bus.add_window(0x7000, 0x6000),
+        Err(BankError::RegisterInUse(0x7FFF))
+    );
+    assert_eq!(
+        BankedMemory::new(Memory::initialize(), 0, 0x1000).err(),
+        Some(BankError::NoBanks)
+    );
+}
+
+#[test]
+fn cpu_reads_program_data_beyond_64k() {
+    // Sum the first byte of each bank into $10:
+    //   loop: STX $7FFF / LDA $8000 / CLC / ADC $10 / STA $10 / INX / CPX #$08 / BNE loop
+    let program = [
+        Instruction::InsStxAbs as Byte,
+        0xFF,
+        0x7F,
+        Instruction::InsLdaAbs as Byte,
+        0x00,
+        0x80,
+        Instruction::InsClc as Byte,
+        Instruction::InsAdcZp as Byte,
+        0x10,
+        Instruction::InsStaZp as Byte,
+        0x10,
+        Instruction::InsInx as Byte,
+        Instruction::InsCpxIm as Byte,
+        0x08,
+        Instruction::InsBne as Byte,
+        0xF0,
+        Instruction::InsNop as Byte,
+    ];
+    let mut bus = BankedMemory::single_window(MemoryMap::new(), 8, 0x4000, 0x8000, 0x7FFF).unwrap();
+    bus.inner
+        .map_rom(0xF000, &program, RomWritePolicy::Ignore)
+        .unwrap();
+    let image: Vec<Byte> = (0..8 * 0x4000)
+        .map(|index| (index / 0x4000) as Byte)
+        .collect();
+    bus.load_physical(physical(0, 0), &image).unwrap();
+    let mut cpu = CPU::start_at(0xF000);
+
+    while cpu.program_counter != 0xF010 {
+        cpu.step(&mut bus).unwrap();
+    }
+
+    assert_eq!(bus.read(0x0010), 1 + 2 + 3 + 4 + 5 + 6 + 7);
+    assert_eq!(bus.windows()[0].bank, 7);
+}
+
+#[test]
+fn faults_from_inner_bus_pass_through() {
+    let mut inner = MemoryMap::new();
+    inner
+        .map_rom(0xF000, &[0xEA], RomWritePolicy::Fault)
+        .unwrap();
+    inner.write(0x0200, Instruction::InsStaAbs as Byte);
+    inner.write(0x0201, 0x00);
+    inner.write(0x0202, 0xF0);
+    let mut bus = BankedMemory::single_window(inner, 2, 0x1000, 0x8000, 0x7FFF).unwrap();
+    let mut cpu = CPU::start_at(0x0200);
+
+    assert_eq!(
+        cpu.execute(4, &mut bus),
+        Err(InstructionsError::WriteToRom {
+            program_counter: 0x0200,
+            address: 0xF000,
+            value: 0x00
+        })
+    );
+}
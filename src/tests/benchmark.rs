@@ -0,0 +1,56 @@
+use std::time::Instant;
+
+use crate::{
+    cpu::{Byte, CPU},
+    memory::Memory,
+};
+
+/*
+* *=$1000
+*
+* loop
+* lda #0
+* ldx #0
+* inner
+*     adc $10,x
+*     sta $0200,x
+*     asl $30
+*     inx
+*     bne inner
+* inc $20
+* jmp loop
+* */
+static TIGHT_LOOP_PROGRAM: [Byte; 21] = [
+    0x00, 0x10, 0xA9, 0x00, 0xA2, 0x00, 0x75, 0x10, 0x9D, 0x00, 0x02, 0x06, 0x30, 0xE8, 0xD0, 0xF6,
+    0xE6, 0x20, 0x4C, 0x00, 0x10,
+];
+
+const BENCHMARK_CYCLES: i64 = 100_000_000;
+
+// Run with `cargo test --release -- --ignored --nocapture benchmark`
+#[test]
+#[ignore]
+fn benchmark_tight_loop() {
+    let mut cpu = CPU::start_at(0x1000);
+    let mut memory = Memory::initialize();
+
+    cpu.load_program(
+        &TIGHT_LOOP_PROGRAM,
+        TIGHT_LOOP_PROGRAM.len() as u16,
+        &mut memory,
+    );
+
+    let start = Instant::now();
+    let mut executed: i64 = 0;
+    while executed < BENCHMARK_CYCLES {
+        executed += cpu.execute(1000, &mut memory).unwrap() as i64;
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{} cycles in {:.3}s ({:.1} MHz)",
+        executed,
+        elapsed.as_secs_f64(),
+        executed as f64 / elapsed.as_secs_f64() / 1_000_000.0
+    );
+}
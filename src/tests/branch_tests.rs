@@ -7,7 +7,7 @@ use crate::{
 // BEQ
 #[test]
 fn beq_can_branch_forwards_when_zero_is_set() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.zero = true;
@@ -26,7 +26,7 @@ fn beq_can_branch_forwards_when_zero_is_set() {
 
 #[test]
 fn beq_does_not_branch_forwards_when_zero_is_not_set() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.zero = false;
@@ -45,7 +45,7 @@ fn beq_does_not_branch_forwards_when_zero_is_not_set() {
 
 #[test]
 fn beq_can_branch_forwards_into_new_page_when_zero_is_set() {
-    let mut cpu = CPU::reset(Some(0xFEFD));
+    let mut cpu = CPU::start_at(0xFEFD);
     let mut memory = Memory::initialize();
 
     cpu.status.zero = true;
@@ -64,7 +64,7 @@ fn beq_can_branch_forwards_into_new_page_when_zero_is_set() {
 
 #[test]
 fn beq_can_branch_backwards_when_zero_is_set() {
-    let mut cpu = CPU::reset(Some(0xFFCC));
+    let mut cpu = CPU::start_at(0xFFCC);
     let mut memory = Memory::initialize();
 
     cpu.status.zero = true;
@@ -84,7 +84,7 @@ fn beq_can_branch_backwards_when_zero_is_set() {
 // BNE
 #[test]
 fn bne_can_branch_forwards_when_zero_is_not_set() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.zero = false;
@@ -104,7 +104,7 @@ fn bne_can_branch_forwards_when_zero_is_not_set() {
 // BCS
 #[test]
 fn bcs_can_branch_forwards_when_carry_is_set() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -124,7 +124,7 @@ fn bcs_can_branch_forwards_when_carry_is_set() {
 // BCC
 #[test]
 fn bcc_can_branch_forwards_when_carry_is_not_set() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -144,7 +144,7 @@ fn bcc_can_branch_forwards_when_carry_is_not_set() {
 // BMI
 #[test]
 fn bmi_can_branch_forwards_when_negative_is_set() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.negative = true;
@@ -164,7 +164,7 @@ fn bmi_can_branch_forwards_when_negative_is_set() {
 // BPL
 #[test]
 fn bpl_can_branch_forwards_when_negative_is_not_set() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.negative = false;
@@ -184,7 +184,7 @@ fn bpl_can_branch_forwards_when_negative_is_not_set() {
 // BVS
 #[test]
 fn bvs_can_branch_forwards_when_overflow_is_set() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.overflow = true;
@@ -204,7 +204,7 @@ fn bvs_can_branch_forwards_when_overflow_is_set() {
 // BVC
 #[test]
 fn bvc_can_branch_forwards_when_overflow_is_not_set() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.overflow = false;
@@ -0,0 +1,118 @@
+use crate::{
+    bus::Bus,
+    cpu::{Byte, Word, CPU},
+    instructions::Instruction,
+    memory::Memory,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Access {
+    Read(Word),
+    Write(Word, Byte),
+}
+
+/// Flat RAM that records every access the CPU makes.
+struct LoggingBus {
+    memory: Memory,
+    accesses: Vec<Access>,
+}
+
+impl LoggingBus {
+    fn new() -> Self {
+        LoggingBus {
+            memory: Memory::initialize(),
+            accesses: Vec::new(),
+        }
+    }
+}
+
+impl Bus for LoggingBus {
+    fn read(&mut self, address: Word) -> Byte {
+        self.accesses.push(Access::Read(address));
+        self.memory.read(address)
+    }
+
+    fn write(&mut self, address: Word, value: Byte) {
+        self.accesses.push(Access::Write(address, value));
+        self.memory.write(address, value);
+    }
+
+    fn peek(&self, address: Word) -> Byte {
+        self.memory.peek(address)
+    }
+}
+
+#[test]
+fn memory_reads_and_writes_through_bus() {
+    let mut memory = Memory::initialize();
+
+    memory.write(0x1234, 0x42);
+
+    assert_eq!(memory.read(0x1234), 0x42);
+    assert_eq!(memory.peek(0x1234), 0x42);
+    assert_eq!(memory[0x1234_u16], 0x42);
+}
+
+#[test]
+fn cpu_executes_against_custom_bus() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut bus = LoggingBus::new();
+    bus.memory[0xFF00] = Instruction::InsLdaAbs as Byte;
+    bus.memory[0xFF01] = 0x80;
+    bus.memory[0xFF02] = 0x44;
+    bus.memory[0xFF03] = Instruction::InsStaZp as Byte;
+    bus.memory[0xFF04] = 0x10;
+    bus.memory[0x4480] = 0x37;
+
+    let cycles = cpu.execute(7, &mut bus);
+
+    assert_eq!(cycles, Ok(7));
+    assert_eq!(
+        bus.accesses,
+        vec![
+            Access::Read(0xFF00),
+            Access::Read(0xFF01),
+            Access::Read(0xFF02),
+            Access::Read(0x4480),
+            Access::Read(0xFF03),
+            Access::Read(0xFF04),
+            Access::Write(0x0010, 0x37),
+        ]
+    );
+}
+
+#[test]
+fn step_report_only_peeks_the_bus() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut bus = LoggingBus::new();
+    bus.memory[0xFF00] = Instruction::InsLdaIndY as Byte;
+    bus.memory[0xFF01] = 0x10;
+    bus.memory[0x0010] = 0x00;
+    bus.memory[0x0011] = 0x80;
+
+    let result = cpu.step(&mut bus).unwrap().unwrap();
+
+    assert_eq!(result.effective_address, Some(0x8000));
+    assert_eq!(
+        bus.accesses,
+        vec![
+            Access::Read(0xFF00),
+            Access::Read(0xFF01),
+            Access::Read(0x0010),
+            Access::Read(0x0011),
+            Access::Read(0x8000),
+        ]
+    );
+}
+
+#[test]
+fn stack_helpers_use_the_bus() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut bus = LoggingBus::new();
+    bus.memory[0xFF00] = Instruction::InsPha as Byte;
+    cpu.a_register = 0x99;
+
+    cpu.execute(3, &mut bus).unwrap();
+
+    assert_eq!(bus.accesses.last(), Some(&Access::Write(0x01FF, 0x99)));
+}
@@ -0,0 +1,473 @@
+use crate::{
+    cpu::{Byte, CpuVariant, CPU},
+    instructions::{Instruction, InstructionsError},
+    memory::Memory,
+};
+
+fn cmos_cpu() -> CPU {
+    CPU::start_at(0xFF00).with_variant(CpuVariant::Cmos65C02)
+}
+
+#[test]
+fn nmos_rejects_65c02_opcodes() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsBra.opcode();
+
+    let cycles = cpu.execute(3, &mut memory);
+
+    assert_eq!(cycles, Err(InstructionsError::InstructionDoesntExist(0x80)));
+}
+
+// BRA
+#[test]
+fn bra_always_branches() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsBra.opcode();
+    memory[0xFF01] = 0x10;
+
+    let cycles = cpu.execute(3, &mut memory);
+
+    assert_eq!(cycles, Ok(3));
+    assert_eq!(cpu.program_counter, 0xFF12);
+}
+
+// PHX, PLX, PHY, PLY
+#[test]
+fn phx_and_ply_move_x_to_y_through_the_stack() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.x_register = 0x80;
+    memory[0xFF00] = Instruction::InsPhx.opcode();
+    memory[0xFF01] = Instruction::InsPly.opcode();
+
+    let cycles = cpu.execute(7, &mut memory);
+
+    assert_eq!(cycles, Ok(7));
+    assert_eq!(cpu.y_register, 0x80);
+    assert_eq!(cpu.stack_pointer, 0xFF);
+    assert!(cpu.status.negative);
+}
+
+#[test]
+fn phy_and_plx_move_y_to_x_through_the_stack() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.y_register = 0x00;
+    cpu.x_register = 0x42;
+    memory[0xFF00] = Instruction::InsPhy.opcode();
+    memory[0xFF01] = Instruction::InsPlx.opcode();
+
+    let cycles = cpu.execute(7, &mut memory);
+
+    assert_eq!(cycles, Ok(7));
+    assert_eq!(cpu.x_register, 0x00);
+    assert!(cpu.status.zero);
+}
+
+// STZ
+#[test]
+fn stz_absolute_x_stores_zero() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.x_register = 0x01;
+    memory[0xFF00] = Instruction::InsStzAbsX.opcode();
+    memory[0xFF01] = 0x00;
+    memory[0xFF02] = 0x80;
+    memory[0x8001] = 0x42;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(memory[0x8001_u16], 0x00);
+}
+
+// TRB and TSB
+#[test]
+fn tsb_zero_page_sets_bits_and_tests_against_a() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0b0000_1111;
+    memory[0xFF00] = Instruction::InsTsbZp.opcode();
+    memory[0xFF01] = 0x42;
+    memory[0x0042] = 0b1111_0000;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(memory[0x0042_u16], 0xFF);
+    assert!(cpu.status.zero);
+}
+
+#[test]
+fn trb_absolute_clears_bits_and_tests_against_a() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0b0000_1111;
+    memory[0xFF00] = Instruction::InsTrbAbs.opcode();
+    memory[0xFF01] = 0x00;
+    memory[0xFF02] = 0x80;
+    memory[0x8000] = 0b1111_1100;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(memory[0x8000_u16], 0b1111_0000);
+    assert!(!cpu.status.zero);
+}
+
+// Zero page indirect
+#[test]
+fn lda_zero_page_indirect_loads_through_pointer() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsLdaIndZp.opcode();
+    memory[0xFF01] = 0x42;
+    memory[0x0042] = 0x00;
+    memory[0x0043] = 0x80;
+    memory[0x8000] = 0x37;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(cpu.a_register, 0x37);
+}
+
+#[test]
+fn sta_zero_page_indirect_stores_through_pointer() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0x37;
+    memory[0xFF00] = Instruction::InsStaIndZp.opcode();
+    memory[0xFF01] = 0x42;
+    memory[0x0042] = 0x00;
+    memory[0x0043] = 0x80;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(memory[0x8000_u16], 0x37);
+}
+
+// BIT
+#[test]
+fn bit_immediate_only_affects_zero_flag() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0x01;
+    memory[0xFF00] = Instruction::InsBitIm.opcode();
+    memory[0xFF01] = 0xC0;
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert!(cpu.status.zero);
+    assert!(!cpu.status.negative);
+    assert!(!cpu.status.overflow);
+}
+
+// INC A and DEC A
+#[test]
+fn inc_and_dec_accumulator() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0xFF;
+    memory[0xFF00] = Instruction::InsIncA.opcode();
+    memory[0xFF01] = Instruction::InsDecA.opcode();
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.a_register, 0x00);
+    assert!(cpu.status.zero);
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.a_register, 0xFF);
+    assert!(cpu.status.negative);
+}
+
+// JMP
+#[test]
+fn jmp_absolute_indexed_indirect_jumps_through_table() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.x_register = 0x02;
+    memory[0xFF00] = Instruction::InsJmpAbsIndX.opcode();
+    memory[0xFF01] = 0x00;
+    memory[0xFF02] = 0x80;
+    memory[0x8002] = 0x34;
+    memory[0x8003] = 0x12;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(cpu.program_counter, 0x1234);
+}
+
+#[test]
+fn jmp_indirect_reads_pointer_across_page_with_extra_cycle() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsJmpInd.opcode();
+    memory[0xFF01] = 0xFF;
+    memory[0xFF02] = 0x80;
+    memory[0x80FF] = 0x34;
+    memory[0x8100] = 0x12;
+    memory[0x8000] = 0x56;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(cpu.program_counter, 0x1234);
+}
+
+// BBR and BBS
+#[test]
+fn bbr_branches_when_bit_is_clear() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsBbr3.opcode();
+    memory[0xFF01] = 0x42;
+    memory[0xFF02] = 0x10;
+    memory[0x0042] = 0b1111_0111;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(cpu.program_counter, 0xFF13);
+}
+
+#[test]
+fn bbs_does_not_branch_when_bit_is_clear() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsBbs3.opcode();
+    memory[0xFF01] = 0x42;
+    memory[0xFF02] = 0x10;
+    memory[0x0042] = 0b1111_0111;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(cpu.program_counter, 0xFF03);
+}
+
+// RMB and SMB
+#[test]
+fn rmb_and_smb_modify_a_single_bit() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsSmb7.opcode();
+    memory[0xFF01] = 0x42;
+    memory[0xFF02] = Instruction::InsRmb0.opcode();
+    memory[0xFF03] = 0x42;
+    memory[0x0042] = 0b0000_0001;
+
+    let cpu_copy = cpu;
+
+    let cycles = cpu.execute(10, &mut memory);
+
+    assert_eq!(cycles, Ok(10));
+    assert_eq!(memory[0x0042_u16], 0b1000_0000);
+    assert_eq!(cpu.status, cpu_copy.status);
+}
+
+// WAI and STP
+#[test]
+fn wai_waits_until_an_interrupt_arrives() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsWai.opcode();
+    memory[0xFF01] = Instruction::InsNop.opcode();
+    memory[0xFFFE] = 0x00;
+    memory[0xFFFF] = 0x80;
+
+    cpu.execute(3, &mut memory).unwrap();
+    let cycles = cpu.execute(100, &mut memory);
+
+    assert_eq!(cycles, Ok(100));
+    assert_eq!(cpu.program_counter, 0xFF01);
+
+    cpu.set_irq(true);
+    cpu.execute(7, &mut memory).unwrap();
+
+    assert_eq!(cpu.program_counter, 0x8000);
+    assert!(!cpu.waiting_for_interrupt);
+}
+
+#[test]
+fn wai_resumes_without_taking_masked_interrupt() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.status.interupt_disable = true;
+    memory[0xFF00] = Instruction::InsWai.opcode();
+    memory[0xFF01] = Instruction::InsNop.opcode();
+
+    cpu.execute(3, &mut memory).unwrap();
+    cpu.set_irq(true);
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.program_counter, 0xFF02);
+}
+
+#[test]
+fn stp_stops_until_reset() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsStp.opcode();
+    memory[0xFFFC] = 0x00;
+    memory[0xFFFD] = 0x80;
+
+    cpu.execute(3, &mut memory).unwrap();
+    cpu.set_irq(true);
+    cpu.execute(100, &mut memory).unwrap();
+
+    assert_eq!(cpu.program_counter, 0xFF01);
+
+    cpu.reset(&mut memory);
+
+    assert!(!cpu.stopped);
+    assert_eq!(cpu.program_counter, 0x8000);
+}
+
+// Undefined opcodes
+#[test]
+fn undefined_opcodes_are_nops_of_the_correct_length() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = 0x03;
+    memory[0xFF01] = 0x02;
+    memory[0xFF02] = 0xFF;
+    memory[0xFF03] = 0x5C;
+    memory[0xFF04] = 0xFF;
+    memory[0xFF05] = 0xFF;
+
+    let cycles = cpu.execute(1 + 2 + 8, &mut memory);
+
+    assert_eq!(cycles, Ok(11));
+    assert_eq!(cpu.program_counter, 0xFF06);
+}
+
+// Decimal mode
+#[test]
+fn adc_decimal_mode_has_valid_flags_and_takes_extra_cycle() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.status.decimal_mode = true;
+    cpu.a_register = 0x99;
+    memory[0xFF00] = Instruction::InsAdcIm.opcode();
+    memory[0xFF01] = 0x01;
+
+    let cycles = cpu.execute(3, &mut memory);
+
+    assert_eq!(cycles, Ok(3));
+    assert_eq!(cpu.a_register, 0x00);
+    assert!(cpu.status.carry);
+    assert!(cpu.status.zero);
+    assert!(!cpu.status.negative);
+}
+
+#[test]
+fn sbc_decimal_mode_has_valid_flags_and_takes_extra_cycle() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.status.decimal_mode = true;
+    cpu.status.carry = true;
+    cpu.a_register = 0x12;
+    memory[0xFF00] = Instruction::InsSbcIm.opcode();
+    memory[0xFF01] = 0x21;
+
+    let cycles = cpu.execute(3, &mut memory);
+
+    assert_eq!(cycles, Ok(3));
+    assert_eq!(cpu.a_register, 0x91);
+    assert!(!cpu.status.carry);
+    assert!(cpu.status.negative);
+}
+
+#[test]
+fn brk_clears_decimal_mode() {
+    let mut cpu = cmos_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.status.decimal_mode = true;
+    memory[0xFF00] = Instruction::InsBrk.opcode();
+
+    cpu.execute(7, &mut memory).unwrap();
+
+    assert!(!cpu.status.decimal_mode);
+}
+
+// Indexed read-modify-write timing
+fn absolute_x_cycles(
+    cpu: &mut CPU,
+    instruction: Instruction,
+    x: Byte,
+) -> Result<i32, InstructionsError> {
+    let mut memory = Memory::initialize();
+
+    cpu.x_register = x;
+    memory[0xFF00] = instruction.opcode();
+    memory[0xFF01] = 0x80;
+    memory[0xFF02] = 0x40;
+
+    let cycles = cpu.execute(1, &mut memory);
+    assert_eq!(cpu.program_counter, 0xFF03);
+    cycles
+}
+
+#[test]
+fn shifts_absolute_x_only_pay_for_a_page_cross() {
+    for instruction in [
+        Instruction::InsAslAbsX,
+        Instruction::InsLsrAbsX,
+        Instruction::InsRolAbsX,
+        Instruction::InsRorAbsX,
+    ] {
+        assert_eq!(absolute_x_cycles(&mut cmos_cpu(), instruction, 0x01), Ok(6));
+        assert_eq!(absolute_x_cycles(&mut cmos_cpu(), instruction, 0x80), Ok(7));
+    }
+}
+
+#[test]
+fn inc_dec_absolute_x_always_take_seven_cycles() {
+    for instruction in [Instruction::InsIncAbsX, Instruction::InsDecAbsX] {
+        assert_eq!(absolute_x_cycles(&mut cmos_cpu(), instruction, 0x01), Ok(7));
+        assert_eq!(absolute_x_cycles(&mut cmos_cpu(), instruction, 0x80), Ok(7));
+    }
+}
+
+#[test]
+fn nmos_shifts_absolute_x_always_take_seven_cycles() {
+    let mut cpu = CPU::start_at(0xFF00);
+    assert_eq!(
+        absolute_x_cycles(&mut cpu, Instruction::InsAslAbsX, 0x01),
+        Ok(7)
+    );
+}
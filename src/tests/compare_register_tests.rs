@@ -31,7 +31,7 @@ struct CmpTestData {
 }
 
 fn test_im(data: CmpTestData, register_to_test: CompareRegister) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
@@ -72,7 +72,7 @@ fn test_im(data: CmpTestData, register_to_test: CompareRegister) {
 }
 
 fn test_zp(data: CmpTestData, register_to_test: CompareRegister) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
@@ -114,7 +114,7 @@ fn test_zp(data: CmpTestData, register_to_test: CompareRegister) {
 }
 
 fn test_zp_x(data: CmpTestData) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
@@ -142,7 +142,7 @@ fn test_zp_x(data: CmpTestData) {
 }
 
 fn test_abs(data: CmpTestData, register_to_test: CompareRegister) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
@@ -185,7 +185,7 @@ fn test_abs(data: CmpTestData, register_to_test: CompareRegister) {
 }
 
 fn test_abs_x(data: CmpTestData) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
@@ -214,7 +214,7 @@ fn test_abs_x(data: CmpTestData) {
 }
 
 fn test_abs_x_page_cross(data: CmpTestData) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
@@ -243,7 +243,7 @@ fn test_abs_x_page_cross(data: CmpTestData) {
 }
 
 fn test_abs_y(data: CmpTestData) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
@@ -272,7 +272,7 @@ fn test_abs_y(data: CmpTestData) {
 }
 
 fn test_abs_y_page_cross(data: CmpTestData) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
@@ -301,7 +301,7 @@ fn test_abs_y_page_cross(data: CmpTestData) {
 }
 
 fn test_ind_x(data: CmpTestData) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
@@ -331,7 +331,7 @@ fn test_ind_x(data: CmpTestData) {
 }
 
 fn test_ind_y(data: CmpTestData) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
@@ -361,7 +361,7 @@ fn test_ind_y(data: CmpTestData) {
 }
 
 fn test_ind_y_page_cross(data: CmpTestData) {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = !data.expect_c;
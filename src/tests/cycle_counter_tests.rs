@@ -0,0 +1,140 @@
+use crate::{
+    cpu::{Byte, CpuVariant, CPU},
+    instructions::Instruction,
+    memory::Memory,
+};
+
+fn nop_program(memory: &mut Memory) {
+    for address in 0xFF00..=0xFFF0_u16 {
+        memory[address] = Instruction::InsNop as Byte;
+    }
+}
+
+#[test]
+fn execute_keeps_overshoot_as_debt() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsLdaAbs as Byte;
+    memory[0xFF03] = Instruction::InsNop as Byte;
+    memory[0xFF04] = Instruction::InsNop as Byte;
+
+    let cycles = cpu.execute(3, &mut memory);
+
+    assert_eq!(cycles, Ok(4));
+    assert_eq!(cpu.cycle_debt, 1);
+    assert_eq!(cpu.program_counter, 0xFF03);
+
+    let cycles = cpu.execute(3, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.cycle_debt, 0);
+    assert_eq!(cpu.program_counter, 0xFF04);
+}
+
+#[test]
+fn execute_runs_nothing_while_debt_covers_the_budget() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsJsr as Byte;
+
+    cpu.execute(1, &mut memory).unwrap();
+    assert_eq!(cpu.cycle_debt, 5);
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(0));
+    assert_eq!(cpu.cycle_debt, 3);
+    assert_eq!(cpu.instructions_retired, 1);
+}
+
+#[test]
+fn failed_execute_keeps_the_debt() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsNop as Byte;
+    memory[0xFF01] = 0x02;
+
+    cpu.cycle_debt = 3;
+
+    assert!(cpu.execute(10, &mut memory).is_err());
+    assert_eq!(cpu.cycle_debt, 3);
+}
+
+#[test]
+fn idle_cycles_leave_no_debt() {
+    let mut cpu = CPU::start_at(0xFF00).with_variant(CpuVariant::Cmos65C02);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsNop as Byte;
+    memory[0xFF01] = Instruction::InsWai.opcode();
+
+    // NOP and WAI run one cycle past the budget
+    assert_eq!(cpu.execute(4, &mut memory), Ok(5));
+    assert_eq!(cpu.cycle_debt, 1);
+
+    // Waiting pays off the debt and idles through the rest
+    assert_eq!(cpu.execute(10, &mut memory), Ok(9));
+    assert_eq!(cpu.cycle_debt, 0);
+    assert_eq!(cpu.total_cycles, 14);
+
+    assert_eq!(cpu.execute(10, &mut memory), Ok(10));
+    assert_eq!(cpu.cycle_debt, 0);
+}
+
+#[test]
+fn clock_does_not_drift_over_many_calls() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    // LDA $0000 / JMP $FF00 loop, 4 + 3 cycles never lines up with the budget
+    memory[0xFF00] = Instruction::InsLdaAbs as Byte;
+    memory[0xFF03] = Instruction::InsJmpAbs as Byte;
+    memory[0xFF04] = 0x00;
+    memory[0xFF05] = 0xFF;
+
+    let mut executed = 0;
+    for _ in 0..1000 {
+        executed += cpu.execute(5, &mut memory).unwrap();
+    }
+
+    assert_eq!(executed as u64, cpu.total_cycles);
+    assert_eq!(executed - cpu.cycle_debt, 5000);
+}
+
+#[test]
+fn counters_track_cycles_and_instructions() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    nop_program(&mut memory);
+
+    cpu.execute(10, &mut memory).unwrap();
+    cpu.step(&mut memory).unwrap();
+
+    assert_eq!(cpu.total_cycles, 12);
+    assert_eq!(cpu.instructions_retired, 6);
+}
+
+#[test]
+fn interrupts_count_cycles_but_not_instructions() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFFFA] = 0x00;
+    memory[0xFFFB] = 0xFF;
+    nop_program(&mut memory);
+
+    cpu.set_nmi(true);
+    cpu.execute(9, &mut memory).unwrap();
+
+    assert_eq!(cpu.total_cycles, 9);
+    assert_eq!(cpu.instructions_retired, 1);
+}
+
+#[test]
+fn waiting_cpu_still_counts_cycles() {
+    let mut cpu = CPU::start_at(0xFF00).with_variant(CpuVariant::Cmos65C02);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsWai.opcode();
+
+    cpu.execute(100, &mut memory).unwrap();
+
+    assert_eq!(cpu.total_cycles, 100);
+    assert_eq!(cpu.instructions_retired, 1);
+}
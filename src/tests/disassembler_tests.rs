@@ -0,0 +1,214 @@
+use crate::{
+    assembler::Assembler,
+    cpu::{CpuVariant, Word},
+    disassembler::{listing_text, DisassembledLine, Disassembler},
+    loader::LoaderError,
+    memory::Memory,
+    symbols::SymbolTable,
+};
+
+fn text(disassembler: &Disassembler, bytes: &[u8], address: Word) -> Vec<String> {
+    disassembler
+        .disassemble_bytes(bytes, address)
+        .iter()
+        .map(|line| {
+            format!("{} {}", line.mnemonic, line.operand)
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn addressing_modes_use_standard_syntax() {
+    let disassembler = Disassembler::new(CpuVariant::Nmos6502, false);
+    let bytes = [
+        0x0A, 0xEA, 0xA9, 0x01, 0xA5, 0x10, 0xB5, 0x10, 0xB6, 0x10, 0xAD, 0x34, 0x12, 0xBD, 0x34,
+        0x12, 0xB9, 0x34, 0x12, 0x6C, 0x34, 0x12, 0xA1, 0x10, 0xB1, 0x10,
+    ];
+    assert_eq!(
+        text(&disassembler, &bytes, 0x0200),
+        [
+            "ASL A",
+            "NOP",
+            "LDA #$01",
+            "LDA $10",
+            "LDA $10,X",
+            "LDX $10,Y",
+            "LDA $1234",
+            "LDA $1234,X",
+            "LDA $1234,Y",
+            "JMP ($1234)",
+            "LDA ($10,X)",
+            "LDA ($10),Y",
+        ]
+    );
+}
+
+#[test]
+fn cmos_opcodes_and_zero_page_branches() {
+    let disassembler = Disassembler::new(CpuVariant::Cmos65C02, false);
+    let lines =
+        disassembler.disassemble_bytes(&[0xB2, 0x10, 0x7C, 0x34, 0x12, 0x3F, 0x10, 0xFD], 0x0200);
+    assert_eq!(
+        text(
+            &disassembler,
+            &[0xB2, 0x10, 0x7C, 0x34, 0x12, 0x3F, 0x10, 0xFD],
+            0x0200
+        ),
+        ["LDA ($10)", "JMP ($1234,X)", "BBR3 $10,$0205"]
+    );
+    assert_eq!(lines[2].branch_target, Some(0x0205));
+}
+
+#[test]
+fn branches_show_their_target() {
+    let disassembler = Disassembler::new(CpuVariant::Nmos6502, false);
+    let lines = disassembler.disassemble_bytes(&[0xA9, 0x00, 0xF0, 0xFC, 0xD0, 0x02], 0x1000);
+    assert_eq!(lines[1].operand, "$1000");
+    assert_eq!(lines[1].branch_target, Some(0x1000));
+    assert_eq!(lines[2].branch_target, Some(0x1008));
+    assert_eq!(lines[0].branch_target, None);
+}
+
+#[test]
+fn graphics_instructions() {
+    let disassembler = Disassembler::new(CpuVariant::Nmos6502, false);
+    assert_eq!(
+        text(&disassembler, &[0x44, 0x83, 0x88, 0x43, 0x00, 0x03], 0xFF00),
+        ["DBG #$8883", "DBG $0300"]
+    );
+}
+
+#[test]
+fn undefined_and_truncated_opcodes_become_bytes() {
+    let disassembler = Disassembler::new(CpuVariant::Nmos6502, false);
+    assert_eq!(
+        text(&disassembler, &[0x02, 0xEA, 0xAD, 0x34], 0x1000),
+        [".byte $02", "NOP", ".byte $AD", ".byte $34"]
+    );
+    assert_eq!(
+        text(
+            &Disassembler::new(CpuVariant::Nmos6502, true),
+            &[0xA7, 0x10],
+            0x1000
+        ),
+        ["LAX $10"]
+    );
+}
+
+#[test]
+fn symbols_replace_addresses() {
+    let mut symbols = SymbolTable::new();
+    symbols.insert("start", 0x1000);
+    symbols.insert("counter", 0x0042);
+    symbols.insert("table", 0x2000);
+    let disassembler = Disassembler::new(CpuVariant::Nmos6502, false).with_symbols(&symbols);
+    let lines = disassembler.disassemble_bytes(
+        &[
+            0xE6, 0x42, 0xBD, 0x03, 0x20, 0xAD, 0x00, 0x30, 0xD0, 0xF6, 0xA9, 0x42,
+        ],
+        0x1000,
+    );
+    assert_eq!(lines[0].label.as_deref(), Some("start"));
+    assert_eq!(lines[0].operand, "counter");
+    assert_eq!(lines[1].label, None);
+    assert_eq!(lines[1].operand, "table+3,X");
+    assert_eq!(lines[2].operand, "$3000");
+    assert_eq!(lines[3].operand, "start");
+    assert_eq!(lines[4].operand, "#$42");
+}
+
+#[test]
+fn disassembles_memory_ranges() {
+    let mut memory = Memory::initialize();
+    memory[0x0300_u16] = 0xA2;
+    memory[0x0301_u16] = 0x14;
+    memory[0x0302_u16] = 0xE8;
+    let lines =
+        Disassembler::new(CpuVariant::Nmos6502, false).disassemble(&memory, 0x0300..=0x0302);
+    assert_eq!(
+        lines,
+        [
+            DisassembledLine {
+                address: 0x0300,
+                bytes: vec![0xA2, 0x14],
+                label: None,
+                mnemonic: "LDX",
+                operand: "#$14".to_string(),
+                branch_target: None,
+            },
+            DisassembledLine {
+                address: 0x0302,
+                bytes: vec![0xE8],
+                label: None,
+                mnemonic: "INX",
+                operand: String::new(),
+                branch_target: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn disassembles_prg_files() {
+    let disassembler = Disassembler::new(CpuVariant::Nmos6502, false);
+    let prg = [
+        0x00, 0x10, 0xA9, 0x00, 0x18, 0x69, 0x08, 0xC9, 0x18, 0xD0, 0xFA, 0xA2, 0x14,
+    ];
+    assert_eq!(
+        listing_text(&disassembler.disassemble_prg(&prg).unwrap()),
+        "\
+1000  A9 00               LDA #$00
+1002  18                  CLC
+1003  69 08               ADC #$08
+1005  C9 18               CMP #$18
+1007  D0 FA               BNE $1003
+1009  A2 14               LDX #$14
+"
+    );
+    assert_eq!(
+        disassembler.disassemble_prg(&[0x00]),
+        Err(LoaderError::ImageTooShort)
+    );
+}
+
+#[test]
+fn listing_reassembles_to_the_same_bytes() {
+    let source = "\
+*=$1000
+start
+    ldx #0
+loop
+    lda $0300,x
+    sta $0200,x
+    inx
+    cpx #4
+    bne loop
+    beq done
+    jmp (start)
+done
+    jmp start
+";
+    let assembler = Assembler::new(CpuVariant::Nmos6502);
+    let assembly = assembler.assemble(source).unwrap();
+    let mut symbols = SymbolTable::new();
+    for (name, address) in &assembly.labels {
+        symbols.insert(name, *address);
+    }
+    let prg = assembly.to_prg();
+    let lines = Disassembler::new(CpuVariant::Nmos6502, false)
+        .with_symbols(&symbols)
+        .disassemble_prg(&prg)
+        .unwrap();
+    let mut reassembled = String::from("*=$1000\n");
+    for line in &lines {
+        reassembled += &format!(
+            "{} {} {}\n",
+            line.label.as_deref().unwrap_or(""),
+            line.mnemonic,
+            line.operand
+        );
+    }
+    assert_eq!(assembler.assemble(&reassembled).unwrap().to_prg(), prg);
+}
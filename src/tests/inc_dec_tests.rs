@@ -18,7 +18,7 @@ fn verify_unmodified_flags(cpu: &CPU, cpu_copy: &CPU) {
 // INX
 #[test]
 fn inx_can_increment_a_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -42,7 +42,7 @@ fn inx_can_increment_a_zero_value() {
 
 #[test]
 fn inx_can_increment_255() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -66,7 +66,7 @@ fn inx_can_increment_255() {
 
 #[test]
 fn inx_can_increment_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -91,7 +91,7 @@ fn inx_can_increment_negative_value() {
 // DEX
 #[test]
 fn dex_can_decrement_non_zero_non_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -115,7 +115,7 @@ fn dex_can_decrement_non_zero_non_negative_value() {
 
 #[test]
 fn dex_can_decrement_a_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -139,7 +139,7 @@ fn dex_can_decrement_a_zero_value() {
 
 #[test]
 fn dex_can_decrement_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -164,7 +164,7 @@ fn dex_can_decrement_negative_value() {
 // INY
 #[test]
 fn iny_can_increment_a_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -188,7 +188,7 @@ fn iny_can_increment_a_zero_value() {
 
 #[test]
 fn iny_can_increment_255() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -212,7 +212,7 @@ fn iny_can_increment_255() {
 
 #[test]
 fn iny_can_increment_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -237,7 +237,7 @@ fn iny_can_increment_negative_value() {
 // DEY
 #[test]
 fn dey_can_decrement_non_zero_non_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -261,7 +261,7 @@ fn dey_can_decrement_non_zero_non_negative_value() {
 
 #[test]
 fn dey_can_decrement_a_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -285,7 +285,7 @@ fn dey_can_decrement_a_zero_value() {
 
 #[test]
 fn dey_can_decrement_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -310,7 +310,7 @@ fn dey_can_decrement_negative_value() {
 // DEC
 #[test]
 fn dec_zero_page_can_decrement_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -334,7 +334,7 @@ fn dec_zero_page_can_decrement_value() {
 
 #[test]
 fn dec_zero_page_x_can_decrement_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -360,7 +360,7 @@ fn dec_zero_page_x_can_decrement_value() {
 
 #[test]
 fn dec_abs_can_decrement_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -385,7 +385,7 @@ fn dec_abs_can_decrement_value() {
 
 #[test]
 fn dec_abs_x_can_decrement_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -413,7 +413,7 @@ fn dec_abs_x_can_decrement_value() {
 // INC
 #[test]
 fn inc_zero_page_can_decrement_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -437,7 +437,7 @@ fn inc_zero_page_can_decrement_value() {
 
 #[test]
 fn inc_zero_page_x_can_decrement_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -463,7 +463,7 @@ fn inc_zero_page_x_can_decrement_value() {
 
 #[test]
 fn inc_abs_can_decrement_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -488,7 +488,7 @@ fn inc_abs_can_decrement_value() {
 
 #[test]
 fn inc_abs_x_can_decrement_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
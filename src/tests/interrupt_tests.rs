@@ -0,0 +1,159 @@
+use crate::{
+    cpu::{Byte, ProcessorFlags, CPU},
+    instructions::Instruction,
+    memory::Memory,
+};
+
+fn setup_vectors(memory: &mut Memory) {
+    // IRQ handler at 0x8000, NMI handler at 0x9000
+    memory[0xFFFE] = 0x00;
+    memory[0xFFFF] = 0x80;
+    memory[0xFFFA] = 0x00;
+    memory[0xFFFB] = 0x90;
+}
+
+// IRQ
+#[test]
+fn irq_will_load_program_counter_from_irq_vector() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    setup_vectors(&mut memory);
+
+    cpu.set_irq(true);
+
+    let cycles = cpu.execute(7, &mut memory);
+
+    assert_eq!(cycles, Ok(7));
+    assert_eq!(cpu.program_counter, 0x8000);
+    assert!(cpu.status.interupt_disable);
+}
+
+#[test]
+fn irq_pushes_program_counter_and_status_with_break_clear() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    setup_vectors(&mut memory);
+
+    cpu.status.carry = true;
+    cpu.set_irq(true);
+
+    cpu.execute(7, &mut memory).unwrap();
+
+    assert_eq!(cpu.stack_pointer, 0xFC);
+    assert_eq!(memory[0x01FF_u16], 0xFF);
+    assert_eq!(memory[0x01FE_u16], 0x00);
+    let pushed_status = ProcessorFlags::from(memory[0x01FD_u16]);
+    assert!(!pushed_status.break_command);
+    assert!(pushed_status.unused);
+    assert!(pushed_status.carry);
+}
+
+#[test]
+fn irq_is_ignored_when_interrupts_are_disabled() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    setup_vectors(&mut memory);
+
+    memory[0xFF00] = Instruction::InsNop as Byte;
+    cpu.status.interupt_disable = true;
+    cpu.set_irq(true);
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.program_counter, 0xFF01);
+    assert_eq!(cpu.stack_pointer, 0xFF);
+}
+
+#[test]
+fn irq_is_taken_after_cli() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    setup_vectors(&mut memory);
+
+    memory[0xFF00] = Instruction::InsCli as Byte;
+    cpu.status.interupt_disable = true;
+    cpu.set_irq(true);
+
+    let cycles = cpu.execute(9, &mut memory);
+
+    assert_eq!(cycles, Ok(9));
+    assert_eq!(cpu.program_counter, 0x8000);
+}
+
+#[test]
+fn rti_returns_from_irq_handler() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    setup_vectors(&mut memory);
+
+    memory[0x8000] = Instruction::InsRti as Byte;
+    memory[0xFF00] = Instruction::InsNop as Byte;
+    cpu.status.carry = true;
+    cpu.set_irq(true);
+
+    cpu.execute(7, &mut memory).unwrap();
+    cpu.set_irq(false);
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(cpu.program_counter, 0xFF00);
+    assert_eq!(cpu.stack_pointer, 0xFF);
+    assert!(cpu.status.carry);
+    assert!(!cpu.status.interupt_disable);
+}
+
+// NMI
+#[test]
+fn nmi_will_load_program_counter_from_nmi_vector_even_when_interrupts_are_disabled() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    setup_vectors(&mut memory);
+
+    cpu.status.interupt_disable = true;
+    cpu.set_nmi(true);
+
+    let cycles = cpu.execute(7, &mut memory);
+
+    assert_eq!(cycles, Ok(7));
+    assert_eq!(cpu.program_counter, 0x9000);
+    let pushed_status = ProcessorFlags::from(memory[0x01FD_u16]);
+    assert!(!pushed_status.break_command);
+}
+
+#[test]
+fn nmi_is_edge_triggered() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    setup_vectors(&mut memory);
+
+    memory[0x9000] = Instruction::InsNop as Byte;
+    cpu.set_nmi(true);
+    cpu.execute(7, &mut memory).unwrap();
+
+    cpu.set_nmi(true);
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.program_counter, 0x9001);
+
+    cpu.set_nmi(false);
+    cpu.set_nmi(true);
+    cpu.execute(7, &mut memory).unwrap();
+
+    assert_eq!(cpu.program_counter, 0x9000);
+}
+
+#[test]
+fn nmi_takes_priority_over_irq() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    setup_vectors(&mut memory);
+
+    cpu.set_irq(true);
+    cpu.set_nmi(true);
+
+    cpu.execute(7, &mut memory).unwrap();
+
+    assert_eq!(cpu.program_counter, 0x9000);
+}
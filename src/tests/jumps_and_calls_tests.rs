@@ -1,5 +1,5 @@
 use crate::{
-    cpu::{Byte, CPU},
+    cpu::{Byte, CpuVariant, CPU},
     instructions::{Instruction, InstructionsError},
     memory::Memory,
 };
@@ -16,7 +16,7 @@ fn verify_unmodified_flags(cpu: &CPU, cpu_copy: &CPU) {
 
 #[test]
 fn can_jump_to_subroutine_and_jump_back() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     memory[0xFF00] = Instruction::InsJsr as Byte;
@@ -40,7 +40,7 @@ fn can_jump_to_subroutine_and_jump_back() {
 
 #[test]
 fn jsr_does_not_affect_processor_status() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     memory[0xFF00] = Instruction::InsJsr as Byte;
@@ -63,7 +63,7 @@ fn jsr_does_not_affect_processor_status() {
 
 #[test]
 fn rts_does_not_affect_processor_status() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     memory[0xFF00] = Instruction::InsJsr as Byte;
@@ -85,7 +85,7 @@ fn rts_does_not_affect_processor_status() {
 
 #[test]
 fn jmp_absolute_can_jump_to_new_location() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     memory[0xFF00] = Instruction::InsJmpAbs as Byte;
@@ -106,7 +106,7 @@ fn jmp_absolute_can_jump_to_new_location() {
 
 #[test]
 fn jmp_indirect_can_jump_to_new_location() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     memory[0xFF00] = Instruction::InsJmpInd as Byte;
@@ -127,3 +127,57 @@ fn jmp_indirect_can_jump_to_new_location() {
 
     verify_unmodified_flags(&cpu, &cpu_copy);
 }
+
+#[test]
+fn jmp_indirect_wraps_pointer_within_page() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsJmpInd as Byte;
+    memory[0xFF01] = 0xFF;
+    memory[0xFF02] = 0x80;
+    memory[0x80FF] = 0x00;
+    memory[0x8000] = 0x90;
+    memory[0x8100] = 0x70;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(cpu.program_counter, 0x9000);
+}
+
+#[test]
+fn jmp_indirect_with_pointer_at_end_of_memory_wraps_within_page() {
+    let mut cpu = CPU::start_at(0x1000);
+    let mut memory = Memory::initialize();
+
+    memory[0x1000] = Instruction::InsJmpInd as Byte;
+    memory[0x1001] = 0xFF;
+    memory[0x1002] = 0xFF;
+    memory[0xFFFF] = 0x00;
+    memory[0xFF00] = 0x90;
+    memory[0x0000] = 0x70;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(cpu.program_counter, 0x9000);
+}
+
+#[test]
+fn jmp_indirect_on_65c02_reads_pointer_across_end_of_memory() {
+    let mut cpu = CPU::start_at(0x1000).with_variant(CpuVariant::Cmos65C02);
+    let mut memory = Memory::initialize();
+
+    memory[0x1000] = Instruction::InsJmpInd as Byte;
+    memory[0x1001] = 0xFF;
+    memory[0x1002] = 0xFF;
+    memory[0xFFFF] = 0x00;
+    memory[0xFF00] = 0x90;
+    memory[0x0000] = 0x70;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(cpu.program_counter, 0x7000);
+}
@@ -23,7 +23,7 @@ fn verify_unmodified_flags(cpu: &CPU, cpu_copy: &CPU) {
 
 #[test]
 fn cpu_does_nothing_zero_cycles() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
     let cycles = cpu.execute(0, &mut memory);
 
@@ -32,7 +32,7 @@ fn cpu_does_nothing_zero_cycles() {
 
 /* #[test]
 fn executing_bad_instruction_does_not_loop() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     memory[0xFFFC] = 0x0;
@@ -44,7 +44,7 @@ fn executing_bad_instruction_does_not_loop() {
 } */
 
 fn test_loading_register_immediate(opcode: Instruction, register_to_test: RegisterToTest) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     memory[0xFFFC] = opcode as Byte;
@@ -68,7 +68,7 @@ fn test_loading_register_immediate(opcode: Instruction, register_to_test: Regist
 }
 
 fn test_loading_register_zero_page(opcode: Instruction, register_to_test: RegisterToTest) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     memory[0xFFFC] = opcode as Byte;
@@ -97,7 +97,7 @@ fn test_loading_register_zero_page_plus_register(
     register_to_test: RegisterToTest,
     register_being_added: RegisterToTest,
 ) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     match register_being_added {
@@ -132,7 +132,7 @@ fn test_loading_register_zero_page_plus_register_when_wrap(
     register_to_test: RegisterToTest,
     register_being_added: RegisterToTest,
 ) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     match register_being_added {
@@ -163,7 +163,7 @@ fn test_loading_register_zero_page_plus_register_when_wrap(
 }
 
 fn test_loading_register_absolute(opcode: Instruction, register_to_test: RegisterToTest) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     memory[0xFFFC] = opcode as Byte;
@@ -193,7 +193,7 @@ fn test_loading_register_absolute_plus_register(
     register_to_test: RegisterToTest,
     register_being_added: RegisterToTest,
 ) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     match register_being_added {
@@ -229,7 +229,7 @@ fn test_loading_register_absolute_plus_register_when_crossing_page_boundary(
     register_to_test: RegisterToTest,
     register_being_added: RegisterToTest,
 ) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     match register_being_added {
@@ -267,7 +267,7 @@ fn lda_immediate_can_load_value() {
 
 #[test]
 fn lda_immediate_can_affect_zero_flag() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0x44;
@@ -353,7 +353,7 @@ fn lda_absolute_y_can_load_value_when_crossing_page_boundary() {
 
 #[test]
 fn lda_indirect_x_can_load_value() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.x_register = 0x04;
@@ -379,7 +379,7 @@ fn lda_indirect_x_can_load_value() {
 
 #[test]
 fn lda_indirect_y_can_load_value() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.y_register = 0x04;
@@ -405,7 +405,7 @@ fn lda_indirect_y_can_load_value() {
 
 #[test]
 fn lda_indirect_y_can_load_value_when_crossing_page_boundary() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.y_register = 0xFF;
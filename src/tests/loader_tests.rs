@@ -0,0 +1,218 @@
+use crate::{
+    bus::Bus,
+    cpu::Byte,
+    loader::{
+        load_file, parse_intel_hex, parse_prg, parse_raw, parse_srecord, Format, Image,
+        LoaderError, Segment,
+    },
+    memory::Memory,
+};
+
+const INTEL_HEX: &str = "\
+:05020000A9428D003051
+:01020500EA0E
+:02FFFC00000201
+:0400000500000200F5
+:00000001FF
+";
+
+const S_RECORD: &str = "\
+S00600004844521B
+S1060200A942EA22
+S2060010001122B6
+S5030002FA
+S9030200FA
+";
+
+#[test]
+fn intel_hex_merges_contiguous_records_into_segments() {
+    let image = parse_intel_hex(INTEL_HEX).unwrap();
+
+    assert_eq!(
+        image.segments,
+        vec![
+            Segment {
+                address: 0x0200,
+                data: vec![0xA9, 0x42, 0x8D, 0x00, 0x30, 0xEA]
+            },
+            Segment {
+                address: 0xFFFC,
+                data: vec![0x00, 0x02]
+            },
+        ]
+    );
+    assert_eq!(image.entry, Some(0x0200));
+}
+
+#[test]
+fn intel_hex_loads_into_memory() {
+    let mut memory = Memory::initialize();
+
+    let start = parse_intel_hex(INTEL_HEX).unwrap().load_into(&mut memory);
+
+    assert_eq!(start, Some(0x0200));
+    assert_eq!(memory[0x0205_u16], 0xEA);
+    assert_eq!(memory[0xFFFD_u16], 0x02);
+}
+
+#[test]
+fn intel_hex_reports_checksum_error_with_line() {
+    let text = ":05020000A9428D003051\n\n:01020500EA0F\n";
+
+    assert_eq!(
+        parse_intel_hex(text),
+        Err(LoaderError::ChecksumMismatch {
+            line: 3,
+            expected: 0x0E,
+            found: 0x0F
+        })
+    );
+}
+
+#[test]
+fn intel_hex_reports_format_errors_with_line() {
+    assert_eq!(parse_intel_hex(":00000001FF\n"), Ok(Image::default()));
+    assert_eq!(
+        parse_intel_hex("05020000A9428D003051\n"),
+        Err(LoaderError::MissingStartCode { line: 1 })
+    );
+    assert_eq!(
+        parse_intel_hex(":01020500EA0E\n:0102050XEA0E\n"),
+        Err(LoaderError::InvalidHexDigit { line: 2 })
+    );
+    assert_eq!(
+        parse_intel_hex(":02020500EA0E\n"),
+        Err(LoaderError::LengthMismatch { line: 1 })
+    );
+    assert_eq!(
+        parse_intel_hex(":01020500EA0E\n"),
+        Err(LoaderError::MissingEndRecord)
+    );
+}
+
+#[test]
+fn intel_hex_extended_addresses_must_stay_in_range() {
+    assert_eq!(
+        parse_intel_hex(":020000040001F9\n:010010007778\n:00000001FF\n"),
+        Err(LoaderError::AddressOutOfRange {
+            line: 2,
+            address: 0x10010
+        })
+    );
+    assert_eq!(
+        parse_intel_hex(":02000004FFFFFC\n:02FFFF000102FD\n:00000001FF\n"),
+        Err(LoaderError::AddressOutOfRange {
+            line: 2,
+            address: 0xFFFFFFFF
+        })
+    );
+    assert_eq!(
+        parse_intel_hex(":020000021000EC\n:010010007778\n:00000001FF\n"),
+        Err(LoaderError::AddressOutOfRange {
+            line: 2,
+            address: 0x10010
+        })
+    );
+}
+
+#[test]
+fn srecord_parses_segments_and_entry() {
+    let image = parse_srecord(S_RECORD).unwrap();
+
+    assert_eq!(
+        image.segments,
+        vec![
+            Segment {
+                address: 0x0200,
+                data: vec![0xA9, 0x42, 0xEA]
+            },
+            Segment {
+                address: 0x1000,
+                data: vec![0x11, 0x22]
+            },
+        ]
+    );
+    assert_eq!(image.entry, Some(0x0200));
+}
+
+#[test]
+fn srecord_reports_errors_with_line() {
+    assert_eq!(
+        parse_srecord("S1060200A942EA22\nS1071000112233443F\n"),
+        Err(LoaderError::ChecksumMismatch {
+            line: 2,
+            expected: 0x3E,
+            found: 0x3F
+        })
+    );
+    assert_eq!(
+        parse_srecord("S1060200A942EA22\nS3060001000011E7\n"),
+        Err(LoaderError::AddressOutOfRange {
+            line: 2,
+            address: 0x10000
+        })
+    );
+    assert_eq!(
+        parse_srecord("S307FFFFFFFF0102F9\n"),
+        Err(LoaderError::AddressOutOfRange {
+            line: 1,
+            address: 0xFFFFFFFF
+        })
+    );
+    assert_eq!(
+        parse_srecord("S4030002FA\n"),
+        Err(LoaderError::UnsupportedRecordType {
+            line: 1,
+            record_type: 4
+        })
+    );
+    assert_eq!(
+        parse_srecord(":1060200A942EA22\n"),
+        Err(LoaderError::MissingStartCode { line: 1 })
+    );
+}
+
+#[test]
+fn raw_binary_loads_at_base() {
+    let mut memory = Memory::initialize();
+
+    let start = parse_raw(&[0x11, 0x22], 0xFF00)
+        .unwrap()
+        .load_into(&mut memory);
+
+    assert_eq!(start, Some(0xFF00));
+    assert_eq!(memory[0xFF01_u16], 0x22);
+    assert_eq!(
+        parse_raw(&[0x11, 0x22], 0xFFFF),
+        Err(LoaderError::ImagePastEndOfMemory {
+            start: 0xFFFF,
+            length: 2
+        })
+    );
+}
+
+#[test]
+fn prg_uses_header_as_load_address() {
+    let image = parse_prg(&[0x00, 0x10, 0xEA, 0xEA]).unwrap();
+
+    assert_eq!(image.start_address(), Some(0x1000));
+    assert_eq!(image.segments[0].data, vec![0xEA, 0xEA]);
+    assert_eq!(parse_prg(&[0x00]), Err(LoaderError::ImageTooShort));
+}
+
+#[test]
+fn load_file_reads_format_from_disk() {
+    let path = std::env::temp_dir().join(format!("loader_{}.srec", std::process::id()));
+    std::fs::write(&path, S_RECORD).unwrap();
+    let mut memory = Memory::initialize();
+
+    let result = load_file(&path, Format::SRecord, &mut memory);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(result, Ok(Some(0x0200)));
+    assert_eq!(memory.read(0x1001), 0x22);
+    assert_eq!(
+        load_file("does/not/exist.hex", Format::IntelHex, &mut memory),
+        Err(LoaderError::Io(std::io::ErrorKind::NotFound))
+    );
+}
@@ -59,7 +59,7 @@ static COMPARISON_LOOP_PROGRAM: [Byte; 13] = [
 
 #[test]
 fn test_loading_program_into_memory() {
-    let mut cpu = CPU::reset(Some(0x0FFF));
+    let mut cpu = CPU::start_at(0x0FFF);
     let mut memory = Memory::initialize();
 
     cpu.load_program(&TEST_PROGRAM, 14, &mut memory);
@@ -72,7 +72,7 @@ fn test_loading_program_into_memory() {
 
 #[test]
 fn test_executing_program() {
-    let mut cpu = CPU::reset(Some(0x0FFF));
+    let mut cpu = CPU::start_at(0x0FFF);
     let mut memory = Memory::initialize();
 
     let start_address = cpu.load_program(&TEST_PROGRAM, 14, &mut memory);
@@ -87,7 +87,7 @@ fn test_executing_program() {
 
 #[test]
 fn test_executing_inc_memory_program() {
-    let mut cpu = CPU::reset(Some(0x0FFF));
+    let mut cpu = CPU::start_at(0x0FFF);
     let mut memory = Memory::initialize();
 
     let start_address = cpu.load_program(&INC_MEMORY_PROGRAM, 14, &mut memory);
@@ -102,7 +102,7 @@ fn test_executing_inc_memory_program() {
 
 #[test]
 fn test_executing_beq_loop_program() {
-    let mut cpu = CPU::reset(Some(0x0FFF));
+    let mut cpu = CPU::start_at(0x0FFF);
     let mut memory = Memory::initialize();
 
     let start_address = cpu.load_program(&BEQ_LOOP_PROGRAM, 6, &mut memory);
@@ -117,7 +117,7 @@ fn test_executing_beq_loop_program() {
 
 /* #[test]
 fn test_executing_comparison_loop_program() {
-    let mut cpu = CPU::reset(Some(0x0FFF));
+    let mut cpu = CPU::start_at(0x0FFF);
     let mut memory = Memory::initialize();
 
     let start_address = cpu.load_program(&COMPARISON_LOOP_PROGRAM, 13, &mut memory);
@@ -138,7 +138,7 @@ fn test_executing_comparison_loop_program() {
 
 // #[test]
 fn test_6502_test_program() {
-    let mut cpu = CPU::reset(Some(0x0FFF));
+    let mut cpu = CPU::start_at(0x0FFF);
     let mut memory = Memory::initialize();
 
     let start_address = cpu.load_program(&BEQ_LOOP_PROGRAM, 6, &mut memory);
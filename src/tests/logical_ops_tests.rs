@@ -31,7 +31,7 @@ fn do_logical_op(lhs: Byte, rhs: Byte, logical_operator: LogicalOperator) -> Byt
 }
 
 fn test_logical_op_on_a_register_immediate(logical_operator: LogicalOperator) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -63,7 +63,7 @@ fn test_logical_op_on_a_register_immediate(logical_operator: LogicalOperator) {
 }
 
 fn test_logical_op_on_a_register_zero_page(logical_operator: LogicalOperator) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -96,7 +96,7 @@ fn test_logical_op_on_a_register_zero_page(logical_operator: LogicalOperator) {
 }
 
 fn test_logical_op_on_a_register_zero_page_x(logical_operator: LogicalOperator) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -130,7 +130,7 @@ fn test_logical_op_on_a_register_zero_page_x(logical_operator: LogicalOperator)
 }
 
 fn test_logical_op_on_a_register_absolute(logical_operator: LogicalOperator) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -164,7 +164,7 @@ fn test_logical_op_on_a_register_absolute(logical_operator: LogicalOperator) {
 }
 
 fn test_logical_op_on_a_register_absolute_x(logical_operator: LogicalOperator) {
-    let mut cpu = CPU::reset(Some(0xFFF0));
+    let mut cpu = CPU::start_at(0xFFF0);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -201,7 +201,7 @@ fn test_logical_op_on_a_register_absolute_x(logical_operator: LogicalOperator) {
 fn test_logical_op_on_a_register_absolute_x_when_crossing_page_boundary(
     logical_operator: LogicalOperator,
 ) {
-    let mut cpu = CPU::reset(Some(0xFFF0));
+    let mut cpu = CPU::start_at(0xFFF0);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -236,7 +236,7 @@ fn test_logical_op_on_a_register_absolute_x_when_crossing_page_boundary(
 }
 
 fn test_logical_op_on_a_register_absolute_y(logical_operator: LogicalOperator) {
-    let mut cpu = CPU::reset(Some(0xFFF0));
+    let mut cpu = CPU::start_at(0xFFF0);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -273,7 +273,7 @@ fn test_logical_op_on_a_register_absolute_y(logical_operator: LogicalOperator) {
 fn test_logical_op_on_a_register_absolute_y_when_crossing_page_boundary(
     logical_operator: LogicalOperator,
 ) {
-    let mut cpu = CPU::reset(Some(0xFFF0));
+    let mut cpu = CPU::start_at(0xFFF0);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -308,7 +308,7 @@ fn test_logical_op_on_a_register_absolute_y_when_crossing_page_boundary(
 }
 
 fn test_logical_op_on_a_register_indirect_x(logical_operator: LogicalOperator) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -344,7 +344,7 @@ fn test_logical_op_on_a_register_indirect_x(logical_operator: LogicalOperator) {
 }
 
 fn test_logical_op_on_a_register_indirect_x_wrapping_zero_page(logical_operator: LogicalOperator) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -380,7 +380,7 @@ fn test_logical_op_on_a_register_indirect_x_wrapping_zero_page(logical_operator:
 }
 
 fn test_logical_op_on_a_register_indirect_x_split_by_zero_page(logical_operator: LogicalOperator) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -416,7 +416,7 @@ fn test_logical_op_on_a_register_indirect_x_split_by_zero_page(logical_operator:
 }
 
 fn test_logical_op_on_a_register_indirect_y(logical_operator: LogicalOperator) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -454,7 +454,7 @@ fn test_logical_op_on_a_register_indirect_y(logical_operator: LogicalOperator) {
 fn test_logical_op_on_a_register_indirect_y_when_crossing_page_boundary(
     logical_operator: LogicalOperator,
 ) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -690,7 +690,7 @@ fn test_logical_op_ora_on_a_register_indirect_y_when_wrapping_page_boundary() {
 // BIT
 #[test]
 fn test_bit_zero_page() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -716,7 +716,7 @@ fn test_bit_zero_page() {
 
 #[test]
 fn test_bit_zero_page_modifies_zero_flag() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -742,7 +742,7 @@ fn test_bit_zero_page_modifies_zero_flag() {
 
 #[test]
 fn test_bit_zero_page_modifies_zero_negative_overflow_flag() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0x33;
@@ -768,7 +768,7 @@ fn test_bit_zero_page_modifies_zero_negative_overflow_flag() {
 
 #[test]
 fn test_bit_absolute() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -795,7 +795,7 @@ fn test_bit_absolute() {
 
 #[test]
 fn test_bit_absolute_modifies_zero_flag() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0xCC;
@@ -822,7 +822,7 @@ fn test_bit_absolute_modifies_zero_flag() {
 
 #[test]
 fn test_bit_absolute_modifies_zero_negative_overflow_flag() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0x33;
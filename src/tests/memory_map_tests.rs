@@ -0,0 +1,360 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    bus::{Bus, BusFault},
+    cpu::{Byte, Word, CPU},
+    instructions::{Instruction, InstructionsError},
+    memory::Memory,
+    memory_map::{Device, MemoryMap, MemoryMapError, RomWritePolicy},
+    serial_port::SerialPort,
+};
+
+/// Remembers the offsets it was accessed at.
+#[derive(Default)]
+struct ProbeDevice {
+    reads: Vec<Word>,
+    writes: Vec<(Word, Byte)>,
+}
+
+impl Device for ProbeDevice {
+    fn read(&mut self, offset: Word) -> Byte {
+        self.reads.push(offset);
+        0xAA
+    }
+
+    fn write(&mut self, offset: Word, value: Byte) {
+        self.writes.push((offset, value));
+    }
+
+    fn peek(&self, offset: Word) -> Byte {
+        0xAA
+    }
+}
+
+#[test]
+fn unmapped_addresses_use_ram() {
+    let mut bus = MemoryMap::new();
+
+    bus.write(0x1234, 0x42);
+
+    assert_eq!(bus.read(0x1234), 0x42);
+    assert_eq!(bus.ram[0x1234_u16], 0x42);
+}
+
+#[test]
+fn mapped_region_is_routed_to_device_with_relative_offset() {
+    let mut bus = MemoryMap::new();
+    let probe = Rc::new(RefCell::new(ProbeDevice::default()));
+    bus.map(0xD000..=0xD00F, probe.clone()).unwrap();
+
+    bus.write(0xD003, 0x42);
+    let value = bus.read(0xD00F);
+    bus.write(0xD010, 0x99);
+
+    assert_eq!(value, 0xAA);
+    assert_eq!(probe.borrow().writes, vec![(0x0003, 0x42)]);
+    assert_eq!(probe.borrow().reads, vec![0x000F]);
+    assert_eq!(bus.ram[0xD003_u16], 0x00);
+    assert_eq!(bus.ram[0xD010_u16], 0x99);
+}
+
+#[test]
+fn peek_does_not_reach_device_read() {
+    let mut bus = MemoryMap::new();
+    let probe = Rc::new(RefCell::new(ProbeDevice::default()));
+    bus.map(0xD000..=0xD00F, probe.clone()).unwrap();
+
+    assert_eq!(bus.peek(0xD000), 0xAA);
+    assert!(probe.borrow().reads.is_empty());
+}
+
+#[test]
+fn overlapping_regions_are_rejected() {
+    let mut bus = MemoryMap::new();
+    bus.map(0xD000..=0xD0FF, ProbeDevice::default()).unwrap();
+
+    let result = bus.map(0xD0F0..=0xD10F, ProbeDevice::default());
+
+    assert_eq!(
+        result,
+        Err(MemoryMapError::RegionOverlaps {
+            start: 0xD000,
+            end: 0xD0FF
+        })
+    );
+}
+
+#[test]
+fn empty_regions_are_rejected() {
+    let mut bus = MemoryMap::new();
+
+    #[allow(clippy::reversed_empty_ranges)]
+    let result = bus.map(0xD010..=0xD000, ProbeDevice::default());
+
+    assert_eq!(
+        result,
+        Err(MemoryMapError::EmptyRegion {
+            start: 0xD010,
+            end: 0xD000
+        })
+    );
+}
+
+#[test]
+fn rom_ignores_writes() {
+    let mut bus = MemoryMap::new();
+    bus.map_rom(0xF000, &[0x11, 0x22, 0x33], RomWritePolicy::Ignore)
+        .unwrap();
+
+    bus.write(0xF001, 0x99);
+
+    assert_eq!(bus.read(0xF001), 0x22);
+    assert_eq!(bus.read(0xF003), 0x00);
+}
+
+#[test]
+fn rom_past_end_of_memory_is_rejected() {
+    let mut bus = MemoryMap::new();
+
+    let result = bus.map_rom(0xFFFF, &[0x11, 0x22], RomWritePolicy::Ignore);
+
+    assert_eq!(
+        result,
+        Err(MemoryMapError::RegionPastEndOfMemory {
+            start: 0xFFFF,
+            length: 2
+        })
+    );
+}
+
+#[test]
+fn cpu_write_to_ignoring_rom_is_dropped() {
+    let mut bus = MemoryMap::new();
+    bus.map_rom(
+        0xFFFA,
+        &[0x00, 0x80, 0x00, 0x80, 0x00, 0x80],
+        RomWritePolicy::Ignore,
+    )
+    .unwrap();
+    bus.ram[0x0200_u16] = Instruction::InsStaAbs as Byte;
+    bus.ram[0x0201_u16] = 0xFC;
+    bus.ram[0x0202_u16] = 0xFF;
+    let mut cpu = CPU::start_at(0x0200);
+    cpu.a_register = 0x42;
+
+    let result = cpu.execute(4, &mut bus);
+
+    assert_eq!(result, Ok(4));
+    assert_eq!(bus.read(0xFFFC), 0x00);
+    assert_eq!(bus.ram[0xFFFC_u16], 0x00);
+}
+
+#[test]
+fn cpu_write_to_rom_faults_with_program_counter_and_address() {
+    let mut bus = MemoryMap::new();
+    bus.map_rom(
+        0xFFFA,
+        &[0x00, 0x80, 0x00, 0x80, 0x00, 0x80],
+        RomWritePolicy::Fault,
+    )
+    .unwrap();
+    bus.ram[0x0200_u16] = Instruction::InsNop as Byte;
+    bus.ram[0x0201_u16] = Instruction::InsStaAbs as Byte;
+    bus.ram[0x0202_u16] = 0xFD;
+    bus.ram[0x0203_u16] = 0xFF;
+    bus.ram[0x0204_u16] = Instruction::InsNop as Byte;
+    let mut cpu = CPU::start_at(0x0200);
+    cpu.a_register = 0x42;
+
+    let result = cpu.execute(100, &mut bus);
+
+    assert_eq!(
+        result,
+        Err(InstructionsError::WriteToRom {
+            program_counter: 0x0201,
+            address: 0xFFFD,
+            value: 0x42
+        })
+    );
+    assert_eq!(cpu.program_counter, 0x0204);
+    assert_eq!(bus.read(0xFFFD), 0x80);
+    assert_eq!(bus.take_fault(), None);
+}
+
+#[test]
+fn step_reports_rom_write_fault() {
+    let mut bus = MemoryMap::new();
+    bus.map_rom(0xF000, &[0xEA], RomWritePolicy::Fault).unwrap();
+    bus.ram[0x0200_u16] = Instruction::InsIncZp as Byte;
+    bus.ram[0x0201_u16] = 0x10;
+    bus.ram[0x0202_u16] = Instruction::InsIncAbs as Byte;
+    bus.ram[0x0203_u16] = 0x00;
+    bus.ram[0x0204_u16] = 0xF0;
+    let mut cpu = CPU::start_at(0x0200);
+
+    assert!(cpu.step(&mut bus).is_ok());
+    assert_eq!(
+        cpu.step(&mut bus),
+        Err(InstructionsError::WriteToRom {
+            program_counter: 0x0202,
+            address: 0xF000,
+            value: 0xEB
+        })
+    );
+    assert_eq!(bus.read(0xF000), 0xEA);
+}
+
+#[test]
+fn each_rom_region_has_its_own_write_policy() {
+    let mut bus = MemoryMap::new();
+    bus.map_rom(0xE000, &[0x11], RomWritePolicy::Ignore)
+        .unwrap();
+    bus.map_rom(0xF000, &[0x22], RomWritePolicy::Fault).unwrap();
+
+    bus.write(0xE000, 0x99);
+    assert_eq!(bus.take_fault(), None);
+    bus.write(0xF000, 0x99);
+
+    assert_eq!(
+        bus.take_fault(),
+        Some(BusFault::WriteToRom {
+            address: 0xF000,
+            value: 0x99
+        })
+    );
+    assert_eq!(bus.read(0xE000), 0x11);
+    assert_eq!(bus.read(0xF000), 0x22);
+}
+
+#[test]
+fn memory_rom_ranges_follow_their_write_policy() {
+    let mut memory = Memory::initialize();
+    memory[0xE000_u16] = 0x11;
+    memory[0xFFFC_u16] = 0x22;
+    memory.map_rom(0xE000..=0xE0FF, RomWritePolicy::Ignore);
+    memory.map_rom(0xFF00..=0xFFFF, RomWritePolicy::Fault);
+    memory[0x0200_u16] = Instruction::InsStaAbs as Byte;
+    memory[0x0201_u16] = 0x00;
+    memory[0x0202_u16] = 0xE0;
+    memory[0x0203_u16] = Instruction::InsStaAbs as Byte;
+    memory[0x0204_u16] = 0xFC;
+    memory[0x0205_u16] = 0xFF;
+    let mut cpu = CPU::start_at(0x0200);
+    cpu.a_register = 0x42;
+
+    let result = cpu.execute(100, &mut memory);
+
+    assert_eq!(
+        result,
+        Err(InstructionsError::WriteToRom {
+            program_counter: 0x0203,
+            address: 0xFFFC,
+            value: 0x42
+        })
+    );
+    assert_eq!(memory[0xE000_u16], 0x11);
+    assert_eq!(memory[0xFFFC_u16], 0x22);
+    memory.write(0xE100, 0x42);
+    assert_eq!(memory[0xE100_u16], 0x42);
+}
+
+#[test]
+fn load_rom_installs_file_at_base() {
+    let path = std::env::temp_dir().join(format!("rom_{}.bin", std::process::id()));
+    std::fs::write(&path, [0x11, 0x22, 0x33]).unwrap();
+    let mut bus = MemoryMap::new();
+
+    let result = bus.load_rom(0xC000, &path, RomWritePolicy::Ignore);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(bus.read(0xC000), 0x11);
+    assert_eq!(bus.read(0xC002), 0x33);
+    bus.write(0xC001, 0x99);
+    assert_eq!(bus.read(0xC001), 0x22);
+    assert_eq!(bus.read(0xC003), 0x00);
+}
+
+#[test]
+fn load_rom_reports_missing_file() {
+    let mut bus = MemoryMap::new();
+
+    let result = bus.load_rom(0xC000, "does/not/exist.bin", RomWritePolicy::Ignore);
+
+    assert_eq!(
+        result,
+        Err(MemoryMapError::Io(std::io::ErrorKind::NotFound))
+    );
+}
+
+#[test]
+fn serial_port_status_transmit_bit_clears_on_read() {
+    let mut serial = SerialPort::new();
+
+    serial.write(SerialPort::DATA, b'A');
+
+    assert_eq!(
+        serial.peek(SerialPort::STATUS),
+        SerialPort::TRANSMIT_DONE_BIT
+    );
+    assert_eq!(
+        serial.read(SerialPort::STATUS),
+        SerialPort::TRANSMIT_DONE_BIT
+    );
+    assert_eq!(serial.read(SerialPort::STATUS), 0);
+    assert_eq!(serial.transmitted, vec![b'A']);
+}
+
+#[test]
+fn serial_port_data_read_consumes_received_byte() {
+    let mut serial = SerialPort::new();
+    serial.received.push_back(b'x');
+
+    assert_eq!(
+        serial.peek(SerialPort::STATUS),
+        SerialPort::RECEIVE_READY_BIT
+    );
+    assert_eq!(serial.peek(SerialPort::DATA), b'x');
+    assert_eq!(serial.read(SerialPort::DATA), b'x');
+    assert_eq!(serial.read(SerialPort::STATUS), 0);
+}
+
+#[test]
+fn cpu_runs_from_rom_and_talks_to_devices() {
+    // Echo one byte from the serial port back to it:
+    //   loop: LDA $D001 / AND #$01 / BEQ loop / LDA $D000 / STA $D000
+    let program = [
+        Instruction::InsLdaAbs as Byte,
+        0x01,
+        0xD0,
+        Instruction::InsAndIm as Byte,
+        SerialPort::RECEIVE_READY_BIT,
+        Instruction::InsBeq as Byte,
+        0xF9,
+        Instruction::InsLdaAbs as Byte,
+        0x00,
+        0xD0,
+        Instruction::InsStaAbs as Byte,
+        0x00,
+        0xD0,
+    ];
+    let serial = Rc::new(RefCell::new(SerialPort::new()));
+    let probe = Rc::new(RefCell::new(ProbeDevice::default()));
+    let mut bus = MemoryMap::new();
+    bus.map_rom(0xF000, &program, RomWritePolicy::Ignore)
+        .unwrap();
+    bus.map(0xD000..=0xD001, serial.clone()).unwrap();
+    bus.map(0xD100..=0xD1FF, probe.clone()).unwrap();
+    let mut cpu = CPU::start_at(0xF000);
+
+    // Poll the empty port a few times
+    cpu.execute(30, &mut bus).unwrap();
+    assert!(serial.borrow().transmitted.is_empty());
+
+    serial.borrow_mut().received.push_back(b'!');
+    cpu.execute(30, &mut bus).unwrap();
+
+    assert_eq!(serial.borrow().transmitted, vec![b'!']);
+    assert!(serial.borrow().received.is_empty());
+    assert!(probe.borrow().reads.is_empty());
+}
@@ -7,7 +7,7 @@ use crate::{
 // NOP
 #[test]
 fn nop_will_do_nothing_but_consume_cycle() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -28,7 +28,7 @@ fn nop_will_do_nothing_but_consume_cycle() {
 // BRK
 #[test]
 fn brk_will_load_program_counter_from_interrupt_vector() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -45,7 +45,7 @@ fn brk_will_load_program_counter_from_interrupt_vector() {
 
 #[test]
 fn brk_will_load_program_counter_from_interrupt_vector_again() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -62,7 +62,7 @@ fn brk_will_load_program_counter_from_interrupt_vector_again() {
 
 #[test]
 fn brk_will_set_break_flag() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -79,7 +79,7 @@ fn brk_will_set_break_flag() {
 
 #[test]
 fn brk_will_push_3_bytes_onto_stack() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -96,7 +96,7 @@ fn brk_will_push_3_bytes_onto_stack() {
 
 #[test]
 fn brk_will_push_pc_and_ps_onto_stack() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let mut cpu_copy = cpu;
@@ -119,7 +119,7 @@ fn brk_will_push_pc_and_ps_onto_stack() {
 
 #[test]
 fn rti_can_can_return_from_interrupt_leaving_cpu_in_original_state() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -0,0 +1,194 @@
+use crate::{
+    cpu::{Byte, CPU},
+    instructions::Instruction,
+    memory::Memory,
+    movie::{hash_bytes, InputEvent, Movie, MovieError, MovieEvent, Recorder},
+};
+
+/// Copies every key that lands in $FF to a buffer at $0300:
+///
+/// ```text
+///         SEI
+/// loop:   LDA $FF
+///         BEQ loop
+///         STA $0300,X
+///         INX
+///         LDA #0
+///         STA $FF
+///         JMP loop
+/// ```
+const IMAGE: [Byte; 16] = [
+    Instruction::InsSei as Byte,
+    Instruction::InsLdaZp as Byte,
+    0xFF,
+    Instruction::InsBeq as Byte,
+    0xFC,
+    Instruction::InsStaAbsX as Byte,
+    0x00,
+    0x03,
+    Instruction::InsInx as Byte,
+    Instruction::InsLdaIm as Byte,
+    0x00,
+    Instruction::InsStaZp as Byte,
+    0xFF,
+    Instruction::InsJmpAbs as Byte,
+    0x01,
+    0x02,
+];
+
+fn machine() -> (CPU, Box<Memory>) {
+    let mut memory = Box::new(Memory::initialize());
+    memory[0x0200..0x0200 + IMAGE.len()].copy_from_slice(&IMAGE);
+    (CPU::start_at(0x0200), memory)
+}
+
+fn key(value: Byte) -> InputEvent {
+    InputEvent::Write {
+        address: 0x00FF,
+        value,
+    }
+}
+
+/// Steps the machine, delivering `events` after the given number of steps.
+fn record(events: &[(usize, InputEvent)], steps: usize) -> Movie {
+    let (mut cpu, mut memory) = machine();
+    let mut recorder = Recorder::new(&IMAGE);
+    for step in 0..steps {
+        for (_, event) in events.iter().filter(|(at, _)| *at == step) {
+            recorder.record(*event, &mut cpu, memory.as_mut());
+        }
+        cpu.step(memory.as_mut()).unwrap();
+    }
+    assert_eq!(memory[0x0300_u16], b'h');
+    recorder.finish(&cpu, &memory)
+}
+
+fn sample_movie() -> Movie {
+    record(
+        &[
+            (10, key(b'h')),
+            (37, key(b'i')),
+            (38, InputEvent::Irq(true)),
+            (60, InputEvent::Irq(false)),
+            (61, key(b'!')),
+        ],
+        200,
+    )
+}
+
+#[test]
+fn replay_reproduces_recorded_run() {
+    let movie = sample_movie();
+    let (mut cpu, mut memory) = machine();
+
+    let result = movie.replay(&IMAGE, &mut cpu, memory.as_mut());
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(memory[0x0300..0x0303], *b"hi!");
+    assert_eq!(cpu.total_cycles, movie.final_cycle);
+}
+
+#[test]
+fn movie_keeps_cycles_and_image_hash() {
+    let movie = sample_movie();
+
+    assert_eq!(movie.image_hash, hash_bytes(&IMAGE));
+    assert_eq!(movie.events.len(), 5);
+    assert!(movie
+        .events
+        .windows(2)
+        .all(|pair| pair[0].cycle <= pair[1].cycle));
+    assert_eq!(movie.events[0].event, key(b'h'));
+}
+
+#[test]
+fn text_round_trip() {
+    let movie = sample_movie();
+
+    let text = movie.to_text();
+
+    assert!(text.starts_with("6502MOVIE 1\nimage "));
+    assert_eq!(Movie::from_text(&text), Ok(movie));
+}
+
+#[test]
+fn file_round_trip() {
+    let movie = sample_movie();
+    let path = std::env::temp_dir().join(format!("movie_{}.txt", std::process::id()));
+
+    movie.save_to_file(&path).unwrap();
+    let loaded = Movie::load_from_file(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, Ok(movie));
+}
+
+#[test]
+fn replay_rejects_different_image() {
+    let movie = sample_movie();
+    let mut image = IMAGE;
+    image[2] = 0xFE;
+    let (mut cpu, mut memory) = machine();
+
+    assert_eq!(
+        movie.replay(&image, &mut cpu, memory.as_mut()),
+        Err(MovieError::ImageMismatch {
+            expected: movie.image_hash,
+            found: hash_bytes(&image)
+        })
+    );
+}
+
+#[test]
+fn replay_detects_different_final_state() {
+    let mut movie = sample_movie();
+    movie.events[1].event = key(b'o');
+    let (mut cpu, mut memory) = machine();
+
+    let result = movie.replay(&IMAGE, &mut cpu, memory.as_mut());
+
+    assert!(matches!(result, Err(MovieError::FinalStateMismatch { .. })));
+}
+
+#[test]
+fn replay_detects_event_off_instruction_boundary() {
+    let mut movie = sample_movie();
+    movie.events[0].cycle += 1;
+    let (mut cpu, mut memory) = machine();
+
+    assert_eq!(
+        movie.replay(&IMAGE, &mut cpu, memory.as_mut()),
+        Err(MovieError::Desync {
+            cycle: movie.events[0].cycle
+        })
+    );
+}
+
+#[test]
+fn parse_errors_report_line() {
+    assert_eq!(Movie::from_text("movie\n"), Err(MovieError::InvalidHeader));
+    assert_eq!(
+        Movie::from_text("6502MOVIE 1\nimage 00ff\n12 write 00ff 61\n"),
+        Err(MovieError::MissingEnd)
+    );
+    assert_eq!(
+        Movie::from_text("6502MOVIE 1\nimage 00ff\n12 write 00ff\nend 20 0\n"),
+        Err(MovieError::InvalidLine { line: 3 })
+    );
+    assert_eq!(
+        Movie::from_text("6502MOVIE 1\nimage 00ff\n\n12 irq 2\nend 20 0\n"),
+        Err(MovieError::InvalidLine { line: 4 })
+    );
+    assert_eq!(
+        Movie::from_text("6502MOVIE 1\nimage 00ff\n12 nmi 1\nend 20 ab\n"),
+        Ok(Movie {
+            image_hash: 0xFF,
+            events: vec![MovieEvent {
+                cycle: 12,
+                event: InputEvent::Nmi(true)
+            }],
+            final_cycle: 20,
+            final_hash: 0xAB
+        })
+    );
+}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use crate::{
+    bus::Bus,
+    cpu::{Byte, Word, CPU},
+    memory::Memory,
+    o65::{parse_o65, segment, O65Bases, O65Error},
+};
+
+fn word(value: Word) -> [Byte; 2] {
+    value.to_le_bytes()
+}
+
+/// Builds a module assembled for text at $1000 and data at $20F0:
+///
+/// ```text
+/// start:  LDA message
+///         STA $0200
+///         LDA #<message
+///         LDX #>message
+///         JSR print
+///         JMP start
+///         RTS
+/// message: .byte $42
+///         .word start
+/// ```
+fn module(mode: Word) -> Vec<Byte> {
+    let mut bytes = vec![0x01, 0x00, b'o', b'6', b'5', 0x00];
+    bytes.extend(word(mode));
+    for value in [0x1000, 17, 0x20F0, 3, 0x3000, 4, 0x0080, 0, 0] {
+        bytes.extend(word(value));
+    }
+    // Options, a file name
+    bytes.extend([6, 0, b'm', b'o', b'd', 0]);
+    bytes.push(0);
+    bytes.extend([0xAD, 0xF0, 0x20, 0x8D, 0x00, 0x02, 0xA9, 0xF0, 0xA2, 0x20]);
+    bytes.extend([0x20, 0x00, 0x00, 0x4C, 0x00, 0x10, 0x60]);
+    bytes.extend([0x42, 0x00, 0x10]);
+    // Undefined references
+    bytes.extend(word(1));
+    bytes.extend(b"print\0");
+    // Text relocations
+    bytes.extend([2, 0x80 | segment::DATA]);
+    bytes.extend([6, 0x20 | segment::DATA]);
+    bytes.extend([2, 0x40 | segment::DATA, 0xF0]);
+    bytes.extend([2, 0x80 | segment::UNDEFINED, 0x00, 0x00]);
+    bytes.extend([3, 0x80 | segment::TEXT]);
+    bytes.push(0);
+    // Data relocations
+    bytes.extend([2, 0x80 | segment::TEXT]);
+    bytes.push(0);
+    // Exports
+    bytes.extend(word(2));
+    bytes.extend(b"start\0");
+    bytes.push(segment::TEXT);
+    bytes.extend(word(0x1000));
+    bytes.extend(b"message\0");
+    bytes.push(segment::DATA);
+    bytes.extend(word(0x20F0));
+    bytes
+}
+
+fn imports() -> HashMap<String, Word> {
+    HashMap::from([(String::from("print"), 0xFFD2)])
+}
+
+const BASES: O65Bases = O65Bases {
+    text: 0xC123,
+    data: 0x3456,
+    bss: 0x4000,
+    zero: 0x0090,
+};
+
+#[test]
+fn parses_header_and_tables() {
+    let file = parse_o65(&module(0)).unwrap();
+
+    assert_eq!(file.bases.text, 0x1000);
+    assert_eq!(file.bases.data, 0x20F0);
+    assert_eq!(file.bss_length, 4);
+    assert_eq!(file.options, vec![(0, b"mod\0".to_vec())]);
+    assert_eq!(file.text.len(), 17);
+    assert_eq!(file.data, vec![0x42, 0x00, 0x10]);
+    assert_eq!(file.undefined, vec![String::from("print")]);
+    assert_eq!(file.text_relocations.len(), 5);
+    assert_eq!(file.text_relocations[4].offset, 14);
+    assert_eq!(file.data_relocations[0].offset, 1);
+    assert_eq!(file.exports.len(), 2);
+}
+
+#[test]
+fn load_relocates_into_memory_and_returns_symbols() {
+    let file = parse_o65(&module(0)).unwrap();
+    let mut memory = Memory::initialize();
+
+    let symbols = file.load(BASES, &imports(), &mut memory).unwrap();
+
+    assert_eq!(symbols["start"], 0xC123);
+    assert_eq!(symbols["message"], 0x3456);
+    assert_eq!(
+        memory[0xC123..0xC123 + 17],
+        [
+            0xAD, 0x56, 0x34, 0x8D, 0x00, 0x02, 0xA9, 0x56, 0xA2, 0x34, 0x20, 0xD2, 0xFF, 0x4C,
+            0x23, 0xC1, 0x60
+        ]
+    );
+    assert_eq!(memory[0x3456..0x3459], [0x42, 0x23, 0xC1]);
+}
+
+#[test]
+fn loading_at_assembled_bases_changes_nothing() {
+    let bytes = module(0);
+    let file = parse_o65(&bytes).unwrap();
+
+    let relocated = file.relocate(file.bases, &imports()).unwrap();
+
+    assert_eq!(relocated.image.segments[0].data[..10], file.text[..10]);
+    assert_eq!(relocated.image.segments[1].data, file.data);
+}
+
+#[test]
+fn host_can_call_into_loaded_module() {
+    let file = parse_o65(&module(0)).unwrap();
+    let mut memory = Memory::initialize();
+    memory[0xFFD2_u16] = 0x60;
+    let symbols = file.load(BASES, &imports(), &mut memory).unwrap();
+    let mut cpu = CPU::start_at(symbols["start"]);
+
+    // LDA, STA, LDA, LDX, JSR, RTS
+    for _ in 0..6 {
+        cpu.step(&mut memory).unwrap();
+    }
+
+    assert_eq!(memory[0x0200_u16], 0x42);
+    assert_eq!(cpu.a_register, 0x56);
+    assert_eq!(cpu.x_register, 0x34);
+    assert_eq!(cpu.program_counter, 0xC123 + 13);
+}
+
+#[test]
+fn bss_is_zeroed_when_requested() {
+    let file = parse_o65(&module(0x0200)).unwrap();
+    let mut memory = Memory::power_on(crate::power_on::PowerOnPattern::Fixed(0xEE));
+
+    file.load(BASES, &imports(), &mut memory).unwrap();
+
+    assert_eq!(memory[0x4000..0x4005], [0x00, 0x00, 0x00, 0x00, 0xEE]);
+}
+
+#[test]
+fn missing_import_is_reported() {
+    let file = parse_o65(&module(0)).unwrap();
+
+    assert_eq!(
+        file.relocate(BASES, &HashMap::new()),
+        Err(O65Error::UndefinedSymbol(String::from("print")))
+    );
+}
+
+#[test]
+fn invalid_files_are_rejected() {
+    let mut bytes = module(0);
+
+    assert_eq!(parse_o65(&bytes[..3]), Err(O65Error::NotO65));
+    assert_eq!(parse_o65(&bytes[..40]), Err(O65Error::UnexpectedEnd));
+
+    bytes[6..8].copy_from_slice(&word(0x2000));
+    assert_eq!(parse_o65(&bytes), Err(O65Error::UnsupportedMode(0x2000)));
+
+    bytes[5] = 1;
+    assert_eq!(parse_o65(&bytes), Err(O65Error::UnsupportedVersion(1)));
+
+    bytes[2] = b'x';
+    assert_eq!(parse_o65(&bytes), Err(O65Error::NotO65));
+}
+
+#[test]
+fn long_relocation_gaps_use_extension_bytes() {
+    let mut bytes = vec![0x01, 0x00, b'o', b'6', b'5', 0x00];
+    bytes.extend(word(0));
+    for value in [0x1000, 302, 0, 0, 0, 0, 0, 0, 0] {
+        bytes.extend(word(value));
+    }
+    bytes.push(0);
+    let mut text = vec![0xEA; 302];
+    text[300..302].copy_from_slice(&word(0x1000));
+    bytes.extend(text);
+    bytes.extend(word(0));
+    // Offsets start at -1, so -1 + 254 + 47 reaches 300
+    bytes.extend([255, 47, 0x80 | segment::TEXT, 0]);
+    bytes.push(0);
+    bytes.extend(word(0));
+    let file = parse_o65(&bytes).unwrap();
+    let mut memory = Memory::initialize();
+
+    file.load(
+        O65Bases {
+            text: 0x8000,
+            ..file.bases
+        },
+        &HashMap::new(),
+        &mut memory,
+    )
+    .unwrap();
+
+    assert_eq!(file.text_relocations[0].offset, 300);
+    assert_eq!(memory.read(0x812C), 0x00);
+    assert_eq!(memory.read(0x812D), 0x80);
+}
@@ -0,0 +1,154 @@
+use crate::{
+    cpu::{Byte, CpuVariant, ProcessorFlags, CPU},
+    instructions::{defined_opcodes, opcode_table, AddressingMode, Instruction, Operation},
+    memory::Memory,
+};
+
+fn verify_table_matches_decoder(variant: CpuVariant, undocumented_opcodes: bool) {
+    let table = opcode_table(variant, undocumented_opcodes);
+    for opcode in 0..=0xFF_u8 {
+        let entry = table[opcode as usize];
+        let decoded = Instruction::decode(opcode, variant, undocumented_opcodes).ok();
+        assert_eq!(
+            entry.map(|entry| entry.instruction),
+            decoded,
+            "opcode {:02X}",
+            opcode
+        );
+        if let Some(entry) = entry {
+            assert_eq!(entry.instruction.opcode(), opcode);
+        }
+    }
+}
+
+#[test]
+fn opcode_tables_match_decoder() {
+    verify_table_matches_decoder(CpuVariant::Nmos6502, false);
+    verify_table_matches_decoder(CpuVariant::Nmos6502, true);
+    verify_table_matches_decoder(CpuVariant::Cmos65C02, false);
+}
+
+#[test]
+fn nmos_table_only_has_official_and_custom_opcodes() {
+    let table = opcode_table(CpuVariant::Nmos6502, false);
+    let defined = table.iter().filter(|entry| entry.is_some()).count();
+
+    assert_eq!(defined, 151 + 2);
+}
+
+#[test]
+fn undocumented_table_fills_every_opcode() {
+    let table = opcode_table(CpuVariant::Nmos6502, true);
+
+    assert!(table.iter().all(|entry| entry.is_some()));
+}
+
+#[test]
+fn opcode_table_entries_carry_operation_and_mode() {
+    let table = opcode_table(CpuVariant::Cmos65C02, false);
+
+    let lda = table[Instruction::InsLdaIndY as usize].unwrap();
+    assert_eq!(lda.operation, Operation::Lda);
+    assert_eq!(lda.mode, AddressingMode::IndirectIndexed);
+
+    let bbs = table[Instruction::InsBbs3.opcode() as usize].unwrap();
+    assert_eq!(bbs.operation, Operation::Bbs(3));
+    assert_eq!(bbs.mode, AddressingMode::ZeroPageRelative);
+}
+
+fn verify_cycles_match_emulator(variant: CpuVariant, undocumented_opcodes: bool) {
+    for entry in defined_opcodes(variant, undocumented_opcodes) {
+        if entry.operation == Operation::Jam {
+            continue;
+        }
+        let mut cpu = CPU::start_at(0x0200).with_variant(variant);
+        cpu.undocumented_opcodes = undocumented_opcodes;
+        cpu.stack_pointer = 0xF0;
+        let mut memory = Memory::initialize();
+        memory[0x0200] = entry.opcode;
+
+        // With zeroed flags and memory, these branch to the next instruction
+        let branch_taken = matches!(
+            entry.operation,
+            Operation::Bcc
+                | Operation::Bne
+                | Operation::Bpl
+                | Operation::Bvc
+                | Operation::Bra
+                | Operation::Bbr(_)
+        );
+        let expected = entry.cycles + branch_taken as i32 * entry.branch_taken_cycles;
+
+        let cycles = cpu.execute(1, &mut memory);
+
+        assert_eq!(
+            cycles,
+            Ok(expected),
+            "{} ({:02X})",
+            entry.mnemonic,
+            entry.opcode
+        );
+        if !matches!(
+            entry.operation,
+            Operation::Jmp | Operation::Jsr | Operation::Rts | Operation::Rti | Operation::Brk
+        ) {
+            assert_eq!(
+                cpu.program_counter,
+                0x0200 + entry.length,
+                "{} ({:02X})",
+                entry.mnemonic,
+                entry.opcode
+            );
+        }
+    }
+}
+
+#[test]
+fn opcode_cycles_and_lengths_match_emulator() {
+    verify_cycles_match_emulator(CpuVariant::Nmos6502, true);
+    verify_cycles_match_emulator(CpuVariant::Cmos65C02, false);
+}
+
+#[test]
+fn opcode_metadata_describes_instruction() {
+    let table = opcode_table(CpuVariant::Nmos6502, false);
+
+    let lda = table[0xBD].unwrap();
+    assert_eq!(lda.mnemonic, "LDA");
+    assert_eq!(lda.mode, AddressingMode::AbsoluteX);
+    assert_eq!(lda.length, 3);
+    assert_eq!(lda.cycles, 4);
+    assert_eq!(lda.page_cross_cycles, 1);
+    assert_eq!(
+        lda.flags_affected,
+        ProcessorFlags::NEGATIVE_FLAG_BIT | ProcessorFlags::ZERO_FLAG_BIT
+    );
+
+    let jmp = table[0x6C].unwrap();
+    assert_eq!(jmp.mnemonic, "JMP");
+    assert_eq!(jmp.mode, AddressingMode::Indirect);
+    assert_eq!(jmp.cycles, 5);
+    assert_eq!(jmp.flags_affected, 0);
+
+    // PHP only pushes a copy of the status register, with B and the unused bit set
+    let php = table[Instruction::InsPhp as usize].unwrap();
+    assert_eq!(php.flags_affected, 0);
+
+    let sta = table[Instruction::InsStaAbsX as usize].unwrap();
+    assert_eq!(sta.cycles, 5);
+    assert_eq!(sta.page_cross_cycles, 0);
+
+    let beq = table[Instruction::InsBeq as usize].unwrap();
+    assert_eq!(beq.branch_taken_cycles, 1);
+    assert_eq!(beq.page_cross_cycles, 1);
+}
+
+#[test]
+fn cmos_metadata_differs_from_nmos() {
+    let jmp = opcode_table(CpuVariant::Cmos65C02, false)[0x6C].unwrap();
+    assert_eq!(jmp.cycles, 6);
+
+    let smb = opcode_table(CpuVariant::Cmos65C02, false)[0xB7].unwrap();
+    assert_eq!(smb.mnemonic, "SMB3");
+    assert_eq!(smb.mode, AddressingMode::ZeroPage);
+}
@@ -0,0 +1,103 @@
+use crate::{
+    bus::Bus,
+    cpu::{Byte, ProcessorFlags, CPU},
+    memory::Memory,
+    power_on::PowerOnPattern,
+};
+
+#[test]
+fn zero_pattern_matches_initialize() {
+    let memory = Memory::power_on(PowerOnPattern::Zero);
+
+    assert!(memory[0x0000..0x10000].iter().all(|byte| *byte == 0));
+}
+
+#[test]
+fn fixed_pattern_fills_every_byte() {
+    let memory = Memory::power_on(PowerOnPattern::Fixed(0xA5));
+
+    assert!(memory[0x0000..0x10000].iter().all(|byte| *byte == 0xA5));
+}
+
+#[test]
+fn alternating_pattern_switches_every_page() {
+    let memory = Memory::power_on(PowerOnPattern::AlternatingPages);
+
+    assert_eq!(memory[0x0000_u16], 0x00);
+    assert_eq!(memory[0x00FF_u16], 0x00);
+    assert_eq!(memory[0x0100_u16], 0xFF);
+    assert_eq!(memory[0x01FF_u16], 0xFF);
+    assert_eq!(memory[0x0200_u16], 0x00);
+    assert_eq!(memory[0xFFFF_u16], 0xFF);
+}
+
+#[test]
+fn random_pattern_is_reproducible_from_seed() {
+    let first = Memory::power_on(PowerOnPattern::Random(1234));
+    let second = Memory::power_on(PowerOnPattern::Random(1234));
+    let other = Memory::power_on(PowerOnPattern::Random(1235));
+
+    assert_eq!(first[0x0000..0x10000], second[0x0000..0x10000]);
+    assert_ne!(first[0x0000..0x10000], other[0x0000..0x10000]);
+}
+
+#[test]
+fn random_pattern_looks_random() {
+    let memory = Memory::power_on(PowerOnPattern::Random(0));
+    let mut seen = [false; 256];
+    for byte in &memory[0x0000..0x10000] {
+        seen[*byte as usize] = true;
+    }
+
+    assert!(seen.iter().all(|seen| *seen));
+}
+
+#[test]
+fn cpu_registers_follow_pattern() {
+    let cpu = CPU::power_on(PowerOnPattern::Fixed(0x5A));
+
+    assert_eq!(cpu.a_register, 0x5A);
+    assert_eq!(cpu.x_register, 0x5A);
+    assert_eq!(cpu.y_register, 0x5A);
+    assert_eq!(cpu.stack_pointer, 0x5A);
+    assert_eq!(cpu.status, ProcessorFlags::from(0x5A));
+}
+
+#[test]
+fn cpu_alternating_pattern_switches_every_register() {
+    let cpu = CPU::power_on(PowerOnPattern::AlternatingPages);
+
+    assert_eq!(cpu.a_register, 0x00);
+    assert_eq!(cpu.x_register, 0xFF);
+    assert_eq!(cpu.y_register, 0x00);
+    assert_eq!(cpu.stack_pointer, 0xFF);
+}
+
+#[test]
+fn cpu_random_registers_are_reproducible() {
+    let first = CPU::power_on(PowerOnPattern::Random(99));
+    let second = CPU::power_on(PowerOnPattern::Random(99));
+
+    assert_eq!(
+        (first.a_register, first.x_register, first.y_register),
+        (second.a_register, second.x_register, second.y_register)
+    );
+    assert_eq!(first.stack_pointer, second.stack_pointer);
+    assert_eq!(first.status, second.status);
+}
+
+#[test]
+fn ram_and_registers_are_chosen_independently() {
+    let mut memory = Memory::power_on(PowerOnPattern::Fixed(0xEE));
+    memory[0xFFFC_u16] = 0x00;
+    memory[0xFFFD_u16] = 0x80;
+    let mut cpu = CPU::power_on(PowerOnPattern::Zero);
+
+    cpu.reset(&mut memory);
+
+    assert_eq!(cpu.program_counter, 0x8000);
+    assert_eq!(cpu.a_register, 0x00);
+    assert_eq!(cpu.stack_pointer, 0xFD);
+    assert!(cpu.status.interupt_disable);
+    assert_eq!(memory.read(0x1234), 0xEE);
+}
@@ -0,0 +1,64 @@
+use crate::{
+    cpu::{Byte, CPU},
+    instructions::Instruction,
+    memory::Memory,
+};
+
+#[test]
+fn reset_loads_program_counter_from_reset_vector() {
+    let mut cpu = CPU::new();
+    let mut memory = Memory::initialize();
+
+    memory[0xFFFC] = 0x34;
+    memory[0xFFFD] = 0x12;
+
+    let cycles = cpu.reset(&mut memory);
+
+    assert_eq!(cycles, 7);
+    assert_eq!(cpu.program_counter, 0x1234);
+}
+
+#[test]
+fn reset_sets_interrupt_disable_and_decrements_stack_pointer_without_writing() {
+    let mut cpu = CPU::new();
+    let mut memory = Memory::initialize();
+
+    cpu.reset(&mut memory);
+
+    assert_eq!(cpu.stack_pointer, 0xFD);
+    assert!(cpu.status.interupt_disable);
+    assert_eq!(&memory[0x0100_usize..0x0200], &[0; 0x100]);
+}
+
+#[test]
+fn reset_works_on_a_running_cpu() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsLdaIm as Byte;
+    memory[0xFF01] = 0x42;
+    memory[0xFFFC] = 0x00;
+    memory[0xFFFD] = 0x80;
+    memory[0x8000] = Instruction::InsNop as Byte;
+
+    cpu.execute(2, &mut memory).unwrap();
+    cpu.reset(&mut memory);
+
+    assert_eq!(cpu.a_register, 0x42);
+    assert_eq!(cpu.stack_pointer, 0xFC);
+    assert_eq!(cpu.program_counter, 0x8000);
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.program_counter, 0x8001);
+}
+
+#[test]
+fn start_at_skips_reset_vector() {
+    let cpu = CPU::start_at(0xFF00);
+
+    assert_eq!(cpu.program_counter, 0xFF00);
+    assert_eq!(cpu.stack_pointer, 0xFF);
+    assert!(!cpu.status.interupt_disable);
+}
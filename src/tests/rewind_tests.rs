@@ -0,0 +1,139 @@
+use crate::{
+    cpu::{Byte, CPU},
+    instructions::Instruction,
+    memory::Memory,
+    rewind::{Rewind, RewindError},
+};
+
+/// `loop: INX / TXA / STA $0300,X / INC $10 / JMP loop`
+fn program() -> (CPU, Box<Memory>) {
+    let mut memory = Box::new(Memory::initialize());
+    let program = [
+        Instruction::InsInx as Byte,
+        Instruction::InsTxa as Byte,
+        Instruction::InsStaAbsX as Byte,
+        0x00,
+        0x03,
+        Instruction::InsIncZp as Byte,
+        0x10,
+        Instruction::InsJmpAbs as Byte,
+        0x00,
+        0x02,
+    ];
+    for (offset, byte) in program.iter().enumerate() {
+        memory[0x0200 + offset as u16] = *byte;
+    }
+    (CPU::start_at(0x0200), memory)
+}
+
+/// Steps `count` instructions, recording snapshots and keeping every state for checking.
+fn run(
+    count: usize,
+    rewind: &mut Rewind,
+    cpu: &mut CPU,
+    memory: &mut Memory,
+) -> Vec<(CPU, Vec<Byte>)> {
+    let mut history = Vec::new();
+    for _ in 0..count {
+        rewind.record(cpu, memory);
+        history.push((*cpu, memory[0..0x10000].to_vec()));
+        cpu.step(memory).unwrap();
+    }
+    history
+}
+
+#[test]
+fn step_back_restores_previous_instruction() {
+    let (mut cpu, mut memory) = program();
+    let mut rewind = Rewind::new(50, 16);
+    let history = run(300, &mut rewind, &mut cpu, &mut memory);
+
+    for expected in history.iter().rev().take(40) {
+        rewind.step_back(&mut cpu, &mut memory).unwrap();
+
+        assert_eq!(cpu, expected.0);
+        assert_eq!(memory[0..0x10000], expected.1[..]);
+    }
+}
+
+#[test]
+fn step_back_keeps_injected_writes() {
+    let (mut cpu, mut memory) = program();
+    let mut rewind = Rewind::new(64, 16);
+    let before = run(30, &mut rewind, &mut cpu, &mut memory);
+
+    memory[0x0010_u16] = 0x80;
+    rewind.take_snapshot(&cpu, &memory);
+    let after = run(30, &mut rewind, &mut cpu, &mut memory);
+
+    for expected in after.iter().rev().chain(before.iter().rev().take(5)) {
+        rewind.step_back(&mut cpu, &mut memory).unwrap();
+
+        assert_eq!(cpu, expected.0);
+        assert_eq!(memory[0..0x10000], expected.1[..]);
+    }
+}
+
+#[test]
+fn rewind_to_cycle_lands_on_instruction_boundary() {
+    let (mut cpu, mut memory) = program();
+    let mut rewind = Rewind::new(64, 64);
+    let history = run(500, &mut rewind, &mut cpu, &mut memory);
+    let target = 1001;
+
+    rewind
+        .rewind_to_cycle(target, &mut cpu, &mut memory)
+        .unwrap();
+
+    let expected = history
+        .iter()
+        .rev()
+        .find(|(state, _)| state.total_cycles <= target)
+        .unwrap();
+    assert_eq!(cpu, expected.0);
+    assert_eq!(memory[0..0x10000], expected.1[..]);
+    assert!(cpu.total_cycles <= target);
+}
+
+#[test]
+fn running_after_rewind_repeats_history() {
+    let (mut cpu, mut memory) = program();
+    let mut rewind = Rewind::new(100, 8);
+    let history = run(400, &mut rewind, &mut cpu, &mut memory);
+    let (final_cpu, final_memory) = (cpu, memory[0..0x10000].to_vec());
+
+    rewind
+        .rewind_to_cycle(history[350].0.total_cycles, &mut cpu, &mut memory)
+        .unwrap();
+    assert_eq!(cpu, history[350].0);
+    run(50, &mut rewind, &mut cpu, &mut memory);
+
+    assert_eq!(cpu, final_cpu);
+    assert_eq!(memory[0..0x10000], final_memory[..]);
+}
+
+#[test]
+fn buffer_is_bounded() {
+    let (mut cpu, mut memory) = program();
+    let mut rewind = Rewind::new(10, 4);
+    run(200, &mut rewind, &mut cpu, &mut memory);
+
+    assert_eq!(rewind.len(), 4);
+    assert!(rewind.oldest_cycle().unwrap() > 0);
+    assert_eq!(
+        rewind.rewind_to_cycle(0, &mut cpu, &mut memory),
+        Err(RewindError::NoSnapshot)
+    );
+}
+
+#[test]
+fn step_back_without_history_fails() {
+    let (mut cpu, mut memory) = program();
+    let mut rewind = Rewind::new(10, 4);
+
+    assert_eq!(
+        rewind.step_back(&mut cpu, &mut memory),
+        Err(RewindError::NoSnapshot)
+    );
+    assert!(rewind.is_empty());
+}
@@ -0,0 +1,145 @@
+use sdl2::pixels::Color;
+
+use crate::{
+    bus::Bus,
+    cpu::{Byte, CpuVariant, CPU},
+    graphics_adapter::GraphicsAdapter,
+    instructions::Instruction,
+    memory::Memory,
+    power_on::PowerOnPattern,
+    save_state::{load, load_from_file, save, save_to_file, SaveStateError, VERSION},
+};
+
+/// A counting loop that runs forever: `loop: INX / TXA / STA $0300,X / JMP loop`.
+fn running_program() -> (CPU, Memory) {
+    let mut memory = Memory::power_on(PowerOnPattern::Random(7));
+    let program = [
+        Instruction::InsInx as Byte,
+        Instruction::InsTxa as Byte,
+        Instruction::InsStaAbsX as Byte,
+        0x00,
+        0x03,
+        Instruction::InsJmpAbs as Byte,
+        0x00,
+        0x02,
+    ];
+    for (offset, byte) in program.iter().enumerate() {
+        memory[0x0200 + offset as u16] = *byte;
+    }
+    let mut graphics = GraphicsAdapter::new(Color::RGB(10, 20, 30));
+    graphics.get_data(0b1000100010000011);
+    let mut cpu = CPU::new_graphics(graphics, 0x0200);
+    cpu.x_register = 0;
+    cpu.execute(1000, &mut memory).unwrap();
+    (cpu, memory)
+}
+
+#[test]
+fn round_trip_restores_everything() {
+    let (mut cpu, memory) = running_program();
+    cpu.set_irq(true);
+    cpu.set_nmi(true);
+    cpu.undocumented_opcodes = true;
+    cpu.cycle_debt = 3;
+    cpu.status.decimal_mode = true;
+
+    let (loaded_cpu, loaded_memory) = load(&save(&cpu, &memory)).unwrap();
+
+    assert_eq!(loaded_cpu, cpu);
+    assert_eq!(loaded_memory, memory);
+    assert_eq!(loaded_cpu.total_cycles, cpu.total_cycles);
+    assert_eq!(
+        loaded_cpu.get_graphics().unwrap().get_pixels()[2][2],
+        Color::RGB(0, 0, 255)
+    );
+}
+
+#[test]
+fn round_trip_without_graphics_and_cmos() {
+    let cpu = CPU::start_at(0x1234).with_variant(CpuVariant::Cmos65C02);
+    let memory = Memory::initialize();
+
+    let (loaded_cpu, loaded_memory) = load(&save(&cpu, &memory)).unwrap();
+
+    assert_eq!(loaded_cpu, cpu);
+    assert!(loaded_cpu.get_graphics().is_none());
+    assert_eq!(loaded_memory, memory);
+}
+
+#[test]
+fn resumed_run_matches_uninterrupted_run() {
+    let (mut cpu, mut memory) = running_program();
+    let (mut resumed_cpu, mut resumed_memory) = load(&save(&cpu, &memory)).unwrap();
+
+    cpu.execute(5000, &mut memory).unwrap();
+    resumed_cpu.execute(5000, &mut resumed_memory).unwrap();
+
+    assert_eq!(resumed_cpu, cpu);
+    assert_eq!(resumed_memory, memory);
+}
+
+#[test]
+fn header_carries_magic_and_version() {
+    let bytes = save(&CPU::new(), &Memory::initialize());
+
+    assert_eq!(&bytes[0..8], b"6502SAVE");
+    assert_eq!(&bytes[8..10], &VERSION.to_le_bytes());
+}
+
+#[test]
+fn invalid_states_fail_cleanly() {
+    let bytes = save(&CPU::new(), &Memory::initialize());
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert_eq!(load(&wrong_magic), Err(SaveStateError::NotASaveState));
+    assert_eq!(load(&bytes[..4]), Err(SaveStateError::NotASaveState));
+
+    let mut newer = bytes.clone();
+    newer[8..10].copy_from_slice(&(VERSION + 1).to_le_bytes());
+    assert_eq!(
+        load(&newer),
+        Err(SaveStateError::UnsupportedVersion(VERSION + 1))
+    );
+
+    let mut unknown = bytes.clone();
+    unknown[8..10].copy_from_slice(&0_u16.to_le_bytes());
+    assert_eq!(load(&unknown), Err(SaveStateError::UnsupportedVersion(0)));
+
+    assert_eq!(
+        load(&bytes[..bytes.len() - 1]),
+        Err(SaveStateError::Truncated)
+    );
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(load(&trailing), Err(SaveStateError::TrailingData));
+
+    let mut bad_variant = bytes.clone();
+    bad_variant[18] = 9;
+    assert_eq!(
+        load(&bad_variant),
+        Err(SaveStateError::InvalidValue {
+            field: "variant",
+            value: 9
+        })
+    );
+}
+
+#[test]
+fn file_round_trip() {
+    let (cpu, memory) = running_program();
+    let path = std::env::temp_dir().join(format!("state_{}.sav", std::process::id()));
+
+    save_to_file(&path, &cpu, &memory).unwrap();
+    let result = load_from_file(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let (loaded_cpu, mut loaded_memory) = result.unwrap();
+    assert_eq!(loaded_cpu, cpu);
+    assert_eq!(loaded_memory.read(0x0301), memory[0x0301_u16]);
+    assert_eq!(
+        load_from_file("does/not/exist.sav"),
+        Err(SaveStateError::Io(std::io::ErrorKind::NotFound))
+    );
+}
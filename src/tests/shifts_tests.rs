@@ -20,7 +20,7 @@ fn verify_unmodified_flags(cpu: &CPU, cpu_copy: &CPU) {
 
 #[test]
 fn asl_a_can_shift_value_of_one() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -44,7 +44,7 @@ fn asl_a_can_shift_value_of_one() {
 
 #[test]
 fn asl_a_can_shift_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -70,7 +70,7 @@ fn asl_a_can_shift_negative_value() {
 
 #[test]
 fn asl_zp_can_shift_value_of_one() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -94,7 +94,7 @@ fn asl_zp_can_shift_value_of_one() {
 
 #[test]
 fn asl_zp_can_shift_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -120,7 +120,7 @@ fn asl_zp_can_shift_negative_value() {
 
 #[test]
 fn asl_zp_x_can_shift_value_of_one() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -146,7 +146,7 @@ fn asl_zp_x_can_shift_value_of_one() {
 
 #[test]
 fn asl_zp_x_can_shift_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -174,7 +174,7 @@ fn asl_zp_x_can_shift_negative_value() {
 
 #[test]
 fn asl_abs_can_shift_value_of_one() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -199,7 +199,7 @@ fn asl_abs_can_shift_value_of_one() {
 
 #[test]
 fn asl_abs_can_shift_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -226,7 +226,7 @@ fn asl_abs_can_shift_negative_value() {
 
 #[test]
 fn asl_abs_x_can_shift_value_of_one() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -253,7 +253,7 @@ fn asl_abs_x_can_shift_value_of_one() {
 
 #[test]
 fn asl_abs_x_can_shift_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -284,7 +284,7 @@ fn asl_abs_x_can_shift_negative_value() {
 
 #[test]
 fn lsr_a_can_shift_value_of_one() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -308,7 +308,7 @@ fn lsr_a_can_shift_value_of_one() {
 
 #[test]
 fn lsr_a_can_shift_zero_to_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -334,7 +334,7 @@ fn lsr_a_can_shift_zero_to_carry() {
 
 #[test]
 fn lsr_zp_can_shift_value_of_one() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -358,7 +358,7 @@ fn lsr_zp_can_shift_value_of_one() {
 
 #[test]
 fn lsr_zp_can_shift_zero_to_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -384,7 +384,7 @@ fn lsr_zp_can_shift_zero_to_carry() {
 
 #[test]
 fn lsr_zp_x_can_shift_value_of_one() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -410,7 +410,7 @@ fn lsr_zp_x_can_shift_value_of_one() {
 
 #[test]
 fn lsr_zp_x_can_shift_zero_to_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -438,7 +438,7 @@ fn lsr_zp_x_can_shift_zero_to_carry() {
 
 #[test]
 fn lsr_abs_can_shift_value_of_one() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -463,7 +463,7 @@ fn lsr_abs_can_shift_value_of_one() {
 
 #[test]
 fn lsr_abs_can_shift_zero_to_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -490,7 +490,7 @@ fn lsr_abs_can_shift_zero_to_carry() {
 
 #[test]
 fn lsr_abs_x_can_shift_value_of_one() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -517,7 +517,7 @@ fn lsr_abs_x_can_shift_value_of_one() {
 
 #[test]
 fn lsr_abs_x_can_shift_zero_to_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -548,7 +548,7 @@ fn lsr_abs_x_can_shift_zero_to_carry() {
 
 #[test]
 fn rol_a_can_shift_bit_out_of_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -572,7 +572,7 @@ fn rol_a_can_shift_bit_out_of_carry() {
 
 #[test]
 fn rol_a_can_shift_bit_into_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -596,7 +596,7 @@ fn rol_a_can_shift_bit_into_carry() {
 
 #[test]
 fn rol_a_can_shift_zero_with_no_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -620,7 +620,7 @@ fn rol_a_can_shift_zero_with_no_carry() {
 
 #[test]
 fn rol_a_can_shift_value_resulting_in_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -646,7 +646,7 @@ fn rol_a_can_shift_value_resulting_in_negative_value() {
 
 #[test]
 fn rol_zp_can_shift_bit_out_of_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -670,7 +670,7 @@ fn rol_zp_can_shift_bit_out_of_carry() {
 
 #[test]
 fn rol_zp_can_shift_bit_into_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -694,7 +694,7 @@ fn rol_zp_can_shift_bit_into_carry() {
 
 #[test]
 fn rol_zp_can_shift_zero_with_no_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -718,7 +718,7 @@ fn rol_zp_can_shift_zero_with_no_carry() {
 
 #[test]
 fn rol_zp_can_shift_value_resulting_in_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -744,7 +744,7 @@ fn rol_zp_can_shift_value_resulting_in_negative_value() {
 
 #[test]
 fn rol_zp_x_can_shift_bit_out_of_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -770,7 +770,7 @@ fn rol_zp_x_can_shift_bit_out_of_carry() {
 
 #[test]
 fn rol_zp_x_can_shift_bit_into_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -796,7 +796,7 @@ fn rol_zp_x_can_shift_bit_into_carry() {
 
 #[test]
 fn rol_zp_x_can_shift_zero_with_no_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -822,7 +822,7 @@ fn rol_zp_x_can_shift_zero_with_no_carry() {
 
 #[test]
 fn rol_zp_x_can_shift_value_resulting_in_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -850,7 +850,7 @@ fn rol_zp_x_can_shift_value_resulting_in_negative_value() {
 
 #[test]
 fn rol_abs_can_shift_bit_out_of_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -875,7 +875,7 @@ fn rol_abs_can_shift_bit_out_of_carry() {
 
 #[test]
 fn rol_abs_can_shift_bit_into_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -900,7 +900,7 @@ fn rol_abs_can_shift_bit_into_carry() {
 
 #[test]
 fn rol_abs_can_shift_zero_with_no_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -925,7 +925,7 @@ fn rol_abs_can_shift_zero_with_no_carry() {
 
 #[test]
 fn rol_abs_can_shift_value_resulting_in_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -952,7 +952,7 @@ fn rol_abs_can_shift_value_resulting_in_negative_value() {
 
 #[test]
 fn rol_abs_x_can_shift_bit_out_of_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -979,7 +979,7 @@ fn rol_abs_x_can_shift_bit_out_of_carry() {
 
 #[test]
 fn rol_abs_x_can_shift_bit_into_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1006,7 +1006,7 @@ fn rol_abs_x_can_shift_bit_into_carry() {
 
 #[test]
 fn rol_abs_x_can_shift_zero_with_no_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1033,7 +1033,7 @@ fn rol_abs_x_can_shift_zero_with_no_carry() {
 
 #[test]
 fn rol_abs_x_can_shift_value_resulting_in_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -1064,7 +1064,7 @@ fn rol_abs_x_can_shift_value_resulting_in_negative_value() {
 
 #[test]
 fn ror_a_can_shift_bit_out_of_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -1088,7 +1088,7 @@ fn ror_a_can_shift_bit_out_of_carry() {
 
 #[test]
 fn ror_a_can_shift_bit_into_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1112,7 +1112,7 @@ fn ror_a_can_shift_bit_into_carry() {
 
 #[test]
 fn ror_a_can_shift_zero_with_no_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1136,7 +1136,7 @@ fn ror_a_can_shift_zero_with_no_carry() {
 
 #[test]
 fn ror_a_can_shift_value_resulting_in_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -1162,7 +1162,7 @@ fn ror_a_can_shift_value_resulting_in_negative_value() {
 
 #[test]
 fn ror_zp_can_shift_bit_out_of_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -1186,7 +1186,7 @@ fn ror_zp_can_shift_bit_out_of_carry() {
 
 #[test]
 fn ror_zp_can_shift_bit_into_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1210,7 +1210,7 @@ fn ror_zp_can_shift_bit_into_carry() {
 
 #[test]
 fn ror_zp_can_shift_zero_with_no_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1234,7 +1234,7 @@ fn ror_zp_can_shift_zero_with_no_carry() {
 
 #[test]
 fn ror_zp_can_shift_value_resulting_in_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -1260,7 +1260,7 @@ fn ror_zp_can_shift_value_resulting_in_negative_value() {
 
 #[test]
 fn ror_zp_x_can_shift_bit_out_of_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -1286,7 +1286,7 @@ fn ror_zp_x_can_shift_bit_out_of_carry() {
 
 #[test]
 fn ror_zp_x_can_shift_bit_into_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1312,7 +1312,7 @@ fn ror_zp_x_can_shift_bit_into_carry() {
 
 #[test]
 fn ror_zp_x_can_shift_zero_with_no_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1338,7 +1338,7 @@ fn ror_zp_x_can_shift_zero_with_no_carry() {
 
 #[test]
 fn ror_zp_x_can_shift_value_resulting_in_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -1366,7 +1366,7 @@ fn ror_zp_x_can_shift_value_resulting_in_negative_value() {
 
 #[test]
 fn ror_abs_can_shift_bit_out_of_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -1391,7 +1391,7 @@ fn ror_abs_can_shift_bit_out_of_carry() {
 
 #[test]
 fn ror_abs_can_shift_bit_into_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1416,7 +1416,7 @@ fn ror_abs_can_shift_bit_into_carry() {
 
 #[test]
 fn ror_abs_can_shift_zero_with_no_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1441,7 +1441,7 @@ fn ror_abs_can_shift_zero_with_no_carry() {
 
 #[test]
 fn ror_abs_can_shift_value_resulting_in_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -1468,7 +1468,7 @@ fn ror_abs_can_shift_value_resulting_in_negative_value() {
 
 #[test]
 fn ror_abs_x_can_shift_bit_out_of_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -1495,7 +1495,7 @@ fn ror_abs_x_can_shift_bit_out_of_carry() {
 
 #[test]
 fn ror_abs_x_can_shift_bit_into_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1522,7 +1522,7 @@ fn ror_abs_x_can_shift_bit_into_carry() {
 
 #[test]
 fn ror_abs_x_can_shift_zero_with_no_carry() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -1549,7 +1549,7 @@ fn ror_abs_x_can_shift_zero_with_no_carry() {
 
 #[test]
 fn ror_abs_x_can_shift_value_resulting_in_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
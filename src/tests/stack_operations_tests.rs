@@ -19,7 +19,7 @@ fn verify_unmodified_flags(cpu: &CPU, cpu_copy: &CPU) {
 
 #[test]
 fn tsx_can_transfer_stack_pointer() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status = 0xFF.into();
@@ -40,7 +40,7 @@ fn tsx_can_transfer_stack_pointer() {
 
 #[test]
 fn tsx_can_transfer_zero_stack_pointer() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status = 0xFF.into();
@@ -62,7 +62,7 @@ fn tsx_can_transfer_zero_stack_pointer() {
 
 #[test]
 fn tsx_can_transfer_negative_stack_pointer() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status = 0xFF.into();
@@ -84,7 +84,7 @@ fn tsx_can_transfer_negative_stack_pointer() {
 
 #[test]
 fn txs_can_transfer_x_register_to_stack_pointer() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.x_register = 0xFF;
@@ -102,7 +102,7 @@ fn txs_can_transfer_x_register_to_stack_pointer() {
 
 #[test]
 fn pha_can_push_a_register_to_stack() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0x42;
@@ -121,7 +121,7 @@ fn pha_can_push_a_register_to_stack() {
 
 #[test]
 fn pla_can_pull_value_from_stack_to_a_register() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0x00;
@@ -141,7 +141,7 @@ fn pla_can_pull_value_from_stack_to_a_register() {
 
 #[test]
 fn pla_can_pull_zero_value_from_stack_to_a_register() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.zero = false;
@@ -165,7 +165,7 @@ fn pla_can_pull_zero_value_from_stack_to_a_register() {
 
 #[test]
 fn pla_can_pull_negative_value_from_stack_to_a_register() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.zero = true;
@@ -189,18 +189,14 @@ fn pla_can_pull_negative_value_from_stack_to_a_register() {
 
 #[test]
 fn php_can_push_zero_to_stack() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status = 0x0.into();
 
     memory[0xFF00] = Instruction::InsPhp as Byte;
 
-    let mut cpu_copy = cpu;
-
-    cpu_copy.status.unused = true;
-    cpu_copy.status.break_command = true;
-    cpu_copy.status.interupt_disable = true;
+    let cpu_copy = cpu;
 
     let cycles = cpu.execute(3, &mut memory);
 
@@ -212,7 +208,7 @@ fn php_can_push_zero_to_stack() {
 
 #[test]
 fn php_can_push_processor_status_to_stack() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status = 0xFF.into();
@@ -220,10 +216,7 @@ fn php_can_push_processor_status_to_stack() {
 
     memory[0xFF00] = Instruction::InsPhp as Byte;
 
-    let mut cpu_copy = cpu;
-
-    cpu_copy.status.unused = true;
-    cpu_copy.status.break_command = true;
+    let cpu_copy = cpu;
 
     let cycles = cpu.execute(3, &mut memory);
 
@@ -233,9 +226,29 @@ fn php_can_push_processor_status_to_stack() {
     assert_eq!(cpu.stack_pointer, 0xFE);
 }
 
+#[test]
+fn php_does_not_mask_a_pending_irq() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+
+    cpu.status = 0x0.into();
+    memory[0xFFFE] = 0x00;
+    memory[0xFFFF] = 0x80;
+    memory[0xFF00] = Instruction::InsPhp as Byte;
+
+    cpu.execute(3, &mut memory).unwrap();
+    assert_eq!(cpu.status, ProcessorFlags::from(0x0));
+
+    cpu.set_irq(true);
+    let cycles = cpu.execute(7, &mut memory);
+
+    assert_eq!(cycles, Ok(7));
+    assert_eq!(cpu.program_counter, 0x8000);
+}
+
 #[test]
 fn plp_can_pull_processor_status_from_stack() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status = 0x00.into();
@@ -7,7 +7,7 @@ use crate::{
 // CLC
 #[test]
 fn clc_will_clear_carry_flag() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = true;
@@ -31,7 +31,7 @@ fn clc_will_clear_carry_flag() {
 // SEC
 #[test]
 fn sec_will_set_carry_flag() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -55,7 +55,7 @@ fn sec_will_set_carry_flag() {
 // CLD
 #[test]
 fn cld_will_clear_decimal_mode_flag() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.decimal_mode = true;
@@ -79,7 +79,7 @@ fn cld_will_clear_decimal_mode_flag() {
 // SED
 #[test]
 fn sed_will_set_decimal_mode_flag() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.carry = false;
@@ -103,7 +103,7 @@ fn sed_will_set_decimal_mode_flag() {
 // CLI
 #[test]
 fn cli_will_clear_interrupt_disable_flag() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.decimal_mode = true;
@@ -127,7 +127,7 @@ fn cli_will_clear_interrupt_disable_flag() {
 // SEI
 #[test]
 fn sei_will_set_interrupt_disable_flag() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.interupt_disable = false;
@@ -151,7 +151,7 @@ fn sei_will_set_interrupt_disable_flag() {
 // CLV
 #[test]
 fn clv_will_clear_overflow_flag() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     cpu.status.overflow = true;
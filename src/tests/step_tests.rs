@@ -0,0 +1,162 @@
+use crate::{
+    cpu::{Byte, CpuVariant, StepResult, CPU},
+    instructions::{Instruction, InstructionsError},
+    memory::Memory,
+};
+
+#[test]
+fn step_runs_one_instruction_and_reports_it() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsLdaAbs as Byte;
+    memory[0xFF01] = 0x80;
+    memory[0xFF02] = 0x44;
+    memory[0xFF03] = Instruction::InsLdaIm as Byte;
+    memory[0x4480] = 0x37;
+
+    let result = cpu.step(&mut memory).unwrap().unwrap();
+
+    assert_eq!(result.program_counter, 0xFF00);
+    assert_eq!(result.opcode, Instruction::InsLdaAbs as Byte);
+    assert_eq!(result.instruction, Some(Instruction::InsLdaAbs));
+    assert_eq!(result.operand_bytes(), &[0x80, 0x44]);
+    assert_eq!(result.effective_address, Some(0x4480));
+    assert_eq!(result.cycles, 4);
+    assert!(!result.branch_taken);
+    assert!(!result.page_crossed);
+    assert_eq!(cpu.a_register, 0x37);
+    assert_eq!(cpu.program_counter, 0xFF03);
+}
+
+#[test]
+fn step_reports_implied_instruction_without_operand() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsInx as Byte;
+
+    let result = cpu.step(&mut memory).unwrap().unwrap();
+
+    assert!(result.operand_bytes().is_empty());
+    assert_eq!(result.effective_address, None);
+    assert_eq!(result.cycles, 2);
+}
+
+#[test]
+fn step_reports_page_crossed_on_indexed_read() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsLdaAbsX as Byte;
+    memory[0xFF01] = 0xFF;
+    memory[0xFF02] = 0x44;
+    cpu.x_register = 0x01;
+
+    let result = cpu.step(&mut memory).unwrap().unwrap();
+
+    assert_eq!(result.effective_address, Some(0x4500));
+    assert_eq!(result.cycles, 5);
+    assert!(result.page_crossed);
+}
+
+#[test]
+fn step_reports_branch_taken_across_page() {
+    let mut cpu = CPU::start_at(0xFEFD);
+    let mut memory = Memory::initialize();
+    memory[0xFEFD] = Instruction::InsBne as Byte;
+    memory[0xFEFE] = 0x02;
+
+    let result = cpu.step(&mut memory).unwrap().unwrap();
+
+    assert_eq!(result.effective_address, Some(0xFF01));
+    assert_eq!(result.cycles, 4);
+    assert!(result.branch_taken);
+    assert!(result.page_crossed);
+    assert_eq!(cpu.program_counter, 0xFF01);
+}
+
+#[test]
+fn step_reports_branch_not_taken() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsBeq as Byte;
+    memory[0xFF01] = 0x10;
+
+    let result = cpu.step(&mut memory).unwrap().unwrap();
+
+    assert_eq!(result.effective_address, Some(0xFF12));
+    assert_eq!(result.cycles, 2);
+    assert!(!result.branch_taken);
+    assert!(!result.page_crossed);
+    assert_eq!(cpu.program_counter, 0xFF02);
+}
+
+#[test]
+fn step_reports_jump_target_as_effective_address() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsJsr as Byte;
+    memory[0xFF01] = 0x00;
+    memory[0xFF02] = 0x80;
+
+    let result = cpu.step(&mut memory).unwrap().unwrap();
+
+    assert_eq!(result.effective_address, Some(0x8000));
+    assert_eq!(result.cycles, 6);
+}
+
+#[test]
+fn step_services_pending_interrupt_before_instruction() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFFFA] = 0x00;
+    memory[0xFFFB] = 0x90;
+    memory[0x9000] = Instruction::InsNop as Byte;
+    cpu.set_nmi(true);
+
+    let result = cpu.step(&mut memory).unwrap().unwrap();
+
+    assert!(result.interrupt_taken);
+    assert_eq!(result.program_counter, 0x9000);
+    assert_eq!(result.instruction, Some(Instruction::InsNop));
+    assert_eq!(result.cycles, 7 + 2);
+}
+
+#[test]
+fn step_returns_none_when_cpu_is_stopped_or_waiting() {
+    let mut cpu = CPU::start_at(0xFF00).with_variant(CpuVariant::Cmos65C02);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsWai.opcode();
+
+    assert!(cpu.step(&mut memory).unwrap().is_some());
+    assert_eq!(cpu.step(&mut memory), Ok(None));
+
+    cpu.waiting_for_interrupt = false;
+    cpu.stopped = true;
+    assert_eq!(cpu.step(&mut memory), Ok(None));
+}
+
+#[test]
+fn step_reports_undefined_65c02_nop() {
+    let mut cpu = CPU::start_at(0xFF00).with_variant(CpuVariant::Cmos65C02);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = 0xDC;
+    memory[0xFF01] = 0x34;
+    memory[0xFF02] = 0x12;
+
+    let result = cpu.step(&mut memory).unwrap().unwrap();
+
+    assert_eq!(result.instruction, None);
+    assert_eq!(result.operand_bytes(), &[0x34, 0x12]);
+    assert_eq!(result.cycles, 4);
+    assert_eq!(cpu.program_counter, 0xFF03);
+}
+
+#[test]
+fn step_returns_error_for_unknown_opcode() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = 0x02;
+
+    let result = cpu.step(&mut memory);
+
+    assert_eq!(result, Err(InstructionsError::InstructionDoesntExist(0x02)));
+}
@@ -15,7 +15,7 @@ fn verify_unmodified_flags(cpu: &CPU, cpu_copy: &CPU) {
 }
 
 fn test_store_zero_page(opcode: Instruction, register_to_test: RegisterToTest) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     match register_to_test {
@@ -43,7 +43,7 @@ fn test_store_zero_page_plus_register(
     register_to_test: RegisterToTest,
     register_to_add: RegisterToTest,
 ) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     match register_to_test {
@@ -71,7 +71,7 @@ fn test_store_zero_page_plus_register(
 }
 
 fn test_store_absolute(opcode: Instruction, register_to_test: RegisterToTest) {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     match register_to_test {
@@ -117,7 +117,7 @@ fn sta_absolute_can_store_value() {
 
 #[test]
 fn sta_absolute_x_can_store_value() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0x2F;
@@ -140,7 +140,7 @@ fn sta_absolute_x_can_store_value() {
 
 #[test]
 fn sta_absolute_y_can_store_value() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0x2F;
@@ -163,7 +163,7 @@ fn sta_absolute_y_can_store_value() {
 
 #[test]
 fn sta_indirect_x_can_store_value() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0x2F;
@@ -187,7 +187,7 @@ fn sta_indirect_x_can_store_value() {
 
 #[test]
 fn sta_indirect_y_can_store_value() {
-    let mut cpu = CPU::reset(None);
+    let mut cpu = CPU::start_at(0xFFFC);
     let mut memory = Memory::initialize();
 
     cpu.a_register = 0x2F;
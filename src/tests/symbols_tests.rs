@@ -0,0 +1,144 @@
+use crate::symbols::{SourceLocation, SymbolError, SymbolTable};
+
+const DBGINFO: &str = "\
+version\tmajor=2,minor=0
+info\tcsym=0,file=2,lib=0,line=4,mod=1,scope=1,seg=2,span=4,sym=3,type=0
+file\tid=0,name=\"main.s\",size=120,mtime=0x65000000,mod=0
+file\tid=1,name=\"lib, with comma.inc\",size=40,mtime=0x65000000,mod=0
+line\tid=0,file=0,line=3,span=0
+line\tid=1,file=0,line=4,span=1
+line\tid=2,file=1,line=7,span=2+3
+line\tid=3,file=0,line=2
+mod\tid=0,name=\"main.o\",file=0
+seg\tid=0,name=\"CODE\",start=0x00C000,size=0x0010,addrsize=absolute,type=ro,oname=\"a.bin\",ooffs=0
+seg\tid=1,name=\"DATA\",start=0x000200,size=0x0004,addrsize=absolute,type=rw
+span\tid=0,seg=0,start=0,size=2
+span\tid=1,seg=0,start=2,size=3
+span\tid=2,seg=0,start=5,size=1
+span\tid=3,seg=1,start=0,size=4
+scope\tid=0,name=\"\",mod=0,size=16,span=0
+sym\tid=0,name=\"start\",addrsize=absolute,scope=0,def=3,ref=1,val=0xC000,seg=0,type=lab
+sym\tid=1,name=\"loop\",addrsize=absolute,scope=0,def=4,val=0xC002,seg=0,type=lab
+sym\tid=2,name=\"COUNT\",addrsize=zeropage,scope=0,def=2,val=0x5,type=equ
+";
+
+#[test]
+fn dbginfo_labels_become_symbols() {
+    let mut symbols = SymbolTable::new();
+
+    symbols.load_ld65_dbginfo(DBGINFO).unwrap();
+
+    assert_eq!(symbols.address_of("start"), Some(0xC000));
+    assert_eq!(symbols.address_of("loop"), Some(0xC002));
+    assert_eq!(symbols.address_of("COUNT"), None);
+    assert_eq!(symbols.name_at(0xC002), Some("loop"));
+    assert_eq!(symbols.name_at(0xC001), None);
+}
+
+#[test]
+fn dbginfo_spans_map_addresses_to_source_lines() {
+    let mut symbols = SymbolTable::new();
+
+    symbols.load_ld65_dbginfo(DBGINFO).unwrap();
+
+    assert_eq!(
+        symbols.source_line(0xC001),
+        Some(SourceLocation {
+            file: "main.s",
+            line: 3
+        })
+    );
+    assert_eq!(
+        symbols.source_line(0xC004),
+        Some(SourceLocation {
+            file: "main.s",
+            line: 4
+        })
+    );
+    assert_eq!(
+        symbols.source_line(0x0203),
+        Some(SourceLocation {
+            file: "lib, with comma.inc",
+            line: 7
+        })
+    );
+    assert_eq!(symbols.source_line(0xC006), None);
+}
+
+#[test]
+fn dbginfo_errors_report_line() {
+    let mut symbols = SymbolTable::new();
+
+    assert_eq!(
+        symbols.load_ld65_dbginfo("version\tmajor=2,minor=0\nsym\tid=0,name=\"x\",type=lab\n"),
+        Err(SymbolError::MissingField {
+            line: 2,
+            field: "val"
+        })
+    );
+    assert_eq!(
+        symbols.load_ld65_dbginfo("seg\tid=0,start=0xZZ\n"),
+        Err(SymbolError::InvalidNumber { line: 1 })
+    );
+    assert_eq!(
+        symbols.load_ld65_dbginfo("file\tid=0,name=\"open\n"),
+        Err(SymbolError::InvalidLine { line: 1 })
+    );
+    assert_eq!(
+        symbols.load_ld65_dbginfo("line\tid=0,file=4,line=1,span=0\n"),
+        Err(SymbolError::UnknownId { line: 1, id: 4 })
+    );
+}
+
+#[test]
+fn vice_labels_are_loaded() {
+    let mut symbols = SymbolTable::new();
+
+    symbols
+        .load_vice_labels("al C:1000 .start\nbreak 1000\nal C:10a0 .irq_handler\nal 0020 ptr\n")
+        .unwrap();
+
+    assert_eq!(symbols.address_of("start"), Some(0x1000));
+    assert_eq!(symbols.address_of("irq_handler"), Some(0x10A0));
+    assert_eq!(symbols.name_at(0x0020), Some("ptr"));
+}
+
+#[test]
+fn vice_label_errors_report_line() {
+    let mut symbols = SymbolTable::new();
+
+    assert_eq!(
+        symbols.load_vice_labels("al C:1000 .start\nal C:10G0 .bad\n"),
+        Err(SymbolError::InvalidNumber { line: 2 })
+    );
+    assert_eq!(
+        symbols.load_vice_labels("al C:1000\n"),
+        Err(SymbolError::InvalidLine { line: 1 })
+    );
+}
+
+#[test]
+fn describe_uses_nearest_symbol() {
+    let mut symbols = SymbolTable::new();
+    symbols.insert("start", 0x1000);
+    symbols.insert("main", 0x1000);
+    symbols.insert("table", 0x1100);
+
+    assert_eq!(symbols.describe(0x1000, 16), "start");
+    assert_eq!(symbols.describe(0x1003, 16), "start+3");
+    assert_eq!(symbols.describe(0x1050, 16), "$1050");
+    assert_eq!(symbols.describe(0x0FFF, 16), "$0FFF");
+    assert_eq!(symbols.nearest(0x1105), Some(("table", 5)));
+}
+
+#[test]
+fn redefining_a_symbol_moves_it() {
+    let mut symbols = SymbolTable::new();
+    symbols.insert("start", 0x1000);
+
+    symbols.insert("start", 0x2000);
+
+    assert_eq!(symbols.address_of("start"), Some(0x2000));
+    assert_eq!(symbols.name_at(0x1000), None);
+    assert_eq!(symbols.name_at(0x2000), Some("start"));
+}
@@ -18,7 +18,7 @@ fn verify_unmodified_flags(cpu: &CPU, cpu_copy: &CPU) {
 // TAX
 #[test]
 fn tax_can_transfer_non_negative_non_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -44,7 +44,7 @@ fn tax_can_transfer_non_negative_non_zero_value() {
 
 #[test]
 fn tax_can_transfer_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -70,7 +70,7 @@ fn tax_can_transfer_zero_value() {
 
 #[test]
 fn tax_can_transfer_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -97,7 +97,7 @@ fn tax_can_transfer_negative_value() {
 // TAY
 #[test]
 fn tay_can_transfer_non_negative_non_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -123,7 +123,7 @@ fn tay_can_transfer_non_negative_non_zero_value() {
 
 #[test]
 fn tay_can_transfer_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -149,7 +149,7 @@ fn tay_can_transfer_zero_value() {
 
 #[test]
 fn tay_can_transfer_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -176,7 +176,7 @@ fn tay_can_transfer_negative_value() {
 // TXA
 #[test]
 fn txa_can_transfer_non_negative_non_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -202,7 +202,7 @@ fn txa_can_transfer_non_negative_non_zero_value() {
 
 #[test]
 fn txa_can_transfer_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -228,7 +228,7 @@ fn txa_can_transfer_zero_value() {
 
 #[test]
 fn txa_can_transfer_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -255,7 +255,7 @@ fn txa_can_transfer_negative_value() {
 // TYA
 #[test]
 fn tya_can_transfer_non_negative_non_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -281,7 +281,7 @@ fn tya_can_transfer_non_negative_non_zero_value() {
 
 #[test]
 fn tya_can_transfer_zero_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -307,7 +307,7 @@ fn tya_can_transfer_zero_value() {
 
 #[test]
 fn tya_can_transfer_negative_value() {
-    let mut cpu = CPU::reset(Some(0xFF00));
+    let mut cpu = CPU::start_at(0xFF00);
     let mut memory = Memory::initialize();
 
     let cpu_copy = cpu;
@@ -0,0 +1,428 @@
+use crate::{
+    cpu::{Byte, CpuVariant, CPU},
+    instructions::{Instruction, InstructionsError},
+    memory::Memory,
+};
+
+fn undocumented_cpu() -> CPU {
+    let mut cpu = CPU::start_at(0xFF00);
+    cpu.undocumented_opcodes = true;
+    cpu
+}
+
+#[test]
+fn undocumented_opcodes_are_rejected_unless_enabled() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsLaxZp as Byte;
+
+    let cycles = cpu.execute(3, &mut memory);
+
+    assert_eq!(
+        cycles,
+        Err(InstructionsError::InstructionDoesntExist(
+            Instruction::InsLaxZp as Byte
+        ))
+    );
+}
+
+#[test]
+fn custom_opcodes_are_not_replaced_by_undocumented_ones() {
+    assert!(matches!(
+        Instruction::decode(0x43, CpuVariant::Nmos6502, true),
+        Ok(Instruction::InsDbgAbs)
+    ));
+    assert!(matches!(
+        Instruction::decode(0x44, CpuVariant::Nmos6502, true),
+        Ok(Instruction::InsDbgIm)
+    ));
+}
+
+// LAX
+#[test]
+fn lax_zero_page_loads_a_and_x() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsLaxZp as Byte;
+    memory[0xFF01] = 0x42;
+    memory[0x0042] = 0x84;
+
+    let cycles = cpu.execute(3, &mut memory);
+
+    assert_eq!(cycles, Ok(3));
+    assert_eq!(cpu.a_register, 0x84);
+    assert_eq!(cpu.x_register, 0x84);
+    assert!(cpu.status.negative);
+    assert!(!cpu.status.zero);
+}
+
+#[test]
+fn lax_absolute_y_takes_extra_cycle_when_crossing_page() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.y_register = 0xFF;
+    memory[0xFF00] = Instruction::InsLaxAbsY as Byte;
+    memory[0xFF01] = 0x02;
+    memory[0xFF02] = 0x44;
+    memory[0x4501] = 0x37;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(cpu.a_register, 0x37);
+    assert_eq!(cpu.x_register, 0x37);
+}
+
+#[test]
+fn lax_indirect_y_takes_extra_cycle_when_crossing_page() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.y_register = 0xFF;
+    memory[0xFF00] = Instruction::InsLaxIndY as Byte;
+    memory[0xFF01] = 0x02;
+    memory[0x0002] = 0x02;
+    memory[0x0003] = 0x80;
+    memory[0x8101] = 0x37;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(cpu.a_register, 0x37);
+    assert_eq!(cpu.x_register, 0x37);
+}
+
+// SAX
+#[test]
+fn sax_zero_page_y_stores_a_and_x() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0b1100_1100;
+    cpu.x_register = 0b1010_1010;
+    cpu.y_register = 0x0F;
+    memory[0xFF00] = Instruction::InsSaxZpY as Byte;
+    memory[0xFF01] = 0x80;
+
+    let cpu_copy = cpu;
+
+    let cycles = cpu.execute(4, &mut memory);
+
+    assert_eq!(cycles, Ok(4));
+    assert_eq!(memory[0x008F_u16], 0b1000_1000);
+    assert_eq!(cpu.status, cpu_copy.status);
+}
+
+// DCP
+#[test]
+fn dcp_absolute_x_decrements_memory_and_compares() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0x41;
+    cpu.x_register = 0x01;
+    memory[0xFF00] = Instruction::InsDcpAbsX as Byte;
+    memory[0xFF01] = 0x00;
+    memory[0xFF02] = 0x80;
+    memory[0x8001] = 0x42;
+
+    let cycles = cpu.execute(7, &mut memory);
+
+    assert_eq!(cycles, Ok(7));
+    assert_eq!(memory[0x8001_u16], 0x41);
+    assert!(cpu.status.zero);
+    assert!(cpu.status.carry);
+}
+
+// ISC
+#[test]
+fn isc_zero_page_increments_memory_and_subtracts() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0x10;
+    cpu.status.carry = true;
+    memory[0xFF00] = Instruction::InsIscZp as Byte;
+    memory[0xFF01] = 0x42;
+    memory[0x0042] = 0x04;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(memory[0x0042_u16], 0x05);
+    assert_eq!(cpu.a_register, 0x0B);
+    assert!(cpu.status.carry);
+}
+
+// SLO
+#[test]
+fn slo_indirect_y_shifts_memory_and_ors_into_a() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0x01;
+    cpu.y_register = 0x04;
+    memory[0xFF00] = Instruction::InsSloIndY as Byte;
+    memory[0xFF01] = 0x02;
+    memory[0x0002] = 0x00;
+    memory[0x0003] = 0x80;
+    memory[0x8004] = 0b1100_0000;
+
+    let cycles = cpu.execute(8, &mut memory);
+
+    assert_eq!(cycles, Ok(8));
+    assert_eq!(memory[0x8004_u16], 0b1000_0000);
+    assert_eq!(cpu.a_register, 0b1000_0001);
+    assert!(cpu.status.carry);
+    assert!(cpu.status.negative);
+}
+
+// RLA
+#[test]
+fn rla_zero_page_x_rotates_memory_and_ands_into_a() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0x0F;
+    cpu.x_register = 0x01;
+    cpu.status.carry = true;
+    memory[0xFF00] = Instruction::InsRlaZpX as Byte;
+    memory[0xFF01] = 0x41;
+    memory[0x0042] = 0b1000_0010;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(memory[0x0042_u16], 0b0000_0101);
+    assert_eq!(cpu.a_register, 0b0000_0101);
+    assert!(cpu.status.carry);
+}
+
+// SRE
+#[test]
+fn sre_absolute_shifts_memory_and_eors_into_a() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0xFF;
+    memory[0xFF00] = Instruction::InsSreAbs as Byte;
+    memory[0xFF01] = 0x00;
+    memory[0xFF02] = 0x80;
+    memory[0x8000] = 0b0000_0011;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(memory[0x8000_u16], 0b0000_0001);
+    assert_eq!(cpu.a_register, 0xFE);
+    assert!(cpu.status.carry);
+}
+
+// RRA
+#[test]
+fn rra_indirect_x_rotates_memory_and_adds_to_a() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0x10;
+    cpu.x_register = 0x02;
+    memory[0xFF00] = Instruction::InsRraIndX as Byte;
+    memory[0xFF01] = 0x40;
+    memory[0x0042] = 0x00;
+    memory[0x0043] = 0x80;
+    memory[0x8000] = 0x05;
+
+    let cycles = cpu.execute(8, &mut memory);
+
+    assert_eq!(cycles, Ok(8));
+    assert_eq!(memory[0x8000_u16], 0x02);
+    // The carry shifted out of the rotate feeds the addition
+    assert_eq!(cpu.a_register, 0x13);
+    assert!(!cpu.status.carry);
+}
+
+// Immediate
+#[test]
+fn anc_copies_negative_into_carry() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0xF0;
+    memory[0xFF00] = Instruction::InsAncIm2B as Byte;
+    memory[0xFF01] = 0x81;
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.a_register, 0x80);
+    assert!(cpu.status.negative);
+    assert!(cpu.status.carry);
+}
+
+#[test]
+fn alr_ands_then_shifts_right() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0xFF;
+    memory[0xFF00] = Instruction::InsAlrIm as Byte;
+    memory[0xFF01] = 0x03;
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.a_register, 0x01);
+    assert!(cpu.status.carry);
+}
+
+#[test]
+fn arr_ands_then_rotates_right_with_special_flags() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0xFF;
+    cpu.status.carry = true;
+    memory[0xFF00] = Instruction::InsArrIm as Byte;
+    memory[0xFF01] = 0x80;
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.a_register, 0xC0);
+    assert!(cpu.status.carry);
+    assert!(cpu.status.overflow);
+    assert!(cpu.status.negative);
+}
+
+#[test]
+fn sbx_subtracts_from_a_and_x_into_x() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 0x3F;
+    cpu.x_register = 0x1F;
+    memory[0xFF00] = Instruction::InsSbxIm as Byte;
+    memory[0xFF01] = 0x20;
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.x_register, 0xFF);
+    assert_eq!(cpu.a_register, 0x3F);
+    assert!(!cpu.status.carry);
+    assert!(cpu.status.negative);
+}
+
+#[test]
+fn sbc_eb_behaves_like_sbc_immediate() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.a_register = 20;
+    cpu.status.carry = true;
+    memory[0xFF00] = Instruction::InsSbcImEB as Byte;
+    memory[0xFF01] = 17;
+
+    let cycles = cpu.execute(2, &mut memory);
+
+    assert_eq!(cycles, Ok(2));
+    assert_eq!(cpu.a_register, 3);
+    assert!(cpu.status.carry);
+}
+
+// NOP
+#[test]
+fn nop_absolute_x_takes_extra_cycle_when_crossing_page() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.x_register = 0xFF;
+    memory[0xFF00] = Instruction::InsNopAbsX1C as Byte;
+    memory[0xFF01] = 0x01;
+    memory[0xFF02] = 0x80;
+
+    let cpu_copy = cpu;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(cpu.program_counter, 0xFF03);
+    assert_eq!(cpu.status, cpu_copy.status);
+    assert_eq!(cpu.a_register, cpu_copy.a_register);
+}
+
+#[test]
+fn nop_variants_skip_their_operands() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsNopImp1A as Byte;
+    memory[0xFF01] = Instruction::InsNopIm80 as Byte;
+    memory[0xFF02] = 0xFF;
+    memory[0xFF03] = Instruction::InsNopZp04 as Byte;
+    memory[0xFF04] = 0xFF;
+    memory[0xFF05] = Instruction::InsNopZpX14 as Byte;
+    memory[0xFF06] = 0xFF;
+    memory[0xFF07] = Instruction::InsNopAbs0C as Byte;
+    memory[0xFF08] = 0xFF;
+    memory[0xFF09] = 0xFF;
+
+    let cycles = cpu.execute(2 + 2 + 3 + 4 + 4, &mut memory);
+
+    assert_eq!(cycles, Ok(15));
+    assert_eq!(cpu.program_counter, 0xFF0A);
+}
+
+// Unstable
+#[test]
+fn shx_stores_x_and_high_byte_plus_one() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.x_register = 0xFF;
+    cpu.y_register = 0x01;
+    memory[0xFF00] = Instruction::InsShxAbsY as Byte;
+    memory[0xFF01] = 0x00;
+    memory[0xFF02] = 0x12;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(memory[0x1201_u16], 0x13);
+}
+
+#[test]
+fn las_ands_memory_with_stack_pointer() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    cpu.stack_pointer = 0xF0;
+    memory[0xFF00] = Instruction::InsLasAbsY as Byte;
+    memory[0xFF01] = 0x00;
+    memory[0xFF02] = 0x80;
+    memory[0x8000] = 0x3C;
+
+    let cycles = cpu.execute(4, &mut memory);
+
+    assert_eq!(cycles, Ok(4));
+    assert_eq!(cpu.a_register, 0x30);
+    assert_eq!(cpu.x_register, 0x30);
+    assert_eq!(cpu.stack_pointer, 0x30);
+}
+
+// JAM
+#[test]
+fn jam_locks_up_the_cpu() {
+    let mut cpu = undocumented_cpu();
+    let mut memory = Memory::initialize();
+
+    memory[0xFF00] = Instruction::InsJam02 as Byte;
+
+    let result = cpu.execute(2, &mut memory);
+
+    assert_eq!(result, Err(InstructionsError::CpuJammed(0x02)));
+    assert_eq!(cpu.program_counter, 0xFF00);
+}
@@ -0,0 +1,213 @@
+use crate::{
+    bus::Bus,
+    cpu::{Byte, Word, CPU},
+    instructions::{Instruction, InstructionsError},
+    memory::Memory,
+    watchpoints::{WatchKind, Watched, Watchpoint, WatchpointHit},
+};
+
+fn watched_program(program: &[Byte]) -> (CPU, Watched<Memory>) {
+    let mut bus = Watched::new(Memory::initialize());
+    for (offset, byte) in program.iter().enumerate() {
+        bus.write(0x0200 + offset as Word, *byte);
+    }
+    (CPU::start_at(0x0200), bus)
+}
+
+fn hit(
+    program_counter: Word,
+    watchpoint: usize,
+    kind: WatchKind,
+    address: Word,
+    old_value: Byte,
+    new_value: Byte,
+) -> InstructionsError {
+    InstructionsError::WatchpointHit {
+        program_counter,
+        hit: WatchpointHit {
+            watchpoint,
+            kind,
+            address,
+            old_value,
+            new_value,
+        },
+    }
+}
+
+#[test]
+fn write_watchpoint_stops_after_instruction() {
+    let (mut cpu, mut bus) = watched_program(&[
+        Instruction::InsLdaIm as Byte,
+        0x42,
+        Instruction::InsStaZp as Byte,
+        0x10,
+        Instruction::InsNop as Byte,
+    ]);
+    bus.inner[0x0010_u16] = 0x07;
+    let id = bus.add(Watchpoint::write(0x0010..=0x0010));
+
+    let result = cpu.execute(100, &mut bus);
+
+    assert_eq!(
+        result,
+        Err(hit(0x0202, id, WatchKind::Write, 0x0010, 0x07, 0x42))
+    );
+    assert_eq!(cpu.program_counter, 0x0204);
+    assert_eq!(bus.inner[0x0010_u16], 0x42);
+}
+
+#[test]
+fn watchpoint_with_value_only_fires_on_match() {
+    let (mut cpu, mut bus) = watched_program(&[
+        Instruction::InsIncZp as Byte,
+        0x10,
+        Instruction::InsIncZp as Byte,
+        0x10,
+        Instruction::InsIncZp as Byte,
+        0x10,
+    ]);
+    let id = bus.add(Watchpoint::write(0x0000..=0x00FF).with_value(0x02));
+
+    let result = cpu.execute(100, &mut bus);
+
+    assert_eq!(
+        result,
+        Err(hit(0x0202, id, WatchKind::Write, 0x0010, 0x01, 0x02))
+    );
+}
+
+#[test]
+fn read_watchpoint_fires_inside_range() {
+    let (mut cpu, mut bus) = watched_program(&[
+        Instruction::InsLdaAbs as Byte,
+        0x00,
+        0x30,
+        Instruction::InsLdaAbs as Byte,
+        0x80,
+        0x40,
+    ]);
+    bus.inner[0x4080_u16] = 0x99;
+    bus.add(Watchpoint::write(0x4000..=0x40FF));
+    let id = bus.add(Watchpoint::read(0x4000..=0x40FF));
+
+    let result = cpu.execute(100, &mut bus);
+
+    assert_eq!(
+        result,
+        Err(hit(0x0203, id, WatchKind::Read, 0x4080, 0x99, 0x99))
+    );
+    assert_eq!(cpu.a_register, 0x99);
+}
+
+#[test]
+fn execute_watchpoint_stops_before_instruction_and_resumes() {
+    // loop: INX / JMP loop
+    let (mut cpu, mut bus) = watched_program(&[
+        Instruction::InsInx as Byte,
+        Instruction::InsJmpAbs as Byte,
+        0x00,
+        0x02,
+    ]);
+    let id = bus.add(Watchpoint::execute(0x0200));
+    let expected = Err(hit(
+        0x0200,
+        id,
+        WatchKind::Execute,
+        0x0200,
+        Instruction::InsInx as Byte,
+        Instruction::InsInx as Byte,
+    ));
+
+    assert_eq!(cpu.execute(100, &mut bus), expected);
+    assert_eq!(cpu.program_counter, 0x0200);
+    assert_eq!(cpu.x_register, 0);
+    assert_eq!(cpu.instructions_retired, 0);
+
+    assert_eq!(cpu.execute(100, &mut bus), expected);
+    assert_eq!(cpu.program_counter, 0x0200);
+    assert_eq!(cpu.x_register, 1);
+    assert_eq!(cpu.instructions_retired, 2);
+}
+
+#[test]
+fn operand_reads_do_not_trigger_execute_watchpoint() {
+    let (mut cpu, mut bus) = watched_program(&[
+        Instruction::InsLdaAbs as Byte,
+        0x01,
+        0x02,
+        Instruction::InsNop as Byte,
+    ]);
+    bus.add(Watchpoint::execute(0x0201));
+
+    assert_eq!(cpu.execute(6, &mut bus), Ok(6));
+}
+
+#[test]
+fn removed_watchpoint_does_not_fire() {
+    let (mut cpu, mut bus) = watched_program(&[Instruction::InsStaZp as Byte, 0x10]);
+    let id = bus.add(Watchpoint::write(0x0010..=0x0010));
+
+    assert_eq!(bus.remove(id), Some(Watchpoint::write(0x0010..=0x0010)));
+    assert_eq!(bus.get(id), None);
+    assert_eq!(cpu.execute(3, &mut bus), Ok(3));
+}
+
+#[test]
+fn peek_does_not_fire_watchpoints() {
+    let mut bus = Watched::new(Memory::initialize());
+    bus.add(Watchpoint::read(0x0000..=0xFFFF));
+
+    bus.peek(0x1234);
+
+    assert_eq!(bus.take_fault(), None);
+}
+
+#[test]
+fn stack_push_hits_write_watchpoint() {
+    let (mut cpu, mut bus) = watched_program(&[Instruction::InsJsr as Byte, 0x00, 0x03]);
+    cpu.stack_pointer = 0xFF;
+    let id = bus.add(Watchpoint::write(0x0100..=0x01FF));
+
+    let result = cpu.step(&mut bus);
+
+    assert_eq!(
+        result,
+        Err(hit(0x0200, id, WatchKind::Write, 0x01FF, 0x00, 0x02))
+    );
+    assert_eq!(cpu.program_counter, 0x0300);
+}
+
+/// A pending IRQ with a write watchpoint on the stack page and the handler at $0300.
+fn irq_with_stack_watchpoint() -> (CPU, Watched<Memory>, InstructionsError) {
+    let (mut cpu, mut bus) = watched_program(&[Instruction::InsNop as Byte]);
+    bus.write(CPU::IRQ_VECTOR, 0x00);
+    bus.write(CPU::IRQ_VECTOR + 1, 0x03);
+    cpu.stack_pointer = 0xFF;
+    cpu.status.interupt_disable = false;
+    cpu.set_irq(true);
+    let id = bus.add(Watchpoint::write(0x0100..=0x01FF));
+    (
+        cpu,
+        bus,
+        hit(0x0200, id, WatchKind::Write, 0x01FF, 0x00, 0x02),
+    )
+}
+
+#[test]
+fn interrupt_entry_hits_stack_write_watchpoint() {
+    let (mut cpu, mut bus, expected) = irq_with_stack_watchpoint();
+
+    assert_eq!(cpu.execute(100, &mut bus), Err(expected));
+    assert_eq!(cpu.program_counter, 0x0300);
+    assert_eq!(cpu.instructions_retired, 0);
+}
+
+#[test]
+fn stepping_into_interrupt_hits_stack_write_watchpoint() {
+    let (mut cpu, mut bus, expected) = irq_with_stack_watchpoint();
+
+    assert_eq!(cpu.step(&mut bus), Err(expected));
+    assert_eq!(cpu.program_counter, 0x0300);
+    assert_eq!(cpu.instructions_retired, 0);
+    assert_eq!(cpu.total_cycles, 7);
+}
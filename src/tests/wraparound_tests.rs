@@ -0,0 +1,212 @@
+use crate::{
+    cpu::{Byte, CpuVariant, Word, CPU},
+    graphics_adapter::GraphicsAdapter,
+    instructions::Instruction,
+    memory::Memory,
+};
+use sdl2::pixels::Color;
+
+#[test]
+fn program_counter_wraps_past_end_of_memory() {
+    let mut cpu = CPU::start_at(0xFFFE);
+    let mut memory = Memory::initialize();
+    memory[0xFFFE] = Instruction::InsLdaAbs as Byte;
+    memory[0xFFFF] = 0x34;
+    memory[0x0000] = 0x12;
+    memory[0x1234] = 0x42;
+
+    let cycles = cpu.execute(4, &mut memory);
+
+    assert_eq!(cycles, Ok(4));
+    assert_eq!(cpu.a_register, 0x42);
+    assert_eq!(cpu.program_counter, 0x0001);
+}
+
+#[test]
+fn absolute_x_wraps_past_end_of_memory() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsLdaAbsX as Byte;
+    memory[0xFF01] = 0xFF;
+    memory[0xFF02] = 0xFF;
+    memory[0x0001] = 0x42;
+    cpu.x_register = 0x02;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(cpu.a_register, 0x42);
+}
+
+#[test]
+fn store_absolute_y_wraps_past_end_of_memory() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsStaAbsY as Byte;
+    memory[0xFF01] = 0xFF;
+    memory[0xFF02] = 0xFF;
+    cpu.a_register = 0x42;
+    cpu.y_register = 0x01;
+
+    let cycles = cpu.execute(5, &mut memory);
+
+    assert_eq!(cycles, Ok(5));
+    assert_eq!(memory[0x0000_u16], 0x42);
+}
+
+#[test]
+fn indirect_y_wraps_past_end_of_memory() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsLdaIndY as Byte;
+    memory[0xFF01] = 0x10;
+    memory[0x0010] = 0xFF;
+    memory[0x0011] = 0xFF;
+    memory[0x0003] = 0x42;
+    cpu.y_register = 0x04;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(cpu.a_register, 0x42);
+}
+
+#[test]
+fn jsr_pushes_wrapping_around_the_stack_page() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsJsr as Byte;
+    memory[0xFF01] = 0x00;
+    memory[0xFF02] = 0x80;
+    cpu.stack_pointer = 0x00;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(cpu.stack_pointer, 0xFE);
+    assert_eq!(memory[0x0100_u16], 0xFF);
+    assert_eq!(memory[0x01FF_u16], 0x02);
+}
+
+#[test]
+fn rts_pops_wrapping_around_the_stack_page() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsRts as Byte;
+    memory[0x0100] = 0x33;
+    memory[0x0101] = 0x44;
+    cpu.stack_pointer = 0xFF;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(cpu.stack_pointer, 0x01);
+    assert_eq!(cpu.program_counter, 0x4434);
+}
+
+#[test]
+fn rti_pops_wrapping_around_the_stack_page() {
+    let mut cpu = CPU::start_at(0xFF00);
+    let mut memory = Memory::initialize();
+    memory[0xFF00] = Instruction::InsRti as Byte;
+    memory[0x01FF] = 0x01;
+    memory[0x0100] = 0x00;
+    memory[0x0101] = 0x80;
+    cpu.stack_pointer = 0xFE;
+
+    let cycles = cpu.execute(6, &mut memory);
+
+    assert_eq!(cycles, Ok(6));
+    assert_eq!(cpu.stack_pointer, 0x01);
+    assert_eq!(cpu.program_counter, 0x8000);
+    assert!(cpu.status.carry);
+}
+
+#[test]
+fn load_program_wraps_past_end_of_memory() {
+    let cpu = CPU::new();
+    let mut memory = Memory::initialize();
+    let program = [0xFF, 0xFF, 0x11, 0x22];
+
+    let load_address = cpu.load_program(&program, program.len() as u16, &mut memory);
+
+    assert_eq!(load_address, 0xFFFF);
+    assert_eq!(memory[0xFFFF_u16], 0x11);
+    assert_eq!(memory[0x0000_u16], 0x22);
+}
+
+/// xorshift64, good enough to generate opcode streams.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn byte(&mut self) -> Byte {
+        self.next() as Byte
+    }
+
+    fn word(&mut self) -> Word {
+        self.next() as Word
+    }
+}
+
+#[test]
+fn random_opcode_streams_never_panic() {
+    let mut random = Random(0x6502_6502_6502_6502);
+    let mut memory = Memory::initialize();
+
+    for round in 0..300 {
+        for address in (0..=0xFFFF_u16).step_by(8) {
+            let bytes = random.next().to_le_bytes();
+            for (offset, byte) in bytes.iter().enumerate() {
+                memory[address + offset as Word] = *byte;
+            }
+        }
+
+        let mut cpu = CPU::new();
+        cpu.variant = match round % 3 {
+            0 => CpuVariant::Cmos65C02,
+            _ => CpuVariant::Nmos6502,
+        };
+        cpu.undocumented_opcodes = round % 3 == 1;
+        if round % 4 == 0 {
+            cpu.graphics_adapter = Some(GraphicsAdapter::new(Color::BLACK));
+        }
+        // Bias the start towards the edges of memory and the stack
+        cpu.program_counter = match round % 5 {
+            0 => 0xFFFF - random.byte() as Word,
+            _ => random.word(),
+        };
+        cpu.stack_pointer = match round % 5 {
+            1 => 0x00,
+            2 => 0xFF,
+            _ => random.byte(),
+        };
+        cpu.a_register = random.byte();
+        cpu.x_register = random.byte();
+        cpu.y_register = random.byte();
+        cpu.status = random.byte().into();
+
+        for _ in 0..200 {
+            if random.byte() < 0x10 {
+                cpu.set_irq(random.byte() & 1 != 0);
+                cpu.set_nmi(random.byte() & 1 != 0);
+            }
+            let result = match random.byte() & 1 {
+                0 => cpu
+                    .execute(random.byte() as i32 % 16, &mut memory)
+                    .map(|_| ()),
+                _ => cpu.step(&mut memory).map(|_| ()),
+            };
+            if result.is_err() {
+                // Unknown and JAM opcodes stop execution, start again somewhere else
+                cpu.program_counter = random.word();
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+    bus::{Bus, BusFault},
+    cpu::{Byte, Word},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    Read,
+    Write,
+    /// An opcode fetch, the CPU stops before running the instruction.
+    Execute,
+}
+
+/// Fires on `kind` accesses inside `range`, and only when the value read, written or
+/// fetched equals `value` if one is given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Watchpoint {
+    pub kind: WatchKind,
+    pub range: RangeInclusive<Word>,
+    pub value: Option<Byte>,
+}
+
+impl Watchpoint {
+    pub fn read(range: RangeInclusive<Word>) -> Watchpoint {
+        Watchpoint {
+            kind: WatchKind::Read,
+            range,
+            value: None,
+        }
+    }
+
+    pub fn write(range: RangeInclusive<Word>) -> Watchpoint {
+        Watchpoint {
+            kind: WatchKind::Write,
+            range,
+            value: None,
+        }
+    }
+
+    pub fn execute(address: Word) -> Watchpoint {
+        Watchpoint {
+            kind: WatchKind::Execute,
+            range: address..=address,
+            value: None,
+        }
+    }
+
+    pub fn with_value(mut self, value: Byte) -> Watchpoint {
+        self.value = Some(value);
+        self
+    }
+
+    fn matches(&self, kind: WatchKind, address: Word, value: Byte) -> bool {
+        self.kind == kind
+            && self.range.contains(&address)
+            && (self.value.is_none() || self.value == Some(value))
+    }
+}
+
+/// Which watchpoint fired and on what. For reads and fetches `old_value` and
+/// `new_value` are both the byte that was read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchpointHit {
+    pub watchpoint: usize,
+    pub kind: WatchKind,
+    pub address: Word,
+    pub old_value: Byte,
+    pub new_value: Byte,
+}
+
+/// Wraps a bus and reports watchpoint hits as bus faults, so the CPU stops after the
+/// instruction that made the access, or before the instruction for `Execute`.
+pub struct Watched<B: Bus> {
+    pub inner: B,
+    watchpoints: Vec<Option<Watchpoint>>,
+    hit: Option<WatchpointHit>,
+    resume_address: Option<Word>,
+}
+
+impl<B: Bus> Watched<B> {
+    pub fn new(inner: B) -> Watched<B> {
+        Watched {
+            inner,
+            watchpoints: Vec::new(),
+            hit: None,
+            resume_address: None,
+        }
+    }
+
+    /// Adds a watchpoint and returns the id hits report it by.
+    pub fn add(&mut self, watchpoint: Watchpoint) -> usize {
+        self.watchpoints.push(Some(watchpoint));
+        self.watchpoints.len() - 1
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<Watchpoint> {
+        self.watchpoints.get_mut(id)?.take()
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Watchpoint> {
+        self.watchpoints.get(id)?.as_ref()
+    }
+
+    fn check(&mut self, kind: WatchKind, address: Word, old_value: Byte, new_value: Byte) {
+        if self.hit.is_some() {
+            return;
+        }
+        let found = self.watchpoints.iter().position(|watchpoint| {
+            watchpoint
+                .as_ref()
+                .is_some_and(|watchpoint| watchpoint.matches(kind, address, new_value))
+        });
+        if let Some(watchpoint) = found {
+            self.hit = Some(WatchpointHit {
+                watchpoint,
+                kind,
+                address,
+                old_value,
+                new_value,
+            });
+        }
+    }
+}
+
+impl<B: Bus> Bus for Watched<B> {
+    fn read(&mut self, address: Word) -> Byte {
+        let value = self.inner.read(address);
+        self.check(WatchKind::Read, address, value, value);
+        value
+    }
+
+    fn write(&mut self, address: Word, value: Byte) {
+        let old_value = self.inner.peek(address);
+        self.inner.write(address, value);
+        self.check(WatchKind::Write, address, old_value, value);
+    }
+
+    fn peek(&self, address: Word) -> Byte {
+        self.inner.peek(address)
+    }
+
+    /// The fetch right after an execute hit at the same address is let through, so
+    /// running again continues past the watchpoint instead of stopping on it forever.
+    fn fetch(&mut self, address: Word) -> Byte {
+        let value = self.inner.fetch(address);
+        if self.resume_address.take() != Some(address) {
+            self.check(WatchKind::Execute, address, value, value);
+        }
+        value
+    }
+
+    fn take_fault(&mut self) -> Option<BusFault> {
+        match self.hit.take() {
+            Some(hit) => {
+                if hit.kind == WatchKind::Execute {
+                    self.resume_address = Some(hit.address);
+                }
+                Some(BusFault::Watchpoint(hit))
+            }
+            None => self.inner.take_fault(),
+        }
+    }
+}